@@ -0,0 +1,146 @@
+//! Shell completion scripts and subcommand suggestions
+//!
+//! `nexus completions <shell>` prints the clap-generated script for a shell.
+//! For bash and fish the script also completes plugin names for
+//! `nexus plugin inspect|enable|disable`, by calling
+//! `nexus plugin list --names`. That prints nothing when there is no
+//! workspace, so completion stays quick outside one.
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+use crate::Cli;
+
+/// Command printing the plugin names to offer
+const PLUGIN_NAMES_COMMAND: &str = "nexus plugin list --names";
+
+/// Plugin subcommands whose argument is a plugin name
+const PLUGIN_NAME_COMMANDS: [&str; 3] = ["inspect", "enable", "disable"];
+
+/// Write the completion script for `shell`
+pub fn write_completions(shell: Shell, out: &mut dyn Write) -> io::Result<()> {
+    clap_complete::generate(shell, &mut Cli::command(), "nexus", out);
+    match shell {
+        Shell::Bash => write!(out, "{}", bash_hook()),
+        Shell::Fish => write!(out, "{}", fish_hook()),
+        _ => Ok(()),
+    }
+}
+
+/// Wraps the generated `_nexus` function to offer plugin names
+fn bash_hook() -> String {
+    format!(
+        r#"
+# Offer the plugins of the current workspace where a plugin name is expected
+_nexus_dynamic() {{
+    if [[ ${{COMP_CWORD}} -eq 3 && "${{COMP_WORDS[1]}}" == "plugin" ]]; then
+        case "${{COMP_WORDS[2]}}" in
+            {commands})
+                COMPREPLY=($(compgen -W "$({names} 2>/dev/null)" -- "${{COMP_WORDS[COMP_CWORD]}}"))
+                return 0
+                ;;
+        esac
+    fi
+    _nexus "$@"
+}}
+
+if [[ "${{BASH_VERSINFO[0]}}" -eq 4 && "${{BASH_VERSINFO[1]}}" -ge 4 || "${{BASH_VERSINFO[0]}}" -gt 4 ]]; then
+    complete -F _nexus_dynamic -o nosort -o bashdefault -o default nexus
+else
+    complete -F _nexus_dynamic -o bashdefault -o default nexus
+fi
+"#,
+        commands = PLUGIN_NAME_COMMANDS.join("|"),
+        names = PLUGIN_NAMES_COMMAND,
+    )
+}
+
+/// Adds plugin names to the generated fish completions
+fn fish_hook() -> String {
+    format!(
+        "\n# Offer the plugins of the current workspace where a plugin name is expected\n\
+         complete -c nexus -n \"__fish_seen_subcommand_from plugin; and __fish_seen_subcommand_from {}\" \
+         -f -a \"({} 2>/dev/null)\"\n",
+        PLUGIN_NAME_COMMANDS.join(" "),
+        PLUGIN_NAMES_COMMAND,
+    )
+}
+
+/// Subcommands close to the first unknown one in `args`, best first
+///
+/// `args` is the command line without the program name. Known subcommands
+/// are followed down the tree, so `nexus plugin inspcet` is matched against
+/// the plugin subcommands only.
+pub fn suggest_subcommand(args: &[String]) -> Vec<String> {
+    let root = Cli::command();
+    let mut command = root.clone();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(flag) = arg.strip_prefix("--") {
+            // Skip the value of `--set KEY=VALUE` and the like
+            if !flag.contains('=') && takes_value(&[&command, &root], flag) {
+                args.next();
+            }
+            continue;
+        }
+        if arg.starts_with('-') {
+            continue;
+        }
+        if !command.has_subcommands() {
+            break;
+        }
+        let Some(next) = command.find_subcommand(arg).cloned() else {
+            let known: Vec<&str> = command
+                .get_subcommands()
+                .map(clap::Command::get_name)
+                .collect();
+            return nexus_core::suggest::suggest(arg, known);
+        };
+        command = next;
+    }
+    Vec::new()
+}
+
+/// Whether the long option `--<long>` of any of `commands` takes a value
+fn takes_value(commands: &[&clap::Command], long: &str) -> bool {
+    commands
+        .iter()
+        .flat_map(|command| command.get_arguments())
+        .find(|arg| arg.get_long() == Some(long))
+        .is_some_and(|arg| arg.get_action().takes_values())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(shell: Shell) -> String {
+        let mut out = Vec::new();
+        write_completions(shell, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn scripts_complete_plugin_names() {
+        let bash = script(Shell::Bash);
+        assert!(bash.contains("_nexus() {"));
+        assert!(bash.contains("inspect|enable|disable)"));
+        assert!(bash.contains("$(nexus plugin list --names 2>/dev/null)"));
+        assert!(bash.contains("complete -F _nexus_dynamic"));
+
+        let fish = script(Shell::Fish);
+        assert!(fish.contains("-a \"(nexus plugin list --names 2>/dev/null)\""));
+        assert!(!script(Shell::Zsh).contains(PLUGIN_NAMES_COMMAND));
+    }
+
+    #[test]
+    fn mistyped_subcommands_get_suggestions() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(suggest_subcommand(&args("verison")), vec!["version"]);
+        assert_eq!(suggest_subcommand(&args("--set a=b plugin inspcet x")), vec!["inspect"]);
+        assert!(suggest_subcommand(&args("plugin list")).is_empty());
+        assert!(suggest_subcommand(&args("plugin inspect verison")).is_empty());
+        assert!(suggest_subcommand(&args("frobnicate")).is_empty());
+    }
+}
@@ -0,0 +1,454 @@
+//! Translated CLI messages
+//!
+//! Messages are looked up by a stable id such as `init.done` in catalogs
+//! compiled into the binary, one per [`Lang`]. The language comes from
+//! `--lang`, then `NEXUS_LANG`, then the system locale (`LC_ALL`,
+//! `LC_MESSAGES`, `LANG`), and is English when none of them names a
+//! supported one. A message missing from a catalog falls back to English and
+//! is traced at debug level with its id; the CLI installs no subscriber, so
+//! the `catalogs_have_the_same_ids` test is what catches such gaps.
+//!
+//! Messages take named arguments written `{name}`. The `Display` impls of
+//! the core errors stay English for logs; [`localized_message`] renders them
+//! for people.
+
+use clap::ValueEnum;
+use nexus_core::error::{AgentError, NexusError};
+use std::fmt::{self, Write};
+
+/// Languages with a catalog
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// English
+    #[default]
+    En,
+    /// Romanian
+    Ro,
+}
+
+impl Lang {
+    /// The language to use: `flag`, then `NEXUS_LANG`, then the system locale
+    pub fn detect(flag: Option<Self>) -> Self {
+        flag.unwrap_or_else(|| Self::from_env(|name| std::env::var(name).ok()))
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(value) = var("NEXUS_LANG") {
+            if let Some(lang) = Self::from_locale(&value) {
+                return lang;
+            }
+            tracing::debug!(value, "NEXUS_LANG names no supported language");
+        }
+        // As in POSIX, the first locale variable that is set decides
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|name| var(name).filter(|value| !value.is_empty()))
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// The language of a locale such as `ro_RO.UTF-8`, `ro-RO` or `en`
+    fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale.split(['_', '-', '.', '@']).next()?;
+        <Self as ValueEnum>::from_str(language, true).ok()
+    }
+
+    const fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => EN,
+            Self::Ro => RO,
+        }
+    }
+}
+
+/// The `--lang` value among the command-line arguments, before clap parses them
+///
+/// Help is rendered while parsing, so the language has to be known first.
+/// Invalid values are left for clap to report.
+pub fn lang_from_args(args: impl IntoIterator<Item = String>) -> Option<Lang> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--lang") {
+            Some("") => args.next()?,
+            Some(rest) => match rest.strip_prefix('=') {
+                Some(value) => value.to_string(),
+                None => continue,
+            },
+            None if arg == "--" => return None,
+            None => continue,
+        };
+        return <Lang as ValueEnum>::from_str(&value, true).ok();
+    }
+    None
+}
+
+/// The message `id` in `lang`, with its `{name}` placeholders replaced by `args`
+///
+/// Placeholders without an argument are kept as they are.
+pub fn tr(lang: Lang, id: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut rest = lookup(lang, id);
+    let mut message = String::with_capacity(rest.len());
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after.find('}').and_then(|end| {
+            args.iter()
+                .find(|(name, _)| *name == &after[..end])
+                .map(|(_, value)| (end, value))
+        });
+        match arg {
+            Some((end, value)) => {
+                let _ = write!(message, "{}", value);
+                rest = &after[end + 1..];
+            },
+            None => {
+                message.push('{');
+                rest = after;
+            },
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+/// The template of `id`, falling back to English and then to the id itself
+fn lookup(lang: Lang, id: &str) -> &str {
+    let find = |lang: Lang| {
+        lang.catalog()
+            .iter()
+            .find(|(key, _)| *key == id)
+            .map(|(_, message)| *message)
+    };
+    find(lang)
+        .or_else(|| {
+            tracing::debug!(key = id, ?lang, "message missing from the catalog");
+            find(Lang::En)
+        })
+        .unwrap_or(id)
+}
+
+/// `error` for people reading `lang`
+pub fn localized_message(error: &NexusError, lang: Lang) -> String {
+    let agent;
+    let (id, detail): (&str, &dyn fmt::Display) = match error {
+        NexusError::Agent(error) => {
+            agent = localized_agent_message(error, lang);
+            ("error.agent", &agent)
+        },
+        NexusError::Config(message) => ("error.config", message),
+        NexusError::Io(error) => ("error.io", error),
+        NexusError::Security(error) => ("error.security", error),
+        NexusError::Plugin(error) => ("error.plugin", error),
+        NexusError::Network(message) => ("error.network", message),
+        NexusError::Serialization(error) => ("error.serialization", error),
+        NexusError::Internal(message) => ("error.internal", message),
+        NexusError::External(error) => ("error.external", error),
+    };
+    tr(lang, id, &[("detail", detail)])
+}
+
+/// `error` for people reading `lang`
+pub fn localized_agent_message(error: &AgentError, lang: Lang) -> String {
+    let (id, detail) = match error {
+        AgentError::ExecutionFailed(detail) => ("error.agent.execution_failed", detail),
+        AgentError::ConfigurationInvalid(detail) => ("error.agent.configuration_invalid", detail),
+        AgentError::ResourceUnavailable(detail) => ("error.agent.resource_unavailable", detail),
+        AgentError::PermissionDenied(detail) => ("error.agent.permission_denied", detail),
+        AgentError::Timeout(detail) => ("error.agent.timeout", detail),
+        AgentError::SecurityViolation(detail) => ("error.agent.security_violation", detail),
+        AgentError::ResourceLimitExceeded(detail) => {
+            ("error.agent.resource_limit_exceeded", detail)
+        },
+        AgentError::NotFound(detail) => ("error.agent.not_found", detail),
+        AgentError::AlreadyExists(detail) => ("error.agent.already_exists", detail),
+        AgentError::InitializationFailed(detail) => ("error.agent.initialization_failed", detail),
+    };
+    tr(lang, id, &[("detail", detail)])
+}
+
+/// English messages; every other catalog has the same ids
+const EN: &[(&str, &str)] = &[
+    ("help.about", "NEXUS - The Living Terminal"),
+    ("help.long_about", "A revolutionary CLI tool combining AI Agents, Web3, and intelligent workflows"),
+    ("help.version", "Show version and system information"),
+    ("help.init", "Initialize NEXUS configuration and directories"),
+    ("help.agent", "Agent management commands"),
+    ("help.config", "Configuration inspection commands"),
+    ("help.crash", "Inspect crash reports from previous runs"),
+    ("help.plugin", "Plugin inspection commands"),
+    ("help.status", "Report the health of every subsystem; exits non-zero when unhealthy"),
+    ("help.completions", "Print a shell completion script"),
+    ("banner.tagline", "The Living Terminal"),
+    ("banner.features", "AI Agents • Web3 • Intelligent Workflows"),
+    ("banner.version", "NEXUS Version: "),
+    ("banner.system", "System Information:"),
+    ("banner.rustc", "Rust Compiler: {version}"),
+    ("banner.target", "Target Triple: {target}"),
+    ("banner.os", "OS: {os} {family}"),
+    ("banner.members", "Workspace Members:"),
+    ("banner.member.cli", "nexus-cli (binary)"),
+    ("banner.member.core", "nexus-core (library)"),
+    ("banner.member.plugins", "plugins/example (future)"),
+    ("crash.notice", "NEXUS crashed during a previous run: {message} (run `nexus crash show {id}` for details)"),
+    ("crash.none", "No crash reports in {dir}"),
+    ("crash.deleted", "Deleted crash report {id}"),
+    ("crash.deleted_all", "Deleted {count} crash reports"),
+    ("config.showing_defaults", "No configuration file found, showing defaults"),
+    ("config.none", "No configuration file found"),
+    ("config.not_found", "Config file not found: {path}"),
+    ("config.valid", "{path} is valid"),
+    ("config.invalid", "{path} has {count} error(s)"),
+    ("init.needs_terminal", "--interactive needs a terminal on stdin; using defaults"),
+    ("init.start", "Initializing NEXUS workspace..."),
+    ("init.created", "Created: {path}"),
+    ("init.exists", "Exists: {path}"),
+    ("init.overwrote", "Overwrote: {path}"),
+    ("init.done", "NEXUS workspace initialized successfully!"),
+    ("onboarding.wrote", "Wrote {path}"),
+    ("onboarding.next_steps", "Next steps:"),
+    ("onboarding.step.review", "Review {path} and adjust anything you like"),
+    ("onboarding.step.plugins", "Drop plugins into {dir}"),
+    ("onboarding.step.api_key", "Export {variable} with your API key"),
+    ("onboarding.step.show", "Run `nexus config show` to see the effective configuration"),
+    ("agent.coming_soon", "Agent management coming soon..."),
+    ("agent.tip", "Tip: Use 'nexus agent run --dry' to simulate agent execution"),
+    ("plugin.none", "No plugins loaded"),
+    ("plugin.name", "NAME"),
+    ("plugin.version", "VERSION"),
+    ("plugin.author", "AUTHOR"),
+    ("plugin.health", "HEALTH"),
+    ("plugin.restarted_once", "restarted once"),
+    ("plugin.restarted", "restarted {count} times"),
+    ("plugin.unloaded", "Unloaded after failing health checks:"),
+    ("plugin.gave_up", "{name}: gave up after {count} restarts"),
+    ("plugin.skipped", "Not loaded:"),
+    ("plugin.inspect.name", "Name"),
+    ("plugin.inspect.version", "Version"),
+    ("plugin.inspect.description", "Description"),
+    ("plugin.inspect.author", "Author"),
+    ("plugin.inspect.requires", "Requires NEXUS"),
+    ("plugin.inspect.dependencies", "Dependencies"),
+    ("plugin.inspect.dependents", "Dependents"),
+    ("plugin.inspect.permissions", "Permissions"),
+    ("plugin.inspect.signed", "Signed"),
+    ("plugin.inspect.path", "Path"),
+    ("plugin.inspect.none", "none"),
+    ("plugin.inspect.yes", "yes"),
+    ("plugin.inspect.no", "no"),
+    ("plugin.inspect.built_in", "built in"),
+    ("plugin.signed", "{name} {version} is signed by trusted publisher '{publisher}'"),
+    ("plugin.wrote", "Wrote {path}"),
+    ("plugin.verified", "Loaded plugins match {path}"),
+    ("plugin.no_config", "no configuration file found; run `nexus init` first"),
+    ("plugin.disabled", "Plugin '{name}' disabled in {path}"),
+    ("plugin.enabled", "Plugin '{name}' enabled in {path}"),
+    ("plugin.already_disabled", "Plugin '{name}' is already disabled"),
+    ("plugin.already_enabled", "Plugin '{name}' is already enabled"),
+    ("plugin.dependents", "These plugins depend on '{name}': {dependents}"),
+    ("status.component", "COMPONENT"),
+    ("status.status", "STATUS"),
+    ("status.reason", "REASON"),
+    ("status.overall", "overall: {status}"),
+    ("status.healthy", "healthy"),
+    ("status.degraded", "degraded"),
+    ("status.unhealthy", "unhealthy"),
+    ("status.failed", "NEXUS is unhealthy"),
+    ("error.prefix", "Error: {message}"),
+    ("error.agent", "Agent error: {detail}"),
+    ("error.config", "Configuration error: {detail}"),
+    ("error.io", "I/O error: {detail}"),
+    ("error.security", "Security error: {detail}"),
+    ("error.plugin", "Plugin error: {detail}"),
+    ("error.network", "Network error: {detail}"),
+    ("error.serialization", "Serialization error: {detail}"),
+    ("error.internal", "Internal error: {detail}"),
+    ("error.external", "External error: {detail}"),
+    ("error.agent.execution_failed", "Agent execution failed: {detail}"),
+    ("error.agent.configuration_invalid", "Agent configuration invalid: {detail}"),
+    ("error.agent.resource_unavailable", "Agent resource unavailable: {detail}"),
+    ("error.agent.permission_denied", "Agent permission denied: {detail}"),
+    ("error.agent.timeout", "Agent timeout: {detail}"),
+    ("error.agent.security_violation", "Agent security violation: {detail}"),
+    ("error.agent.resource_limit_exceeded", "Agent resource limit exceeded: {detail}"),
+    ("error.agent.not_found", "Agent not found: {detail}"),
+    ("error.agent.already_exists", "Agent already exists: {detail}"),
+    ("error.agent.initialization_failed", "Agent initialization failed: {detail}"),
+];
+
+/// Romanian messages
+const RO: &[(&str, &str)] = &[
+    ("help.about", "NEXUS - Terminalul Viu"),
+    ("help.long_about", "Un instrument CLI care îmbină agenți AI, Web3 și fluxuri de lucru inteligente"),
+    ("help.version", "Afișează versiunea și informații despre sistem"),
+    ("help.init", "Inițializează configurația și directoarele NEXUS"),
+    ("help.agent", "Comenzi pentru gestionarea agenților"),
+    ("help.config", "Comenzi pentru inspectarea configurației"),
+    ("help.crash", "Inspectează rapoartele de eroare de la rulările anterioare"),
+    ("help.plugin", "Comenzi pentru inspectarea pluginurilor"),
+    ("help.status", "Raportează starea fiecărui subsistem; iese cu cod nenul când NEXUS nu funcționează"),
+    ("help.completions", "Afișează un script de completare pentru shell"),
+    ("banner.tagline", "Terminalul Viu"),
+    ("banner.features", "Agenți AI • Web3 • Fluxuri de lucru inteligente"),
+    ("banner.version", "Versiune NEXUS: "),
+    ("banner.system", "Informații despre sistem:"),
+    ("banner.rustc", "Compilator Rust: {version}"),
+    ("banner.target", "Arhitectură țintă: {target}"),
+    ("banner.os", "Sistem de operare: {os} {family}"),
+    ("banner.members", "Membrii spațiului de lucru:"),
+    ("banner.member.cli", "nexus-cli (executabil)"),
+    ("banner.member.core", "nexus-core (bibliotecă)"),
+    ("banner.member.plugins", "plugins/example (în viitor)"),
+    ("crash.notice", "NEXUS s-a oprit neașteptat la o rulare anterioară: {message} (rulați `nexus crash show {id}` pentru detalii)"),
+    ("crash.none", "Nu există rapoarte de eroare în {dir}"),
+    ("crash.deleted", "Raportul de eroare {id} a fost șters"),
+    ("crash.deleted_all", "Rapoarte de eroare șterse: {count}"),
+    ("config.showing_defaults", "Nu a fost găsit niciun fișier de configurație; se afișează valorile implicite"),
+    ("config.none", "Nu a fost găsit niciun fișier de configurație"),
+    ("config.not_found", "Fișierul de configurație nu a fost găsit: {path}"),
+    ("config.valid", "{path} este valid"),
+    ("config.invalid", "{path} are erori: {count}"),
+    ("init.needs_terminal", "--interactive are nevoie de un terminal la intrarea standard; se folosesc valorile implicite"),
+    ("init.start", "Se inițializează spațiul de lucru NEXUS..."),
+    ("init.created", "Creat: {path}"),
+    ("init.exists", "Există: {path}"),
+    ("init.overwrote", "Suprascris: {path}"),
+    ("init.done", "Spațiul de lucru NEXUS a fost inițializat cu succes!"),
+    ("onboarding.wrote", "Scris: {path}"),
+    ("onboarding.next_steps", "Pașii următori:"),
+    ("onboarding.step.review", "Verificați {path} și ajustați ce doriți"),
+    ("onboarding.step.plugins", "Puneți pluginurile în {dir}"),
+    ("onboarding.step.api_key", "Exportați {variable} cu cheia dumneavoastră API"),
+    ("onboarding.step.show", "Rulați `nexus config show` pentru a vedea configurația efectivă"),
+    ("agent.coming_soon", "Gestionarea agenților va fi disponibilă în curând..."),
+    ("agent.tip", "Sfat: folosiți 'nexus agent run --dry' pentru a simula execuția agenților"),
+    ("plugin.none", "Niciun plugin încărcat"),
+    ("plugin.name", "NUME"),
+    ("plugin.version", "VERSIUNE"),
+    ("plugin.author", "AUTOR"),
+    ("plugin.health", "STARE"),
+    ("plugin.restarted_once", "repornit o dată"),
+    ("plugin.restarted", "repornit de {count} ori"),
+    ("plugin.unloaded", "Descărcate după verificări de stare eșuate:"),
+    ("plugin.gave_up", "{name}: abandonat după {count} reporniri"),
+    ("plugin.skipped", "Neîncărcate:"),
+    ("plugin.inspect.name", "Nume"),
+    ("plugin.inspect.version", "Versiune"),
+    ("plugin.inspect.description", "Descriere"),
+    ("plugin.inspect.author", "Autor"),
+    ("plugin.inspect.requires", "Necesită NEXUS"),
+    ("plugin.inspect.dependencies", "Dependențe"),
+    ("plugin.inspect.dependents", "Dependenți"),
+    ("plugin.inspect.permissions", "Permisiuni"),
+    ("plugin.inspect.signed", "Semnat"),
+    ("plugin.inspect.path", "Cale"),
+    ("plugin.inspect.none", "niciuna"),
+    ("plugin.inspect.yes", "da"),
+    ("plugin.inspect.no", "nu"),
+    ("plugin.inspect.built_in", "încorporat"),
+    ("plugin.signed", "{name} {version} este semnat de editorul de încredere '{publisher}'"),
+    ("plugin.wrote", "Scris: {path}"),
+    ("plugin.verified", "Pluginurile încărcate corespund cu {path}"),
+    ("plugin.no_config", "nu a fost găsit niciun fișier de configurație; rulați mai întâi `nexus init`"),
+    ("plugin.disabled", "Pluginul '{name}' a fost dezactivat în {path}"),
+    ("plugin.enabled", "Pluginul '{name}' a fost activat în {path}"),
+    ("plugin.already_disabled", "Pluginul '{name}' este deja dezactivat"),
+    ("plugin.already_enabled", "Pluginul '{name}' este deja activat"),
+    ("plugin.dependents", "Aceste pluginuri depind de '{name}': {dependents}"),
+    ("status.component", "COMPONENTĂ"),
+    ("status.status", "STARE"),
+    ("status.reason", "MOTIV"),
+    ("status.overall", "general: {status}"),
+    ("status.healthy", "funcțional"),
+    ("status.degraded", "degradat"),
+    ("status.unhealthy", "nefuncțional"),
+    ("status.failed", "NEXUS nu funcționează"),
+    ("error.prefix", "Eroare: {message}"),
+    ("error.agent", "Eroare de agent: {detail}"),
+    ("error.config", "Eroare de configurație: {detail}"),
+    ("error.io", "Eroare de intrare/ieșire: {detail}"),
+    ("error.security", "Eroare de securitate: {detail}"),
+    ("error.plugin", "Eroare de plugin: {detail}"),
+    ("error.network", "Eroare de rețea: {detail}"),
+    ("error.serialization", "Eroare de serializare: {detail}"),
+    ("error.internal", "Eroare internă: {detail}"),
+    ("error.external", "Eroare externă: {detail}"),
+    ("error.agent.execution_failed", "Execuția agentului a eșuat: {detail}"),
+    ("error.agent.configuration_invalid", "Configurația agentului este invalidă: {detail}"),
+    ("error.agent.resource_unavailable", "Resursă indisponibilă pentru agent: {detail}"),
+    ("error.agent.permission_denied", "Permisiune refuzată agentului: {detail}"),
+    ("error.agent.timeout", "Timpul agentului a expirat: {detail}"),
+    ("error.agent.security_violation", "Încălcare de securitate a agentului: {detail}"),
+    ("error.agent.resource_limit_exceeded", "Limita de resurse a agentului a fost depășită: {detail}"),
+    ("error.agent.not_found", "Agentul nu a fost găsit: {detail}"),
+    ("error.agent.already_exists", "Agentul există deja: {detail}"),
+    ("error.agent.initialization_failed", "Inițializarea agentului a eșuat: {detail}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn ids(catalog: &[(&'static str, &str)]) -> BTreeSet<&'static str> {
+        let ids: BTreeSet<_> = catalog.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids.len(), catalog.len(), "duplicate message id");
+        ids
+    }
+
+    #[test]
+    fn catalogs_have_the_same_ids() {
+        assert_eq!(ids(EN), ids(RO));
+    }
+
+    #[test]
+    fn messages_take_arguments() {
+        let path = std::path::Path::new("nexus/logs");
+        assert_eq!(tr(Lang::Ro, "init.created", &[("path", &path.display())]), "Creat: nexus/logs");
+        assert_eq!(
+            tr(Lang::En, "crash.notice", &[("message", &"boom"), ("id", &7)]),
+            "NEXUS crashed during a previous run: boom (run `nexus crash show 7` for details)"
+        );
+        assert_eq!(tr(Lang::En, "init.exists", &[]), lookup(Lang::En, "init.exists"));
+        assert_eq!(tr(Lang::Ro, "no.such.message", &[]), "no.such.message");
+    }
+
+    #[test]
+    fn english_errors_read_like_their_display() {
+        let errors = [
+            NexusError::Agent(AgentError::Timeout("30s".to_string())),
+            NexusError::Agent(AgentError::ResourceLimitExceeded("memory".to_string())),
+            NexusError::Config("missing [agent]".to_string()),
+            NexusError::Io(std::io::Error::other("disk full")),
+            NexusError::External(anyhow::anyhow!("upstream")),
+        ];
+        for error in &errors {
+            assert_eq!(localized_message(error, Lang::En), error.to_string());
+        }
+        assert_eq!(
+            localized_message(&errors[0], Lang::Ro),
+            "Eroare de agent: Timpul agentului a expirat: 30s"
+        );
+    }
+
+    #[test]
+    fn language_comes_from_the_flag_then_the_environment() {
+        let env = |vars: &'static [(&str, &str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| (*value).to_string())
+            }
+        };
+        assert_eq!(Lang::from_env(env(&[("LANG", "ro_RO.UTF-8")])), Lang::Ro);
+        assert_eq!(Lang::from_env(env(&[("NEXUS_LANG", "en"), ("LANG", "ro_RO")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[("NEXUS_LANG", "xx"), ("LC_ALL", "ro")])), Lang::Ro);
+        assert_eq!(Lang::from_env(env(&[("LC_ALL", "C"), ("LANG", "ro_RO")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[])), Lang::En);
+
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(lang_from_args(args("--lang ro status")), Some(Lang::Ro));
+        assert_eq!(lang_from_args(args("status --lang=EN")), Some(Lang::En));
+        assert_eq!(lang_from_args(args("--language ro")), None);
+        assert_eq!(lang_from_args(args("-- --lang ro")), None);
+    }
+}
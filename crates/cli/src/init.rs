@@ -0,0 +1,314 @@
+//! Non-interactive workspace initialization
+//!
+//! `nexus init` lays out a `nexus/` workspace (data, logs and plugin
+//! directories plus a `nexus.toml` pointing at them). Running it again is
+//! safe: existing directories are kept and an existing config is only
+//! replaced with `--force`. When a step fails, the error names the step.
+
+use nexus_core::config::{Config, ConfigLoader};
+use std::fmt;
+
+use crate::i18n::{tr, Lang};
+use std::path::{Path, PathBuf};
+
+/// Name of the workspace directory created under the target directory
+pub const WORKSPACE_DIR: &str = "nexus";
+
+/// Name of the configuration file inside the workspace
+pub const CONFIG_FILE: &str = "nexus.toml";
+
+/// Subdirectories created inside the workspace
+const SUBDIRS: [&str; 3] = ["data", "logs", "plugins"];
+
+/// Options of `nexus init`
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    /// Directory to create the workspace in
+    pub path: PathBuf,
+    /// Overwrite an existing configuration file
+    pub force: bool,
+    /// Write only the values that differ from the defaults
+    pub minimal: bool,
+}
+
+/// What happened to one path during initialization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The directory was created
+    CreatedDir(PathBuf),
+    /// The directory was already there
+    ExistingDir(PathBuf),
+    /// The configuration file was written
+    WroteConfig(PathBuf),
+    /// The configuration file was replaced
+    OverwroteConfig(PathBuf),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreatedDir(path) => write!(f, "📁 Created: {}", path.display()),
+            Self::ExistingDir(path) => write!(f, "📁 Exists: {}", path.display()),
+            Self::WroteConfig(path) => write!(f, "📄 Created: {}", path.display()),
+            Self::OverwroteConfig(path) => write!(f, "📄 Overwrote: {}", path.display()),
+        }
+    }
+}
+
+impl Outcome {
+    /// The outcome for people reading `lang`
+    pub fn localized(&self, lang: Lang) -> String {
+        let (icon, id, path) = match self {
+            Self::CreatedDir(path) => ("📁", "init.created", path),
+            Self::ExistingDir(path) => ("📁", "init.exists", path),
+            Self::WroteConfig(path) => ("📄", "init.created", path),
+            Self::OverwroteConfig(path) => ("📄", "init.overwrote", path),
+        };
+        format!("{} {}", icon, tr(lang, id, &[("path", &path.display())]))
+    }
+}
+
+/// A failed initialization step
+#[derive(Debug)]
+pub struct InitError {
+    /// Step that failed, e.g. "create ./nexus/logs"
+    pub step: String,
+    /// Why it failed
+    pub reason: String,
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "init failed to {}: {}", self.step, self.reason)
+    }
+}
+
+impl std::error::Error for InitError {}
+
+fn step_error(step: impl Into<String>) -> impl FnOnce(String) -> InitError {
+    let step = step.into();
+    move |reason| InitError { step, reason }
+}
+
+/// Configuration file of the workspace created in `dir`, by either init path
+pub fn config_path(dir: &Path) -> PathBuf {
+    dir.join(WORKSPACE_DIR).join(CONFIG_FILE)
+}
+
+/// Refuse to replace the configuration at `config_path` unless `force`
+///
+/// Returns whether an existing file is being replaced.
+pub fn check_overwrite(config_path: &Path, force: bool) -> Result<bool, InitError> {
+    let replacing = config_path.exists();
+    if replacing && !force {
+        return Err(InitError {
+            step: format!("write {}", config_path.display()),
+            reason: "the file already exists (use --force to overwrite it)".to_string(),
+        });
+    }
+    Ok(replacing)
+}
+
+/// Create the workspace, returning what was done in order
+pub fn run(options: &InitOptions) -> Result<Vec<Outcome>, InitError> {
+    let workspace = options.path.join(WORKSPACE_DIR);
+    let config_path = config_path(&options.path);
+    let replacing = check_overwrite(&config_path, options.force)?;
+
+    let mut outcomes = Vec::new();
+    for dir in
+        std::iter::once(workspace.clone()).chain(SUBDIRS.iter().map(|sub| workspace.join(sub)))
+    {
+        if dir.is_dir() {
+            outcomes.push(Outcome::ExistingDir(dir));
+            continue;
+        }
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| e.to_string())
+            .map_err(step_error(format!("create {}", dir.display())))?;
+        outcomes.push(Outcome::CreatedDir(dir));
+    }
+
+    let config = workspace_config(&workspace)
+        .map_err(step_error(format!("resolve {}", workspace.display())))?;
+    let write = step_error(format!("write {}", config_path.display()));
+    if options.minimal {
+        let content = render_minimal(&config)
+            .map_err(|e| e.to_string())
+            .map_err(step_error("render the minimal configuration"))?;
+        std::fs::write(&config_path, content)
+            .map_err(|e| e.to_string())
+            .map_err(write)?;
+    } else {
+        ConfigLoader::new()
+            .save_to_file(&config, &config_path)
+            .map_err(|e| format!("{:#}", e))
+            .map_err(write)?;
+    }
+
+    outcomes.push(if replacing {
+        Outcome::OverwroteConfig(config_path)
+    } else {
+        Outcome::WroteConfig(config_path)
+    });
+    Ok(outcomes)
+}
+
+/// Default configuration with its directories pointing into the workspace
+fn workspace_config(workspace: &Path) -> Result<Config, String> {
+    // Absolute paths, so the config works no matter where nexus is run from
+    let workspace = workspace.canonicalize().map_err(|e| e.to_string())?;
+
+    let mut config = Config::default();
+    config.agent.data_dir = workspace.join("data");
+    config.plugin.plugin_dirs = vec![workspace.join("plugins")];
+    config.logging.log_file_path = Some(workspace.join("logs").join("nexus.log"));
+    Ok(config)
+}
+
+/// Render only the values of `config` that differ from the defaults
+fn render_minimal(config: &Config) -> Result<String, toml::ser::Error> {
+    let current = toml::Table::try_from(config)?;
+    let defaults = toml::Table::try_from(Config::default())?;
+
+    let mut out = String::from(
+        "# NEXUS configuration\n\
+         #\n\
+         # Only values that differ from the built-in defaults are listed here.\n\
+         # Run `nexus config show` to see the full effective configuration,\n\
+         # and copy any setting from there to change it.\n\n",
+    );
+    out.push_str(&toml::to_string_pretty(&non_default(&current, &defaults))?);
+    Ok(out)
+}
+
+/// Entries of `current` that are absent from or different in `defaults`
+fn non_default(current: &toml::Table, defaults: &toml::Table) -> toml::Table {
+    let mut changed = toml::Table::new();
+    for (key, value) in current {
+        match (value, defaults.get(key)) {
+            (toml::Value::Table(table), Some(toml::Value::Table(default))) => {
+                let nested = non_default(table, default);
+                if !nested.is_empty() {
+                    changed.insert(key.clone(), toml::Value::Table(nested));
+                }
+            }
+            (value, Some(default)) if value == default => {}
+            (value, _) => {
+                changed.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn options(dir: &TempDir) -> InitOptions {
+        InitOptions {
+            path: dir.path().to_path_buf(),
+            ..InitOptions::default()
+        }
+    }
+
+    #[test]
+    fn creates_workspace_and_round_trips_config() {
+        let dir = TempDir::new().unwrap();
+        let outcomes = run(&options(&dir)).unwrap();
+
+        let workspace = dir.path().join(WORKSPACE_DIR);
+        for sub in SUBDIRS {
+            assert!(workspace.join(sub).is_dir());
+        }
+        assert_eq!(outcomes.len(), 5);
+        assert!(outcomes[..4]
+            .iter()
+            .all(|o| matches!(o, Outcome::CreatedDir(_))));
+        assert!(matches!(outcomes[4], Outcome::WroteConfig(_)));
+
+        let config = ConfigLoader::new()
+            .load_from_file(&workspace.join(CONFIG_FILE))
+            .unwrap();
+        assert_eq!(
+            config.agent.data_dir,
+            workspace.canonicalize().unwrap().join("data")
+        );
+    }
+
+    #[test]
+    fn config_is_found_from_the_init_directory() {
+        let dir = TempDir::new().unwrap();
+        run(&options(&dir)).unwrap();
+
+        // What `nexus config show` run from the same directory loads
+        let loader = ConfigLoader::in_dir(dir.path());
+        let workspace = dir.path().join(WORKSPACE_DIR);
+        assert_eq!(loader.find_config_file(), Some(workspace.join(CONFIG_FILE)));
+        let config = loader.load().unwrap();
+        assert_eq!(
+            config.agent.data_dir,
+            workspace.canonicalize().unwrap().join("data")
+        );
+    }
+
+    #[test]
+    fn refuses_to_overwrite_without_force() {
+        let dir = TempDir::new().unwrap();
+        run(&options(&dir)).unwrap();
+
+        let error = run(&options(&dir)).unwrap_err();
+        assert!(error.step.ends_with(CONFIG_FILE));
+        assert!(error.to_string().contains("--force"));
+
+        // Idempotent: existing directories are kept, the config is replaced
+        let outcomes = run(&InitOptions {
+            force: true,
+            ..options(&dir)
+        })
+        .unwrap();
+        assert!(matches!(outcomes[1], Outcome::ExistingDir(_)));
+        assert!(matches!(outcomes.last(), Some(Outcome::OverwroteConfig(_))));
+    }
+
+    #[test]
+    fn minimal_config_lists_only_overrides() {
+        let dir = TempDir::new().unwrap();
+        run(&InitOptions {
+            minimal: true,
+            ..options(&dir)
+        })
+        .unwrap();
+
+        let path = dir.path().join(WORKSPACE_DIR).join(CONFIG_FILE);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# NEXUS configuration"));
+        assert!(content.contains("data_dir"));
+        assert!(!content.contains("max_concurrent_agents"));
+
+        let config = ConfigLoader::new().load_from_file(&path).unwrap();
+        assert_eq!(
+            config.agent.max_concurrent_agents,
+            Config::default().agent.max_concurrent_agents
+        );
+    }
+
+    #[test]
+    fn reports_failing_step() {
+        let dir = TempDir::new().unwrap();
+        // A file where the logs directory should go
+        std::fs::create_dir_all(dir.path().join(WORKSPACE_DIR)).unwrap();
+        std::fs::write(dir.path().join(WORKSPACE_DIR).join("logs"), b"").unwrap();
+
+        let error = run(&options(&dir)).unwrap_err();
+        assert_eq!(
+            error.step,
+            format!(
+                "create {}",
+                dir.path().join(WORKSPACE_DIR).join("logs").display()
+            )
+        );
+    }
+}
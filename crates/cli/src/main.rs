@@ -2,10 +2,29 @@
 //! 
 //! Command-line interface for the NEXUS agent platform
 
-use clap::{Parser, Subcommand};
-use std::io::{self, Write};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use nexus_core::config::{
+    Config, ConfigLoader, LayeredConfig, PluginSecurityPolicy, ValidationReport,
+};
+use nexus_core::crash::{CrashReport, CrashReporter};
+use nexus_core::health::{ComponentHealth, HealthAggregator, HealthStatus, SystemHealth};
+use nexus_core::manifest::PermissionManifest;
+use nexus_core::plugin::{is_plugin_library, library_names, PluginHealth, PluginManager, PluginMetadata};
+use nexus_core::schema;
+use nexus_core::signature::{signature_path, PluginSignature, SignedManifest};
+use nexus_core::suggest::NotFound;
+use std::io::{self, IsTerminal, Write};
+use i18n::{tr, Lang};
+use nexus_core::error::{AgentError, NexusError};
+use std::path::{Path, PathBuf};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+mod completions;
+mod i18n;
+mod init;
+mod onboarding;
+
 /// NEXUS - The Living Terminal
 /// 
 /// A revolutionary CLI tool combining AI Agents, Web3, and intelligent workflows
@@ -17,6 +36,56 @@ use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Override a configuration value, e.g. `--set agent.max_concurrent_agents=4`
+    ///
+    /// Applied after the configuration file and `NEXUS__*` environment variables.
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    set: Vec<(String, String)>,
+    /// Apply a `[profile.<name>]` section of the configuration file
+    ///
+    /// Takes precedence over the `NEXUS_PROFILE` environment variable.
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+    /// Language of the output
+    ///
+    /// Defaults to `NEXUS_LANG`, then to the system locale, then to English.
+    #[arg(long, global = true, value_enum)]
+    lang: Option<Lang>,
+}
+
+/// Top-level subcommands whose help is translated
+const TRANSLATED_SUBCOMMANDS: [&str; 8] =
+    ["version", "init", "agent", "config", "crash", "plugin", "status", "completions"];
+
+/// The command line with its top-level help in `lang`
+fn localized_command(lang: Lang) -> clap::Command {
+    let command = Cli::command()
+        .about(tr(lang, "help.about", &[]))
+        .long_about(tr(lang, "help.long_about", &[]));
+    TRANSLATED_SUBCOMMANDS.into_iter().fold(command, |command, name| {
+        command.mut_subcommand(name, |sub| sub.about(tr(lang, &format!("help.{}", name), &[])))
+    })
+}
+
+/// How every command loads the configuration: `--profile` and `--set`
+struct ConfigSelection {
+    profile: Option<String>,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigSelection {
+    /// A loader applying the selected profile
+    fn loader(&self) -> ConfigLoader {
+        match &self.profile {
+            Some(profile) => ConfigLoader::new().with_profile(profile),
+            None => ConfigLoader::new(),
+        }
+    }
+
+    /// The configuration in layers, with the overrides applied
+    fn load(&self) -> anyhow::Result<LayeredConfig> {
+        self.loader().load_layered(&self.overrides)
+    }
 }
 
 #[derive(Subcommand)]
@@ -29,38 +98,340 @@ enum Commands {
     },
     /// Initialize NEXUS configuration and directories
     #[command(name = "init")]
-    Init,
+    Init {
+        /// Answer a few questions to generate the configuration
+        #[arg(long)]
+        interactive: bool,
+        /// Overwrite an existing nexus.toml
+        #[arg(long)]
+        force: bool,
+        /// Directory to create the workspace in (defaults to the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Write only the values that differ from the defaults
+        #[arg(long, conflicts_with = "interactive")]
+        minimal: bool,
+    },
     /// Agent management commands  
     Agent,
+    /// Configuration inspection commands
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+    /// Inspect crash reports from previous runs
+    Crash {
+        #[command(subcommand)]
+        action: CrashCommands,
+    },
+    /// Plugin inspection commands
+    Plugin {
+        #[command(subcommand)]
+        action: PluginCommands,
+    },
+    /// Report the health of every subsystem; exits non-zero when unhealthy
+    Status {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Print a shell completion script
+    ///
+    /// For example: `nexus completions bash > /etc/bash_completion.d/nexus`
+    Completions {
+        /// Shell to generate the script for
+        shell: Shell,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// Formats of `nexus config schema`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum SchemaFormat {
+    Table,
+    Json,
+    Markdown,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Show the effective configuration
+    Show {
+        /// Configuration file to read (defaults to the first one on the search path)
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Show the merged `include` files, annotated with where each section came from
+        #[arg(long)]
+        resolve_includes: bool,
+        /// Show which layer (file, environment or --set) supplied each value
+        #[arg(long, conflicts_with = "resolve_includes")]
+        origins: bool,
+    },
+    /// List every configuration key with its type, default and description
+    Schema {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = SchemaFormat::Table)]
+        format: SchemaFormat,
+    },
+    /// Check a configuration file, reporting every problem at once
+    ///
+    /// Unknown keys are reported as warnings; they don't fail the check.
+    Validate {
+        /// Configuration file to check (defaults to the first one on the search path)
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CrashCommands {
+    /// List crash reports, newest first
+    List,
+    /// Show a crash report
+    Show {
+        /// Report id as shown by `nexus crash list`
+        id: String,
+    },
+    /// Delete a crash report
+    Delete {
+        /// Report id as shown by `nexus crash list`
+        #[arg(required_unless_present = "all")]
+        id: Option<String>,
+        /// Delete all crash reports
+        #[arg(long, conflicts_with = "id")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PluginCommands {
+    /// List loaded plugins and those found but not loaded
+    List {
+        /// Print only the names of loaded and disabled plugins, one per line
+        #[arg(long)]
+        names: bool,
+    },
+    /// Show the metadata, permissions and dependencies of a loaded plugin
+    Inspect {
+        /// Plugin name
+        name: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Export the permission manifest of a loaded plugin
+    Manifest {
+        /// Plugin name
+        #[arg(required_unless_present = "all")]
+        name: Option<String>,
+        /// Export the manifests of all loaded plugins as a JSON array
+        #[arg(long, conflicts_with = "name")]
+        all: bool,
+        /// File to write the manifest to (defaults to stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Check a plugin library's signature, or loaded plugins against a manifest
+    ///
+    /// A plugin library (.so, .dll or .dylib) is checked against its `.sig`
+    /// file and the trusted publishers without being loaded. Any other file
+    /// is read as a manifest exported by `nexus plugin manifest`, and the
+    /// check fails if a loaded plugin now grants more than it lists.
+    Verify {
+        /// Plugin library or manifest file
+        path: PathBuf,
+    },
+    /// Stop loading a plugin, recording it in the workspace configuration
+    Disable {
+        /// Plugin name
+        name: String,
+    },
+    /// Load a disabled plugin again
+    Enable {
+        /// Plugin name
+        name: String,
+    },
+}
+
+/// Exit status when a named plugin, report or other item doesn't exist
+const EXIT_NOT_FOUND: i32 = 3;
+/// Exit status when a signature or manifest check fails
+const EXIT_VERIFICATION_FAILED: i32 = 4;
+
+fn main() {
+    let lang = Lang::detect(i18n::lang_from_args(std::env::args().skip(1)));
+    let parsed = localized_command(lang)
+        .try_get_matches()
+        .and_then(|matches| Cli::from_arg_matches(&matches));
+    let cli = parsed.unwrap_or_else(|e| {
+        let suggestions = if e.kind() == clap::error::ErrorKind::InvalidSubcommand
+            && e.get(clap::error::ContextKind::SuggestedSubcommand).is_none()
+        {
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            completions::suggest_subcommand(&args)
+        } else {
+            Vec::new()
+        };
+        // Printing only fails when stderr is gone, and then there is no one to tell
+        let _ = e.print();
+        if let Some(hint) = nexus_core::suggest::did_you_mean(&suggestions) {
+            eprintln!("\n{}", hint);
+        }
+        std::process::exit(e.exit_code());
+    });
+
+    let lang = Lang::detect(cli.lang);
+    if let Err(e) = run(cli, lang) {
+        eprintln!("{}", render_error(e.as_ref(), lang));
+        std::process::exit(exit_code(e.as_ref()));
+    }
+}
+
+/// Exit status for an error, so scripts can tell failures apart
+fn exit_code(error: &(dyn std::error::Error + 'static)) -> i32 {
+    if error.is::<NotFound>() {
+        EXIT_NOT_FOUND
+    } else if error.is::<VerificationFailed>() {
+        EXIT_VERIFICATION_FAILED
+    } else {
+        1
+    }
+}
+
+/// A signature or manifest check that did not pass
+#[derive(Debug)]
+struct VerificationFailed(String);
+
+impl std::fmt::Display for VerificationFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "verification failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for VerificationFailed {}
+
+/// Render an error for stderr, including any "did you mean" hint it carries
+fn render_error(error: &(dyn std::error::Error + 'static), lang: Lang) -> String {
+    let message = if let Some(error) = error.downcast_ref::<NexusError>() {
+        i18n::localized_message(error, lang)
+    } else if let Some(error) = error.downcast_ref::<AgentError>() {
+        i18n::localized_agent_message(error, lang)
+    } else {
+        error.to_string()
+    };
+    format!("❌ {}", tr(lang, "error.prefix", &[("message", &message)]))
+}
+
+/// Parse a `--set` argument
+fn parse_key_value(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        },
+        _ => Err(format!("expected KEY=VALUE, got '{}'", arg)),
+    }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+fn run(cli: Cli, lang: Lang) -> Result<(), Box<dyn std::error::Error>> {
+    let selection = ConfigSelection {
+        profile: cli.profile,
+        overrides: cli.set,
+    };
+
+    // Shell completion runs this on every TAB: answer before anything else is set up
+    if let Commands::Plugin { action: PluginCommands::List { names: true } } = cli.command {
+        for name in plugin_names(&selection) {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+
+    let crash_reporter = crash_reporter(&selection);
+    crash_reporter.install();
+    let quiet = matches!(cli.command, Commands::Crash { .. } | Commands::Completions { .. });
+    if !quiet {
+        if let Ok(Some(report)) = crash_reporter.take_notice() {
+            let args: [(&str, &dyn std::fmt::Display); 2] =
+                [("message", &report.message), ("id", &report.id)];
+            eprintln!("⚠️  {}", tr(lang, "crash.notice", &args));
+        }
+    }
 
     match cli.command {
         Commands::Version { verbose } => {
-            print_banner(verbose)?;
+            print_banner(verbose, lang)?;
         },
-        Commands::Init => {
-            print_banner(false)?;
-            println!("🚀 Initializing NEXUS workspace...");
-            println!("📁 Created: ./nexus/");
-            println!("📁 Created: ./nexus/data/");
-            println!("📁 Created: ./nexus/logs/");  
-            println!("📄 Created: ./nexus/nexus.toml");
-            println!("✅ NEXUS workspace initialized successfully!");
+        Commands::Init { interactive, force, path, minimal } => {
+            print_banner(false, lang)?;
+            // Explicit flags ask for the scripted setup, not the first-run wizard
+            let first_run = ConfigLoader::new().find_config_file().is_none()
+                && !force && !minimal && path.is_none();
+            if interactive || first_run {
+                // Never block on a prompt when nobody is there to answer it
+                if io::stdin().is_terminal() {
+                    let dir = path.as_deref().unwrap_or_else(|| Path::new("."));
+                    onboarding::run(&mut onboarding::Terminal, dir, force, lang)?;
+                    return Ok(());
+                }
+                if interactive {
+                    eprintln!("⚠️  {}", tr(lang, "init.needs_terminal", &[]));
+                }
+            }
+            println!("🚀 {}", tr(lang, "init.start", &[]));
+            let options = init::InitOptions {
+                path: path.unwrap_or_else(|| PathBuf::from(".")),
+                force,
+                minimal,
+            };
+            for outcome in init::run(&options)? {
+                println!("{}", outcome.localized(lang));
+            }
+            println!("✅ {}", tr(lang, "init.done", &[]));
         },
         Commands::Agent => {
-            print_banner(false)?;
-            println!("🤖 Agent management coming soon...");
-            println!("💡 Tip: Use 'nexus agent run --dry' to simulate agent execution");
+            print_banner(false, lang)?;
+            println!("🤖 {}", tr(lang, "agent.coming_soon", &[]));
+            println!("💡 {}", tr(lang, "agent.tip", &[]));
+        },
+        Commands::Config { action } => match action {
+            ConfigCommands::Show { config, resolve_includes, origins } => {
+                show_config(config, resolve_includes, origins, &selection, lang)?;
+            },
+            ConfigCommands::Schema { format } => {
+                let keys = Config::schema();
+                match format {
+                    SchemaFormat::Table => print!("{}", schema::render_table(&keys)),
+                    SchemaFormat::Json => println!("{}", serde_json::to_string_pretty(&keys)?),
+                    SchemaFormat::Markdown => print!("{}", schema::render_markdown(&keys)),
+                }
+            },
+            ConfigCommands::Validate { path } => validate_config(path, &selection, lang)?,
+        },
+        Commands::Crash { action } => run_crash_command(&crash_reporter, action, lang)?,
+        Commands::Plugin { action } => run_plugin_command(action, &selection, lang)?,
+        Commands::Status { format } => {
+            let health = tokio::runtime::Runtime::new()?.block_on(system_health(&selection));
+            match format {
+                OutputFormat::Table => print!("{}", render_health(&health, lang)),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&health)?),
+            }
+            if health.overall == HealthStatus::Unhealthy {
+                return Err(tr(lang, "status.failed", &[]).into());
+            }
+        },
+        Commands::Completions { shell } => {
+            completions::write_completions(shell, &mut io::stdout())?;
         },
     }
 
     Ok(())
 }
 
-fn print_banner(verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn print_banner(verbose: bool, lang: Lang) -> Result<(), Box<dyn std::error::Error>> {
     let mut stdout = StandardStream::stdout(ColorChoice::Auto);
     
     // ASCII Art Banner
@@ -74,37 +445,41 @@ fn print_banner(verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     ╚═╝  ╚═══╝╚══════╝╚═╝  ╚═╝ ╚═════╝ ╚══════╝"#)?;
     
     stdout.set_color(ColorSpec::new().set_fg(Some(Color::White)).set_bold(true))?;
-    writeln!(stdout, "    The Living Terminal")?;
+    writeln!(stdout, "    {}", tr(lang, "banner.tagline", &[]))?;
     
     stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
-    writeln!(stdout, "    AI Agents • Web3 • Intelligent Workflows")?;
+    writeln!(stdout, "    {}", tr(lang, "banner.features", &[]))?;
     
     stdout.reset()?;
     writeln!(stdout)?;
 
     // Version Information
     stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
-    write!(stdout, "🔧 NEXUS Version: ")?;
+    write!(stdout, "🔧 {}", tr(lang, "banner.version", &[]))?;
     stdout.set_color(ColorSpec::new().set_fg(Some(Color::White)))?;
     writeln!(stdout, "v{}", env!("CARGO_PKG_VERSION"))?;
 
     if verbose {
         // Detailed system information
         stdout.set_color(ColorSpec::new().set_fg(Some(Color::Blue)))?;
-        writeln!(stdout, "\n📊 System Information:")?;
+        writeln!(stdout, "\n📊 {}", tr(lang, "banner.system", &[]))?;
         
         stdout.set_color(ColorSpec::new().set_fg(Some(Color::White)))?;
-        writeln!(stdout, "   • Rust Compiler: {}", get_rustc_version())?;
-        writeln!(stdout, "   • Target Triple: {}", std::env::consts::ARCH)?;
-        writeln!(stdout, "   • OS: {} {}", std::env::consts::OS, std::env::consts::FAMILY)?;
+        let rustc = get_rustc_version();
+        writeln!(stdout, "   • {}", tr(lang, "banner.rustc", &[("version", &rustc)]))?;
+        let target = tr(lang, "banner.target", &[("target", &std::env::consts::ARCH)]);
+        writeln!(stdout, "   • {}", target)?;
+        let os: [(&str, &dyn std::fmt::Display); 2] =
+            [("os", &std::env::consts::OS), ("family", &std::env::consts::FAMILY)];
+        writeln!(stdout, "   • {}", tr(lang, "banner.os", &os))?;
         
         // Workspace information
         stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)))?;
-        writeln!(stdout, "\n📦 Workspace Members:")?;
+        writeln!(stdout, "\n📦 {}", tr(lang, "banner.members", &[]))?;
         stdout.set_color(ColorSpec::new().set_fg(Some(Color::White)))?;
-        writeln!(stdout, "   • nexus-cli (binary)")?;
-        writeln!(stdout, "   • nexus-core (library)")?;
-        writeln!(stdout, "   • plugins/example (future)")?;
+        for member in ["banner.member.cli", "banner.member.core", "banner.member.plugins"] {
+            writeln!(stdout, "   • {}", tr(lang, member, &[]))?;
+        }
     }
 
     stdout.reset()?;
@@ -112,6 +487,574 @@ fn print_banner(verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn show_config(
+    path: Option<PathBuf>,
+    resolve_includes: bool,
+    origins: bool,
+    selection: &ConfigSelection,
+    lang: Lang,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut loader = selection.loader();
+    if let Some(path) = path {
+        if !path.exists() {
+            return Err(tr(lang, "config.not_found", &[("path", &path.display())]).into());
+        }
+        loader.prepend_search_path(path);
+    }
+
+    let file = loader.find_config_file();
+    if file.is_none() {
+        eprintln!("{}", tr(lang, "config.showing_defaults", &[]));
+    }
+
+    // Validate the merged result before printing anything
+    let layered = loader.load_layered(&selection.overrides)?;
+
+    match file {
+        Some(path) if resolve_includes => {
+            print!("{}", loader.resolve_includes(&path)?.render_with_provenance()?);
+        },
+        _ if origins => print!("{}", layered.render_origins()),
+        _ => print!("{}", toml::to_string_pretty(&layered.config)?),
+    }
+
+    Ok(())
+}
+
+/// Check a configuration file and print every problem found
+///
+/// Fails when the file would not load; warnings alone don't fail.
+fn validate_config(
+    path: Option<PathBuf>,
+    selection: &ConfigSelection,
+    lang: Lang,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let loader = selection.loader();
+    let Some(path) = path.or_else(|| loader.find_config_file()) else {
+        return Err(tr(lang, "config.none", &[]).into());
+    };
+    if !path.exists() {
+        return Err(tr(lang, "config.not_found", &[("path", &path.display())]).into());
+    }
+
+    let report = loader.validate_file(&path)?;
+    print!("{}", render_validation(&path, &report, lang));
+    if !report.is_valid() {
+        let args: [(&str, &dyn std::fmt::Display); 2] =
+            [("path", &path.display()), ("count", &report.errors.len())];
+        return Err(tr(lang, "config.invalid", &args).into());
+    }
+    Ok(())
+}
+
+/// One line per problem, errors first, or a confirmation when there are none
+fn render_validation(path: &Path, report: &ValidationReport, lang: Lang) -> String {
+    let mut output = String::new();
+    for problem in &report.errors {
+        output.push_str(&format!("❌ {}\n", problem));
+    }
+    for problem in &report.warnings {
+        output.push_str(&format!("⚠️  {}\n", problem));
+    }
+    if report.is_valid() {
+        output.push_str(&format!("✅ {}\n", tr(lang, "config.valid", &[("path", &path.display())])));
+    }
+    output
+}
+
+/// Crash reports live next to the agent data directory of the active configuration
+fn crash_reporter(selection: &ConfigSelection) -> CrashReporter {
+    let data_dir = selection
+        .load()
+        .map(|layered| layered.config.agent.data_dir)
+        .unwrap_or_else(|_| Config::default().agent.data_dir);
+
+    CrashReporter::new(data_dir.join("crashes"))
+}
+
+fn run_crash_command(
+    reporter: &CrashReporter,
+    action: CrashCommands,
+    lang: Lang,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        CrashCommands::List => {
+            let reports = reporter.list()?;
+            if reports.is_empty() {
+                println!("{}", tr(lang, "crash.none", &[("dir", &reporter.dir().display())]));
+            }
+            for report in reports {
+                println!("{}  {}", report.id, report.message);
+            }
+        },
+        CrashCommands::Show { id } => {
+            let report = find_crash_report(reporter, &id)?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        },
+        CrashCommands::Delete { id, all } => {
+            if all {
+                let count = reporter.delete_all()?;
+                println!("{}", tr(lang, "crash.deleted_all", &[("count", &count)]));
+            } else if let Some(id) = id {
+                find_crash_report(reporter, &id)?;
+                reporter.delete(&id)?;
+                println!("{}", tr(lang, "crash.deleted", &[("id", &id)]));
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Load the configured plugins
+fn load_plugin_manager(
+    selection: &ConfigSelection,
+) -> Result<PluginManager, Box<dyn std::error::Error>> {
+    let config = selection.load()?.config;
+    let mut manager = PluginManager::new(config.plugin, None);
+    tokio::runtime::Runtime::new()?.block_on(manager.load_plugins())?;
+    Ok(manager)
+}
+
+/// Names of the plugin libraries and disabled plugins, sorted; empty outside a workspace
+///
+/// Shell completion calls this on every TAB, so nothing is loaded: see
+/// [`library_names`]. Failures only leave names out.
+fn plugin_names(selection: &ConfigSelection) -> Vec<String> {
+    if selection.loader().find_config_file().is_none() {
+        return Vec::new();
+    }
+    let Ok(layered) = selection.load() else {
+        return Vec::new();
+    };
+
+    let plugin = layered.config.plugin;
+    let mut names = library_names(&plugin.plugin_dirs);
+    names.extend(plugin.disabled);
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// `nexus status` as a table in `lang`, laid out like [`SystemHealth::render_table`]
+fn render_health(health: &SystemHealth, lang: Lang) -> String {
+    let status = |status: HealthStatus| {
+        let id = match status {
+            HealthStatus::Healthy => "status.healthy",
+            HealthStatus::Degraded => "status.degraded",
+            HealthStatus::Unhealthy => "status.unhealthy",
+        };
+        tr(lang, id, &[])
+    };
+    let [component, status_header, reason] =
+        ["status.component", "status.status", "status.reason"].map(|id| tr(lang, id, &[]));
+    let mut names: Vec<&String> = health.components.keys().collect();
+    names.sort();
+    let name_width = names
+        .iter()
+        .map(|name| name.as_str())
+        .chain([component.as_str()])
+        .map(|cell| cell.chars().count())
+        .max()
+        .unwrap_or(0);
+    let statuses = [HealthStatus::Healthy, HealthStatus::Degraded, HealthStatus::Unhealthy];
+    let status_width = statuses
+        .map(status)
+        .iter()
+        .chain([&status_header])
+        .map(|cell| cell.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = format!(
+        "{:name_width$}  {:status_width$}  {}\n",
+        component, status_header, reason
+    );
+    for name in names {
+        let entry = &health.components[name];
+        let line = format!(
+            "{:name_width$}  {:status_width$}  {}",
+            name,
+            status(entry.status),
+            entry.reason.as_deref().unwrap_or("")
+        );
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output.push_str(&tr(lang, "status.overall", &[("status", &status(health.overall))]));
+    output.push('\n');
+    output
+}
+
+/// Check the configuration and, when it loads, every plugin it enables
+async fn system_health(selection: &ConfigSelection) -> SystemHealth {
+    let config = match selection.load() {
+        Ok(layered) => layered.config,
+        Err(e) => {
+            return SystemHealth::from_components([(
+                "config".to_string(),
+                ComponentHealth::unhealthy(e.to_string()),
+            )]);
+        }
+    };
+
+    let mut manager = PluginManager::new(config.plugin, None);
+    let plugins = match manager.load_plugins().await {
+        Ok(()) => ComponentHealth::healthy(),
+        Err(e) => ComponentHealth::degraded(format!("loading plugins: {}", e)),
+    };
+    let manager = std::sync::Arc::new(tokio::sync::Mutex::new(manager));
+
+    HealthAggregator::new()
+        .component("config", || async { ComponentHealth::healthy() })
+        .component("plugins", move || {
+            let plugins = plugins.clone();
+            async move { plugins }
+        })
+        .group("plugin", move || PluginManager::plugin_health(std::sync::Arc::clone(&manager)))
+        .check()
+        .await
+}
+
+fn run_plugin_command(
+    action: PluginCommands,
+    selection: &ConfigSelection,
+    lang: Lang,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Checking a library's signature must not load it
+    if let PluginCommands::Verify { path } = &action {
+        if is_plugin_library(path) {
+            let config = selection.load()?.config;
+            let signed = verify_library(path, &config.plugin.security_policy)?;
+            let args: [(&str, &dyn std::fmt::Display); 3] = [
+                ("name", &signed.name),
+                ("version", &signed.version),
+                ("publisher", &signed.publisher),
+            ];
+            println!("✅ {}", tr(lang, "plugin.signed", &args));
+            return Ok(());
+        }
+    }
+
+    let manager = load_plugin_manager(selection)?;
+    let disable = matches!(action, PluginCommands::Disable { .. });
+
+    match action {
+        PluginCommands::List { .. } => print!("{}", render_plugin_list(&manager, lang)),
+        PluginCommands::Inspect { name, format } => {
+            print!("{}", render_plugin(&manager, &name, format, lang)?);
+        },
+        PluginCommands::Manifest { name, all, out } => {
+            let json = if all {
+                serde_json::to_string_pretty(&manager.permission_manifests()?)?
+            } else {
+                let name = name.unwrap_or_default();
+                find_plugin(&manager, &name)?;
+                manager.permission_manifest(&name)?.to_json()?
+            };
+
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, json)?;
+                    println!("{}", tr(lang, "plugin.wrote", &[("path", &path.display())]));
+                },
+                None => println!("{}", json),
+            }
+        },
+        PluginCommands::Verify { path: manifest } => {
+            let current = |name: &str| manager.permission_manifest(name).ok();
+            let changes = verify_manifests(current, &std::fs::read_to_string(&manifest)?)?;
+            if !changes.is_empty() {
+                for change in &changes {
+                    eprintln!("  • {}", change);
+                }
+                return Err(Box::new(VerificationFailed(format!(
+                    "{} permission change(s) since {}",
+                    changes.len(),
+                    manifest.display()
+                ))));
+            }
+            println!("✅ {}", tr(lang, "plugin.verified", &[("path", &manifest.display())]));
+        },
+        PluginCommands::Disable { name } | PluginCommands::Enable { name } => {
+            let loader = selection.loader();
+            let config_path =
+                loader.find_config_file().ok_or_else(|| tr(lang, "plugin.no_config", &[]))?;
+            let lines = set_plugin_disabled(&manager, &loader, &config_path, &name, disable, lang)?;
+            for line in lines {
+                println!("{}", line);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// The loaded plugin called `name`, or a not-found error with suggestions
+fn find_plugin<'a>(
+    manager: &'a PluginManager,
+    name: &str,
+) -> Result<&'a PluginMetadata, NotFound> {
+    let loaded = manager.list_plugins();
+    loaded
+        .iter()
+        .find(|metadata| metadata.name == name)
+        .copied()
+        .ok_or_else(|| NotFound::new("plugin", name, loaded.iter().map(|m| m.name.as_str())))
+}
+
+/// Loaded plugins with their health, then libraries that were not loaded
+fn render_plugin_list(manager: &PluginManager, lang: Lang) -> String {
+    let health = manager.check_plugin_health();
+    let status = manager.plugin_status();
+    let mut plugins = manager.list_plugins();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let rows: Vec<[String; 4]> = plugins
+        .iter()
+        .map(|metadata| {
+            let health = health.get(&metadata.name).cloned().unwrap_or(PluginHealth::Healthy);
+            let mut health = health.to_string();
+            let restarts = status.get(&metadata.name).map_or(0, |status| status.restarts);
+            let restarted = match restarts {
+                0 => None,
+                1 => Some(tr(lang, "plugin.restarted_once", &[])),
+                restarts => Some(tr(lang, "plugin.restarted", &[("count", &restarts)])),
+            };
+            if let Some(restarted) = restarted {
+                health.push_str(&format!(" ({})", restarted));
+            }
+            [metadata.name.clone(), metadata.version.clone(), metadata.author.clone(), health]
+        })
+        .collect();
+
+    let mut out = String::new();
+    if rows.is_empty() {
+        out.push_str(&tr(lang, "plugin.none", &[]));
+        out.push('\n');
+    } else {
+        let header = ["plugin.name", "plugin.version", "plugin.author", "plugin.health"]
+            .map(|id| tr(lang, id, &[]));
+        let mut widths = [0; 3];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for row in std::iter::once(&header).chain(&rows) {
+            out.push_str(&format!(
+                "{:w0$}  {:w1$}  {:w2$}  {}\n",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            ));
+        }
+    }
+
+    let failed: Vec<_> = status.iter().filter(|(_, status)| status.failed).collect();
+    if !failed.is_empty() {
+        out.push_str(&format!("\n{}\n", tr(lang, "plugin.unloaded", &[])));
+        for (name, status) in failed {
+            let args: [(&str, &dyn std::fmt::Display); 2] =
+                [("name", name), ("count", &status.restarts)];
+            out.push_str(&format!("  {}\n", tr(lang, "plugin.gave_up", &args)));
+        }
+    }
+
+    let skipped = manager.skipped_plugins();
+    if !skipped.is_empty() {
+        out.push_str(&format!("\n{}\n", tr(lang, "plugin.skipped", &[])));
+        for (path, reason) in skipped {
+            out.push_str(&format!("  {}: {}\n", path.display(), reason));
+        }
+    }
+    out
+}
+
+/// Full description of a loaded plugin
+fn render_plugin(
+    manager: &PluginManager,
+    name: &str,
+    format: OutputFormat,
+    lang: Lang,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let metadata = find_plugin(manager, name)?;
+    let permissions = &metadata.permissions;
+    let granted: Vec<&str> = [
+        ("filesystem", permissions.filesystem_access),
+        ("network", permissions.network_access),
+        ("system_commands", permissions.system_commands),
+        ("web3", permissions.web3_access),
+        ("plugins", permissions.plugin_access),
+        ("config", permissions.config_access),
+    ]
+    .into_iter()
+    .filter_map(|(permission, granted)| granted.then_some(permission))
+    .collect();
+    let path = manager.plugin_path(name).map(|path| path.display().to_string());
+
+    if format == OutputFormat::Json {
+        let json = serde_json::json!({
+            "name": metadata.name,
+            "version": metadata.version,
+            "description": metadata.description,
+            "author": metadata.author,
+            "required_nexus_version": metadata.required_nexus_version,
+            "dependencies": metadata.dependencies,
+            "dependents": manager.dependents_of(name),
+            "permissions": granted,
+            "signed": metadata.signature.is_some(),
+            "path": path,
+        });
+        return Ok(format!("{}\n", serde_json::to_string_pretty(&json)?));
+    }
+
+    let list = |items: &[String]| {
+        if items.is_empty() {
+            tr(lang, "plugin.inspect.none", &[])
+        } else {
+            items.join(", ")
+        }
+    };
+    let granted: Vec<String> = granted.into_iter().map(String::from).collect();
+    let signed = match metadata.signature {
+        Some(_) => "plugin.inspect.yes",
+        None => "plugin.inspect.no",
+    };
+    let rows = [
+        ("plugin.inspect.name", metadata.name.clone()),
+        ("plugin.inspect.version", metadata.version.clone()),
+        ("plugin.inspect.description", metadata.description.clone()),
+        ("plugin.inspect.author", metadata.author.clone()),
+        ("plugin.inspect.requires", metadata.required_nexus_version.clone()),
+        ("plugin.inspect.dependencies", list(&metadata.dependencies)),
+        ("plugin.inspect.dependents", list(&manager.dependents_of(name))),
+        ("plugin.inspect.permissions", list(&granted)),
+        ("plugin.inspect.signed", tr(lang, signed, &[])),
+        ("plugin.inspect.path", path.unwrap_or_else(|| tr(lang, "plugin.inspect.built_in", &[]))),
+    ]
+    .map(|(id, value)| (format!("{}:", tr(lang, id, &[])), value));
+    let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    Ok(rows
+        .iter()
+        .map(|(label, value)| format!("{:width$} {}\n", label, value))
+        .collect())
+}
+
+/// Check the signature of the library at `path` without loading it
+fn verify_library(
+    path: &Path,
+    policy: &PluginSecurityPolicy,
+) -> Result<SignedManifest, VerificationFailed> {
+    let failed = |reason: String| VerificationFailed(format!("{}: {}", path.display(), reason));
+    let signature = match PluginSignature::read_for(path) {
+        Ok(signature) => signature,
+        Err(_) if !signature_path(path).exists() => {
+            return Err(failed("plugin is not signed".to_string()))
+        },
+        Err(e) => return Err(failed(e.to_string())),
+    };
+    signature
+        .verify(path, &policy.trusted_publishers, &policy.trusted_keys)
+        .cloned()
+        .map_err(|e| failed(e.to_string()))
+}
+
+/// Record `name` as disabled or enabled in the configuration `loader` found at `config_path`
+///
+/// Returns the lines to show the user, including a warning when loaded
+/// plugins depend on a plugin being disabled.
+fn set_plugin_disabled(
+    manager: &PluginManager,
+    loader: &ConfigLoader,
+    config_path: &Path,
+    name: &str,
+    disable: bool,
+    lang: Lang,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let disabled = loader.load_from_file(&config_path.to_path_buf())?.plugin.disabled;
+    let known = disabled.contains(&name.to_string());
+    if !known && (!disable || find_plugin(manager, name).is_err()) {
+        let candidates: Vec<&str> = if disable {
+            manager.list_plugins().iter().map(|m| m.name.as_str()).collect()
+        } else {
+            disabled.iter().map(String::as_str).collect()
+        };
+        let kind = if disable { "plugin" } else { "disabled plugin" };
+        return Err(Box::new(NotFound::new(kind, name, candidates)));
+    }
+
+    let changed = loader.set_plugin_disabled(config_path, name, disable)?;
+    let args: [(&str, &dyn std::fmt::Display); 2] =
+        [("name", &name), ("path", &config_path.display())];
+    let mut lines = vec![match (changed, disable) {
+        (true, true) => format!("✅ {}", tr(lang, "plugin.disabled", &args)),
+        (true, false) => format!("✅ {}", tr(lang, "plugin.enabled", &args)),
+        (false, true) => tr(lang, "plugin.already_disabled", &args),
+        (false, false) => tr(lang, "plugin.already_enabled", &args),
+    }];
+    if disable {
+        let dependents = manager.dependents_of(name).join(", ");
+        if !dependents.is_empty() {
+            let args: [(&str, &dyn std::fmt::Display); 2] =
+                [("name", &name), ("dependents", &dependents)];
+            lines.push(format!("⚠️  {}", tr(lang, "plugin.dependents", &args)));
+        }
+    }
+    Ok(lines)
+}
+
+/// Compare reviewed manifests (a single one or an array) against the loaded plugins
+///
+/// `current` returns the manifest of a loaded plugin, or `None` when it isn't loaded.
+fn verify_manifests(
+    current: impl Fn(&str) -> Option<PermissionManifest>,
+    reviewed: &str,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let reviewed: Vec<PermissionManifest> = if reviewed.trim_start().starts_with('[') {
+        let values: Vec<serde_json::Value> = serde_json::from_str(reviewed)?;
+        values
+            .iter()
+            .map(|value| PermissionManifest::from_json(&value.to_string()))
+            .collect::<Result<_, _>>()?
+    } else {
+        vec![PermissionManifest::from_json(reviewed)?]
+    };
+
+    let mut changes = Vec::new();
+    for manifest in &reviewed {
+        match current(&manifest.plugin) {
+            Some(loaded) => changes.extend(
+                loaded
+                    .broadenings_since(manifest)
+                    .into_iter()
+                    .map(|change| format!("{}: {}", manifest.plugin, change)),
+            ),
+            None => changes.push(format!("{}: plugin is not loaded", manifest.plugin)),
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Load a crash report, suggesting close ids when it doesn't exist
+fn find_crash_report(
+    reporter: &CrashReporter,
+    id: &str,
+) -> Result<CrashReport, Box<dyn std::error::Error>> {
+    let reports = reporter.list()?;
+    if let Some(report) = reports.iter().find(|report| report.id == id) {
+        return Ok(report.clone());
+    }
+
+    let ids = reports.iter().map(|report| report.id.as_str());
+    Err(Box::new(NotFound::new("crash report", id, ids)))
+}
+
 fn get_rustc_version() -> String {
     // In a production app, you'd use rustc_version crate or build script
     // For now, we'll use a placeholder that shows the concept
@@ -130,10 +1073,376 @@ mod tests {
         let _cli = Cli::parse_from(&["nexus", "version"]);
     }
 
+    #[test]
+    fn config_show_parses_resolve_includes() {
+        let cli = Cli::parse_from(&["nexus", "config", "show", "--resolve-includes"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Config { action: ConfigCommands::Show { resolve_includes: true, config: None, origins: false } }
+        ));
+    }
+
+    #[test]
+    fn status_format_defaults_to_table() {
+        let cli = Cli::parse_from(["nexus", "status"]);
+        assert!(matches!(cli.command, Commands::Status { format: OutputFormat::Table }));
+
+        let cli = Cli::parse_from(["nexus", "status", "--format", "json"]);
+        assert!(matches!(cli.command, Commands::Status { format: OutputFormat::Json }));
+
+        assert!(Cli::try_parse_from(["nexus", "status", "--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn set_flags_parse_anywhere() {
+        let cli = Cli::parse_from([
+            "nexus", "config", "show", "--origins",
+            "--set", "agent.max_concurrent_agents=4", "--set", "logging.level=debug=x",
+        ]);
+        assert_eq!(
+            cli.set,
+            vec![
+                ("agent.max_concurrent_agents".to_string(), "4".to_string()),
+                ("logging.level".to_string(), "debug=x".to_string()),
+            ]
+        );
+        assert!(matches!(
+            cli.command,
+            Commands::Config { action: ConfigCommands::Show { origins: true, .. } }
+        ));
+
+        let cli = Cli::parse_from(["nexus", "--set", "logging.level=warn", "version"]);
+        assert_eq!(cli.set, vec![("logging.level".to_string(), "warn".to_string())]);
+
+        assert!(Cli::try_parse_from(["nexus", "version", "--set", "no-value"]).is_err());
+        assert!(Cli::try_parse_from(["nexus", "version", "--set", "=4"]).is_err());
+        assert!(Cli::try_parse_from(["nexus", "config", "show", "--origins", "--resolve-includes"]).is_err());
+    }
+
+    #[test]
+    fn profile_flag_selects_profile() {
+        let cli = Cli::parse_from(["nexus", "config", "show", "--profile", "prod"]);
+        assert_eq!(cli.profile.as_deref(), Some("prod"));
+        assert!(Cli::parse_from(["nexus", "status"]).profile.is_none());
+
+        let fixture = nexus_core::testing::Fixture::builder()
+            .set("profile.prod.agent.max_concurrent_agents", 2)
+            .build()
+            .unwrap();
+        let selection = ConfigSelection { profile: cli.profile, overrides: Vec::new() };
+        let mut loader = selection.loader();
+        loader.prepend_search_path(fixture.config_path());
+        assert_eq!(loader.load_layered(&[]).unwrap().config.agent.max_concurrent_agents, 2);
+    }
+
+    #[test]
+    fn crash_delete_requires_id_or_all() {
+        assert!(Cli::try_parse_from(["nexus", "crash", "delete"]).is_err());
+        assert!(Cli::try_parse_from(["nexus", "crash", "delete", "--all"]).is_ok());
+        assert!(Cli::try_parse_from(["nexus", "crash", "delete", "crash-1"]).is_ok());
+    }
+
+    #[test]
+    fn unknown_crash_report_suggests_close_ids() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let report = r#"{"id":"crash-1700000000000","timestamp_secs":1,"message":"boom",
+            "location":null,"thread":null,"backtrace":"","version":"0","platform":"test",
+            "features":[],"recent_logs":[],"truncated":false}"#;
+        std::fs::write(dir.path().join("crash-1700000000000.json"), report).unwrap();
+        let reporter = CrashReporter::new(dir.path());
+
+        let error = find_crash_report(&reporter, "crash-170000000000").unwrap_err();
+        let stderr = render_error(error.as_ref(), Lang::En);
+        assert!(stderr.contains("crash report 'crash-170000000000' not found"));
+        assert!(stderr.contains("Did you mean 'crash-1700000000000'?"));
+
+        let error = find_crash_report(&reporter, "nonsense").unwrap_err();
+        assert!(!render_error(error.as_ref(), Lang::En).contains("Did you mean"));
+    }
+
+    #[test]
+    fn init_parses_flags() {
+        let cli = Cli::parse_from(["nexus", "init", "--force", "--minimal", "--path", "/tmp/ws"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Init { force: true, minimal: true, interactive: false, path: Some(_) }
+        ));
+        assert!(Cli::try_parse_from(["nexus", "init", "--minimal", "--interactive"]).is_err());
+    }
+
+    /// Answers every wizard question with its default
+    struct Defaults;
+
+    impl onboarding::Io for Defaults {
+        fn prompt(&mut self, _question: &str) -> io::Result<Option<String>> {
+            Ok(Some(String::new()))
+        }
+
+        fn say(&mut self, _line: &str) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn interactive_init_honors_path_and_force() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("ws");
+        let cli = Cli::parse_from([
+            "nexus".as_ref(),
+            "init".as_ref(),
+            "--interactive".as_ref(),
+            "--path".as_ref(),
+            target.as_os_str(),
+        ]);
+        let Commands::Init { interactive: true, force, path: Some(path), .. } = cli.command else {
+            panic!("expected init --interactive --path");
+        };
+        assert!(!force);
+
+        onboarding::run(&mut Defaults, &path, force, Lang::En).unwrap();
+        let config_path = init::config_path(&target);
+        assert!(config_path.is_file());
+        assert!(target.join(init::WORKSPACE_DIR).join("data").is_dir());
+
+        assert!(onboarding::run(&mut Defaults, &path, false, Lang::En).is_err());
+        onboarding::run(&mut Defaults, &path, true, Lang::En).unwrap();
+
+        // Scripted init writes the same file, so it refuses the wizard's without --force
+        let options = init::InitOptions {
+            path: target,
+            ..init::InitOptions::default()
+        };
+        assert!(init::run(&options).is_err());
+    }
+
+    #[test]
+    fn plugin_manifest_requires_name_or_all() {
+        assert!(Cli::try_parse_from(["nexus", "plugin", "manifest"]).is_err());
+        assert!(Cli::try_parse_from(["nexus", "plugin", "manifest", "--all"]).is_ok());
+        assert!(Cli::try_parse_from(["nexus", "plugin", "manifest", "mock-plugin", "--out", "m.json"]).is_ok());
+    }
+
+    #[test]
+    fn verify_reports_broadened_plugin() {
+        let fixture = nexus_core::testing::Fixture::builder()
+            .plugin_file("mock.so", b"v1".to_vec())
+            .build()
+            .unwrap();
+        let library = fixture.plugin_dir().join("mock.so");
+        let loaded = || {
+            PermissionManifest::from_json(
+                &serde_json::json!({
+                    "schema_version": 1,
+                    "plugin": "mock-plugin",
+                    "version": "1.0.0",
+                    "required_nexus_version": "0.1.0",
+                    "signature": "unsigned",
+                    "content_hash": null,
+                    "capabilities": [],
+                    "agents": {}
+                })
+                .to_string(),
+            )
+            .unwrap()
+            .with_content_hash(&library)
+            .unwrap()
+        };
+        let current = |name: &str| (name == "mock-plugin").then(loaded);
+
+        let exported = current("mock-plugin").unwrap().to_json().unwrap();
+        assert!(verify_manifests(current, &exported).unwrap().is_empty());
+
+        // Simulate the reviewed manifest predating a newly granted permission
+        let mut reviewed = PermissionManifest::from_json(&exported).unwrap();
+        reviewed.capabilities.clear();
+        let mut broadened = current("mock-plugin").unwrap();
+        broadened.capabilities.insert("network_access".to_string());
+        assert_eq!(broadened.broadenings_since(&reviewed), vec!["capability added: network_access"]);
+
+        // Replacing the library on disk is caught by the content hash
+        std::fs::write(&library, b"v2").unwrap();
+        let changes = verify_manifests(current, &format!("[{}]", exported)).unwrap();
+        assert_eq!(changes, vec!["mock-plugin: content hash changed"]);
+
+        let missing = exported.replace("mock-plugin", "gone");
+        assert_eq!(verify_manifests(current, &missing).unwrap(), vec!["gone: plugin is not loaded"]);
+    }
+
+    #[test]
+    fn config_validate_renders_every_problem() {
+        let cli = Cli::parse_from(["nexus", "config", "schema", "--format", "markdown"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Config { action: ConfigCommands::Schema { format: SchemaFormat::Markdown } }
+        ));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nexus.toml");
+        std::fs::write(&path, "[logging]\nlevel = \"Info\"\nlevle = \"debug\"\n").unwrap();
+        let report = ConfigLoader::new().validate_file(&path).unwrap();
+        assert_eq!(
+            render_validation(&path, &report, Lang::En),
+            "❌ logging.level: Invalid log level 'Info', must be one of: \
+             trace, debug, info, warn, error. Did you mean 'info'?\n\
+             ⚠️  logging.levle: unknown configuration key. Did you mean 'logging.level'?\n"
+        );
+
+        std::fs::write(&path, "[logging]\nlevel = \"info\"\n").unwrap();
+        let report = ConfigLoader::new().validate_file(&path).unwrap();
+        assert_eq!(
+            render_validation(&path, &report, Lang::En),
+            format!("✅ {} is valid\n", path.display())
+        );
+    }
+
     #[test]
     fn rustc_version_format() {
         let version = get_rustc_version();
         assert!(version.contains("rustc"));
         assert!(version.contains("Cargo"));
     }
-}
\ No newline at end of file
+
+    struct StubPlugin {
+        metadata: PluginMetadata,
+    }
+
+    impl StubPlugin {
+        fn new(name: &str, dependencies: &[&str]) -> Box<Self> {
+            Box::new(Self {
+                metadata: PluginMetadata {
+                    name: name.to_string(),
+                    version: "1.2.0".to_string(),
+                    description: "Stub plugin".to_string(),
+                    author: "Tester".to_string(),
+                    required_nexus_version: "0.1.0".to_string(),
+                    dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+                    signature: None,
+                    permissions: nexus_core::plugin::PluginPermissions {
+                        network_access: true,
+                        ..Default::default()
+                    },
+                },
+            })
+        }
+    }
+
+    impl nexus_core::plugin::Plugin for StubPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+
+        fn initialize(&mut self, _config: &nexus_core::config::PluginConfig) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn agents(&self) -> Vec<Box<dyn nexus_core::Agent>> {
+            Vec::new()
+        }
+
+        fn shutdown(&mut self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn health_check(&self) -> anyhow::Result<PluginHealth> {
+            Ok(PluginHealth::Healthy)
+        }
+    }
+
+    fn stub_manager(fixture: &nexus_core::testing::Fixture) -> PluginManager {
+        let config = fixture.config().unwrap();
+        let mut manager = PluginManager::new(config.plugin, None);
+        manager.register_plugin(StubPlugin::new("weather", &[])).unwrap();
+        manager.register_plugin(StubPlugin::new("forecast", &["weather"])).unwrap();
+        manager
+    }
+
+    #[test]
+    fn plugin_commands_parse() {
+        assert!(matches!(
+            Cli::parse_from(["nexus", "plugin", "list"]).command,
+            Commands::Plugin { action: PluginCommands::List { names: false } }
+        ));
+        assert!(matches!(
+            Cli::parse_from(["nexus", "plugin", "inspect", "weather", "--format", "json"]).command,
+            Commands::Plugin { action: PluginCommands::Inspect { format: OutputFormat::Json, .. } }
+        ));
+        assert!(Cli::try_parse_from(["nexus", "plugin", "disable"]).is_err());
+        assert!(Cli::try_parse_from(["nexus", "plugin", "enable", "weather"]).is_ok());
+    }
+
+    #[test]
+    fn plugin_list_and_inspect() {
+        let fixture = nexus_core::testing::Fixture::new().unwrap();
+        let manager = stub_manager(&fixture);
+
+        let list = render_plugin_list(&manager, Lang::En);
+        let lines: Vec<&str> = list.lines().collect();
+        assert_eq!(lines[0], "NAME      VERSION  AUTHOR  HEALTH");
+        assert_eq!(lines[1], "forecast  1.2.0    Tester  healthy");
+        assert_eq!(lines[2], "weather   1.2.0    Tester  healthy");
+
+        let table = render_plugin(&manager, "weather", OutputFormat::Table, Lang::En).unwrap();
+        assert!(table.contains("Dependents:     forecast\n"));
+        assert!(table.contains("Permissions:    network\n"));
+        assert!(table.contains("Path:           built in\n"));
+
+        let list = render_plugin_list(&manager, Lang::Ro);
+        assert!(list.starts_with("NUME      VERSIUNE  AUTOR   STARE\n"));
+        let table = render_plugin(&manager, "weather", OutputFormat::Table, Lang::Ro).unwrap();
+        assert!(table.contains("Dependenți:     forecast\n"));
+        assert!(table.contains("Cale:           încorporat\n"));
+
+        let json = render_plugin(&manager, "forecast", OutputFormat::Json, Lang::En).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["dependencies"], serde_json::json!(["weather"]));
+        assert_eq!(json["signed"], false);
+
+        let error = render_plugin(&manager, "wether", OutputFormat::Table, Lang::En).unwrap_err();
+        assert_eq!(exit_code(error.as_ref()), EXIT_NOT_FOUND);
+        assert!(render_error(error.as_ref(), Lang::En).contains("Did you mean 'weather'?"));
+    }
+
+    #[test]
+    fn plugin_disable_and_enable_persist() {
+        let fixture = nexus_core::testing::Fixture::new().unwrap();
+        let manager = stub_manager(&fixture);
+        let loader = fixture.loader();
+        let config_path = fixture.config_path();
+        let toggle = |name: &str, disable: bool| {
+            set_plugin_disabled(&manager, &loader, &config_path, name, disable, Lang::En)
+        };
+
+        let lines = toggle("weather", true).unwrap();
+        assert!(lines[0].contains("disabled"));
+        assert_eq!(lines[1], "⚠️  These plugins depend on 'weather': forecast");
+        assert_eq!(fixture.config().unwrap().plugin.disabled, vec!["weather"]);
+
+        let lines = toggle("weather", true).unwrap();
+        assert!(lines[0].contains("already disabled"));
+
+        let error = toggle("forecast", false).unwrap_err();
+        assert_eq!(exit_code(error.as_ref()), EXIT_NOT_FOUND);
+        let error = toggle("missing", true).unwrap_err();
+        assert_eq!(exit_code(error.as_ref()), EXIT_NOT_FOUND);
+
+        toggle("weather", false).unwrap();
+        assert!(fixture.config().unwrap().plugin.disabled.is_empty());
+    }
+
+    #[test]
+    fn unsigned_library_fails_verification() {
+        let fixture = nexus_core::testing::Fixture::builder()
+            .plugin_file("mock.so", b"v1".to_vec())
+            .build()
+            .unwrap();
+        let library = fixture.plugin_dir().join("mock.so");
+        let policy = fixture.config().unwrap().plugin.security_policy;
+
+        let error = verify_library(&library, &policy).unwrap_err();
+        assert!(error.to_string().contains("plugin is not signed"));
+        assert_eq!(exit_code(&error), EXIT_VERIFICATION_FAILED);
+
+        std::fs::write(signature_path(&library), "not a signature").unwrap();
+        assert!(verify_library(&library, &policy).is_err());
+    }
+}
@@ -0,0 +1,497 @@
+//! First-run onboarding wizard
+//!
+//! Walks a new user through the handful of choices that matter on day one
+//! and writes a commented `nexus.toml` reflecting them. All terminal access
+//! goes through [`Io`] so tests can script the answers.
+
+use std::fmt::{self, Write as _};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use crate::i18n::{tr, Lang};
+use crate::init::{self, WORKSPACE_DIR};
+
+/// Line-oriented terminal access used by the wizard
+pub trait Io {
+    /// Show a prompt and read one line of input; `None` when input is closed
+    fn prompt(&mut self, question: &str) -> io::Result<Option<String>>;
+
+    /// Print a line of output
+    fn say(&mut self, line: &str) -> io::Result<()>;
+}
+
+/// The process's stdin and stdout
+pub struct Terminal;
+
+impl Io for Terminal {
+    fn prompt(&mut self, question: &str) -> io::Result<Option<String>> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{} ", question)?;
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+
+    fn say(&mut self, line: &str) -> io::Result<()> {
+        writeln!(io::stdout(), "{}", line)
+    }
+}
+
+/// Starting point for the generated configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Quiet, no plugins
+    Minimal,
+    /// Verbose logs, local unsigned plugins allowed
+    Developer,
+    /// Structured file logging for long-running deployments
+    Operator,
+}
+
+impl Profile {
+    fn parse(answer: &str) -> Result<Self, String> {
+        match answer.to_lowercase().as_str() {
+            "minimal" | "m" => Ok(Self::Minimal),
+            "developer" | "d" => Ok(Self::Developer),
+            "operator" | "o" => Ok(Self::Operator),
+            _ => Err("choose minimal, developer or operator".to_string()),
+        }
+    }
+
+    const fn default_log_level(self) -> &'static str {
+        match self {
+            Self::Minimal => "warn",
+            Self::Developer => "debug",
+            Self::Operator => "info",
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Minimal => write!(f, "minimal"),
+            Self::Developer => write!(f, "developer"),
+            Self::Operator => write!(f, "operator"),
+        }
+    }
+}
+
+/// Optional LLM provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmProvider {
+    /// Local models through Ollama
+    Ollama,
+    /// OpenAI API
+    OpenAi,
+    /// Anthropic API
+    Anthropic,
+}
+
+impl LlmProvider {
+    fn parse(answer: &str) -> Result<Option<Self>, String> {
+        match answer.to_lowercase().as_str() {
+            "none" | "n" | "no" => Ok(None),
+            "ollama" => Ok(Some(Self::Ollama)),
+            "openai" => Ok(Some(Self::OpenAi)),
+            "anthropic" => Ok(Some(Self::Anthropic)),
+            _ => Err("choose none, ollama, openai or anthropic".to_string()),
+        }
+    }
+
+    /// Environment variable holding the API key, for hosted providers
+    const fn key_variable(self) -> Option<&'static str> {
+        match self {
+            Self::Ollama => None,
+            Self::OpenAi => Some("NEXUS_OPENAI_API_KEY"),
+            Self::Anthropic => Some("NEXUS_ANTHROPIC_API_KEY"),
+        }
+    }
+}
+
+impl fmt::Display for LlmProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ollama => write!(f, "ollama"),
+            Self::OpenAi => write!(f, "openai"),
+            Self::Anthropic => write!(f, "anthropic"),
+        }
+    }
+}
+
+/// Everything the wizard asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    /// Chosen profile
+    pub profile: Profile,
+    /// Agent data directory
+    pub data_dir: PathBuf,
+    /// Plugin directory, `None` when plugins are disabled
+    pub plugin_dir: Option<PathBuf>,
+    /// Log level
+    pub log_level: String,
+    /// LLM provider, if any
+    pub llm_provider: Option<LlmProvider>,
+}
+
+/// Ask a question until the answer parses; an empty answer takes the default
+fn ask<T>(
+    io: &mut dyn Io,
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> io::Result<T> {
+    loop {
+        let Some(answer) = io.prompt(&format!("{} [{}]", question, default))? else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input closed before setup finished",
+            ));
+        };
+        let answer = if answer.is_empty() {
+            default
+        } else {
+            answer.as_str()
+        };
+
+        match parse(answer) {
+            Ok(value) => return Ok(value),
+            Err(e) => io.say(&format!("  ✗ {}", e))?,
+        }
+    }
+}
+
+fn parse_yes_no(answer: &str) -> Result<bool, String> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        _ => Err("answer yes or no".to_string()),
+    }
+}
+
+/// Absolute, so the answer means the same from the config file's directory
+fn parse_directory(answer: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(answer);
+    if path.is_file() {
+        return Err(format!("{} is a file, not a directory", answer));
+    }
+    std::path::absolute(&path).map_err(|e| e.to_string())
+}
+
+fn parse_log_level(answer: &str) -> Result<String, String> {
+    let level = answer.to_lowercase();
+    match level.as_str() {
+        "trace" | "debug" | "info" | "warn" | "error" => Ok(level),
+        _ => Err("choose trace, debug, info, warn or error".to_string()),
+    }
+}
+
+/// Run the question sequence, with defaults rooted at `root`
+pub fn run_wizard(io: &mut dyn Io, root: &Path) -> io::Result<Answers> {
+    io.say("Welcome to NEXUS! A few questions to set up your workspace.")?;
+    io.say("Press Enter to accept the default shown in brackets.")?;
+
+    let profile = ask(
+        io,
+        "Profile (minimal/developer/operator)?",
+        "developer",
+        Profile::parse,
+    )?;
+    let data_dir = ask(
+        io,
+        "Data directory?",
+        &root.join("data").display().to_string(),
+        parse_directory,
+    )?;
+
+    let plugins_default = if profile == Profile::Minimal {
+        "n"
+    } else {
+        "y"
+    };
+    let plugin_dir = if ask(io, "Enable plugins?", plugins_default, parse_yes_no)? {
+        Some(ask(
+            io,
+            "Plugin directory?",
+            &root.join("plugins").display().to_string(),
+            parse_directory,
+        )?)
+    } else {
+        None
+    };
+
+    let log_level = ask(
+        io,
+        "Log level (trace/debug/info/warn/error)?",
+        profile.default_log_level(),
+        parse_log_level,
+    )?;
+    let llm_provider = ask(
+        io,
+        "LLM provider (none/ollama/openai/anthropic)?",
+        "none",
+        LlmProvider::parse,
+    )?;
+
+    Ok(Answers {
+        profile,
+        data_dir,
+        plugin_dir,
+        log_level,
+        llm_provider,
+    })
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// Render a commented `nexus.toml` for the answers
+pub fn render_config(answers: &Answers) -> String {
+    // Writing to a String cannot fail
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# NEXUS configuration generated by `nexus init --interactive`\n# Profile: {}\n",
+        answers.profile
+    );
+
+    out.push_str("[agent]\n# Where agents keep their state\n");
+    let _ = writeln!(
+        out,
+        "data_dir = {}\n",
+        toml_string(&answers.data_dir.display().to_string())
+    );
+
+    out.push_str("[plugin]\n");
+    if let Some(dir) = &answers.plugin_dir {
+        out.push_str("# Directories scanned for plugins\n");
+        let _ = writeln!(
+            out,
+            "plugin_dirs = [{}]",
+            toml_string(&dir.display().to_string())
+        );
+    } else {
+        out.push_str("# Plugins disabled; add a directory here to enable them\n");
+        out.push_str("plugin_dirs = []\n");
+    }
+    if answers.profile == Profile::Developer && answers.plugin_dir.is_some() {
+        out.push_str("\n[plugin.security_policy]\n");
+        out.push_str("# Allow unsigned plugins you build locally; turn off for shared machines\n");
+        out.push_str("allow_local_unsigned = true\n");
+    }
+
+    out.push_str("\n[logging]\n# One of trace, debug, info, warn, error\n");
+    let _ = writeln!(out, "level = {}", toml_string(&answers.log_level));
+    if answers.profile == Profile::Operator {
+        out.push_str("# Machine-readable logs written to a file for collection\n");
+        out.push_str("structured = true\nlog_to_file = true\n");
+        let _ = writeln!(
+            out,
+            "log_file_path = {}",
+            toml_string(&answers.data_dir.join("nexus.log").display().to_string())
+        );
+    }
+
+    if let Some(provider) = answers.llm_provider {
+        let _ = writeln!(out, "\n# LLM provider: {}", provider);
+        if let Some(variable) = provider.key_variable() {
+            let _ = writeln!(out, "# Set {} in your environment", variable);
+        }
+    }
+
+    out
+}
+
+/// Suggested next steps for the answers
+pub fn next_steps(answers: &Answers, config_path: &Path, lang: Lang) -> Vec<String> {
+    let mut steps = vec![tr(
+        lang,
+        "onboarding.step.review",
+        &[("path", &config_path.display())],
+    )];
+    if let Some(dir) = &answers.plugin_dir {
+        steps.push(tr(
+            lang,
+            "onboarding.step.plugins",
+            &[("dir", &dir.display())],
+        ));
+    }
+    if let Some(variable) = answers.llm_provider.and_then(LlmProvider::key_variable) {
+        steps.push(tr(
+            lang,
+            "onboarding.step.api_key",
+            &[("variable", &variable)],
+        ));
+    }
+    steps.push(tr(lang, "onboarding.step.show", &[]));
+    steps
+}
+
+/// Run the wizard and write the workspace in `dir`
+///
+/// Like `nexus init`, the workspace is `dir/nexus` and the configuration
+/// goes to [`init::config_path`], which is only replaced with `force`.
+pub fn run(io: &mut dyn Io, dir: &Path, force: bool, lang: Lang) -> io::Result<Answers> {
+    let config_path = init::config_path(dir);
+    init::check_overwrite(&config_path, force)
+        .map_err(|e| io::Error::new(io::ErrorKind::AlreadyExists, e.to_string()))?;
+
+    let answers = run_wizard(io, &std::path::absolute(dir)?.join(WORKSPACE_DIR))?;
+
+    std::fs::create_dir_all(&answers.data_dir)?;
+    if let Some(dir) = &answers.plugin_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+    if let Some(workspace) = config_path.parent() {
+        std::fs::create_dir_all(workspace)?;
+    }
+    std::fs::write(&config_path, render_config(&answers))?;
+
+    io.say("")?;
+    let wrote = tr(
+        lang,
+        "onboarding.wrote",
+        &[("path", &config_path.display())],
+    );
+    io.say(&format!("✅ {}", wrote))?;
+    io.say(&tr(lang, "onboarding.next_steps", &[]))?;
+    for step in next_steps(&answers, &config_path, lang) {
+        io.say(&format!("  • {}", step))?;
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nexus_core::config::Config;
+    use std::collections::VecDeque;
+
+    /// Scripted answers; running out of answers behaves like a closed stdin
+    struct Script {
+        answers: VecDeque<String>,
+        output: Vec<String>,
+    }
+
+    impl Script {
+        fn new(answers: &[&str]) -> Self {
+            Self {
+                answers: answers.iter().map(ToString::to_string).collect(),
+                output: Vec::new(),
+            }
+        }
+    }
+
+    impl Io for Script {
+        fn prompt(&mut self, question: &str) -> io::Result<Option<String>> {
+            self.output.push(question.to_string());
+            Ok(self.answers.pop_front())
+        }
+
+        fn say(&mut self, line: &str) -> io::Result<()> {
+            self.output.push(line.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_developer_defaults() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = init::config_path(dir.path());
+        let mut script = Script::new(&["", "", "", "", "", "openai"]);
+
+        let answers = run(&mut script, dir.path(), false, Lang::En).unwrap();
+        assert_eq!(answers.profile, Profile::Developer);
+
+        let config: Config =
+            toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let workspace = dir.path().join(WORKSPACE_DIR);
+        assert_eq!(config.agent.data_dir, workspace.join("data"));
+        assert_eq!(config.plugin.plugin_dirs, vec![workspace.join("plugins")]);
+        assert!(config.plugin.security_policy.allow_local_unsigned);
+        assert_eq!(config.logging.level, "debug");
+        assert!(workspace.join("plugins").is_dir());
+        assert!(script
+            .output
+            .iter()
+            .any(|l| l.contains("NEXUS_OPENAI_API_KEY")));
+    }
+
+    #[test]
+    fn test_declining_optional_features() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let data_dir = dir.path().join("state");
+        let mut script =
+            Script::new(&["operator", data_dir.to_str().unwrap(), "no", "warn", "none"]);
+
+        let answers = run_wizard(&mut script, dir.path()).unwrap();
+        assert_eq!(answers.plugin_dir, None);
+        assert_eq!(answers.llm_provider, None);
+
+        let rendered = render_config(&answers);
+        assert!(!rendered.contains("LLM provider"));
+        let config: Config = toml::from_str(&rendered).unwrap();
+        assert!(config.plugin.plugin_dirs.is_empty());
+        assert_eq!(config.agent.data_dir, dir.path().join("state"));
+        assert_eq!(config.logging.level, "warn");
+        assert!(config.logging.structured);
+    }
+
+    #[test]
+    fn test_invalid_answers_are_asked_again() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut script = Script::new(&["banana", "minimal", "", "", "loud", "error", ""]);
+
+        let answers = run_wizard(&mut script, dir.path()).unwrap();
+        assert_eq!(answers.profile, Profile::Minimal);
+        assert_eq!(answers.plugin_dir, None);
+        assert_eq!(answers.log_level, "error");
+        assert_eq!(
+            script
+                .output
+                .iter()
+                .filter(|l| l.starts_with("  ✗"))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_closed_input_never_blocks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut script = Script::new(&["developer"]);
+
+        let error = run_wizard(&mut script, dir.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_existing_config_not_overwritten() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = init::config_path(dir.path());
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "# mine").unwrap();
+
+        let error = run(&mut Script::new(&[]), dir.path(), false, Lang::En).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert!(error.to_string().contains("--force"));
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "# mine");
+
+        run(
+            &mut Script::new(&["minimal", "", "", "", ""]),
+            dir.path(),
+            true,
+            Lang::En,
+        )
+        .unwrap();
+        assert!(std::fs::read_to_string(&config_path)
+            .unwrap()
+            .contains("[logging]"));
+    }
+}
@@ -0,0 +1,503 @@
+//! Content-addressed storage for large agent outputs
+//!
+//! Bytecode, datasets and other large values are costly to pass around in a
+//! `serde_json::Value` and to repeat in every stored output. [`ArtifactStore`]
+//! keeps each of them once under `<data_dir>/artifacts`, named by its
+//! SHA-256, and hands out an [`ArtifactRef`] to embed in JSON instead.
+//! [`ArtifactStore::spill`] replaces the oversized fields of a value with refs
+//! and [`ArtifactStore::resolve`] puts them back.
+//!
+//! Content is written to a temporary file and renamed into place, so
+//! concurrent puts of the same content never expose a partial file. Reads are
+//! checked against the hash as they stream. Artifacts that are not pinned are
+//! removed by [`ArtifactStore::gc`] once nobody has stored them for a while.
+
+use ring::digest::{Context, SHA256};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+/// Directory under the agent data directory holding artifacts
+pub const ARTIFACTS_DIR: &str = "artifacts";
+
+/// Errors produced by the artifact store
+#[derive(Debug, Error)]
+pub enum ArtifactError {
+    /// A file of the store could not be read or written
+    #[error("failed to access {path}: {source}")]
+    Io {
+        /// File that failed
+        path: PathBuf,
+        /// Underlying error
+        source: io::Error,
+    },
+
+    /// No artifact is stored under this hash
+    #[error("artifact {0} not found")]
+    NotFound(String),
+
+    /// The stored content no longer matches its hash
+    #[error("artifact {0} does not match its hash")]
+    Corrupted(String),
+
+    /// The ref does not hold a hex SHA-256
+    #[error("invalid artifact hash '{0}'")]
+    InvalidRef(String),
+
+    /// A spilled value could not be converted to or from JSON
+    #[error("invalid spilled value: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ArtifactError + '_ {
+    move |source| ArtifactError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Stored content, as embedded in JSON
+///
+/// Serializes as `{"type": "artifact", "sha256": ..., "size": ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "artifact")]
+pub struct ArtifactRef {
+    /// Hex SHA-256 of the content
+    pub sha256: String,
+    /// Length of the content in bytes
+    pub size: u64,
+}
+
+impl ArtifactRef {
+    /// The ref `value` holds, if it is one
+    pub fn from_value(value: &Value) -> Option<Self> {
+        if value.get("type")? != "artifact" {
+            return None;
+        }
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// The ref as JSON, to embed in an output
+    pub fn to_value(&self) -> Value {
+        serde_json::json!({
+            "type": "artifact",
+            "sha256": self.sha256,
+            "size": self.size,
+        })
+    }
+}
+
+/// What [`ArtifactStore::gc`] removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Artifacts removed
+    pub removed: usize,
+    /// Bytes they took up
+    pub freed_bytes: u64,
+}
+
+/// Artifacts of agents, one file per distinct content
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    dir: PathBuf,
+}
+
+impl ArtifactStore {
+    /// Store under `<data_dir>/artifacts`
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(ARTIFACTS_DIR),
+        }
+    }
+
+    /// Directory holding the artifacts
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Store `bytes`, or find them already stored
+    pub fn put(&self, bytes: &[u8]) -> Result<ArtifactRef, ArtifactError> {
+        self.put_reader(bytes)
+    }
+
+    /// Store everything `reader` yields, without holding it in memory
+    pub fn put_reader(&self, mut reader: impl Read) -> Result<ArtifactRef, ArtifactError> {
+        let tmp_dir = self.dir.join("tmp");
+        fs::create_dir_all(&tmp_dir).map_err(io_error(&tmp_dir))?;
+        let tmp = tmp_dir.join(uuid::Uuid::new_v4().to_string());
+        let stored = write_hashed(&mut reader, &tmp).and_then(|artifact| {
+            let path = self.object_path(&artifact.sha256)?;
+            if path.exists() {
+                // Keep the stored copy, and count this put as a use for gc
+                fs::remove_file(&tmp).map_err(io_error(&tmp))?;
+                touch(&path)?;
+            } else {
+                let dir = path.parent().unwrap_or(&self.dir);
+                fs::create_dir_all(dir).map_err(io_error(dir))?;
+                // A concurrent put of the same content may rename first; the
+                // content is identical, so either file can win
+                fs::rename(&tmp, &path).map_err(io_error(&path))?;
+            }
+            Ok(artifact)
+        });
+        if stored.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        stored
+    }
+
+    /// Stream the content of `artifact`
+    ///
+    /// The reader fails with [`io::ErrorKind::InvalidData`] at the end of
+    /// the content if it doesn't match the hash.
+    pub fn get(&self, artifact: &ArtifactRef) -> Result<impl Read, ArtifactError> {
+        let path = self.object_path(&artifact.sha256)?;
+        let file = File::open(&path).map_err(|source| match source.kind() {
+            io::ErrorKind::NotFound => ArtifactError::NotFound(artifact.sha256.clone()),
+            _ => io_error(&path)(source),
+        })?;
+        Ok(VerifiedReader {
+            inner: file,
+            hasher: Context::new(&SHA256),
+            read: 0,
+            expected: artifact.clone(),
+        })
+    }
+
+    /// The content of `artifact`, checked against its hash
+    pub fn read(&self, artifact: &ArtifactRef) -> Result<Vec<u8>, ArtifactError> {
+        let mut content = Vec::new();
+        let path = self.object_path(&artifact.sha256)?;
+        self.get(artifact)?
+            .read_to_end(&mut content)
+            .map_err(|source| match source.kind() {
+                io::ErrorKind::InvalidData => ArtifactError::Corrupted(artifact.sha256.clone()),
+                _ => io_error(&path)(source),
+            })?;
+        Ok(content)
+    }
+
+    /// Keep `artifact` through garbage collection
+    pub fn pin(&self, artifact: &ArtifactRef) -> Result<(), ArtifactError> {
+        if !self.object_path(&artifact.sha256)?.exists() {
+            return Err(ArtifactError::NotFound(artifact.sha256.clone()));
+        }
+        let path = self.pin_path(&artifact.sha256)?;
+        let dir = path.parent().unwrap_or(&self.dir);
+        fs::create_dir_all(dir).map_err(io_error(dir))?;
+        File::create(&path).map_err(io_error(&path))?;
+        Ok(())
+    }
+
+    /// Let garbage collection remove `artifact` again
+    pub fn unpin(&self, artifact: &ArtifactRef) -> Result<(), ArtifactError> {
+        let path = self.pin_path(&artifact.sha256)?;
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io_error(&path)(e)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether `artifact` is pinned
+    pub fn is_pinned(&self, artifact: &ArtifactRef) -> Result<bool, ArtifactError> {
+        Ok(self.pin_path(&artifact.sha256)?.exists())
+    }
+
+    /// Remove unpinned artifacts last stored more than `ttl` ago
+    ///
+    /// Temporary files left by interrupted puts are removed too.
+    pub fn gc(&self, ttl: Duration) -> Result<GcReport, ArtifactError> {
+        self.gc_at(ttl, SystemTime::now())
+    }
+
+    fn gc_at(&self, ttl: Duration, now: SystemTime) -> Result<GcReport, ArtifactError> {
+        let expired = |path: &Path| -> Result<Option<u64>, ArtifactError> {
+            let metadata = fs::metadata(path).map_err(io_error(path))?;
+            let modified = metadata.modified().map_err(io_error(path))?;
+            let age = now.duration_since(modified).unwrap_or_default();
+            Ok((age >= ttl).then_some(metadata.len()))
+        };
+
+        let mut report = GcReport::default();
+        for path in list(&self.dir.join("objects"))?
+            .iter()
+            .map(|dir| list(dir))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+        {
+            let sha256 = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            if self.pin_path(sha256).is_ok_and(|pin| pin.exists()) {
+                continue;
+            }
+            if let Some(size) = expired(&path)? {
+                fs::remove_file(&path).map_err(io_error(&path))?;
+                report.removed += 1;
+                report.freed_bytes += size;
+            }
+        }
+        for tmp in list(&self.dir.join("tmp"))? {
+            if expired(&tmp)?.is_some() {
+                fs::remove_file(&tmp).map_err(io_error(&tmp))?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Store the fields of `value` whose JSON is larger than `threshold` bytes
+    ///
+    /// Each such field is replaced by its [`ArtifactRef`]. Nested fields are
+    /// spilled first, so a large object of small fields is stored whole and
+    /// one holding a single large field keeps its other fields inline. The
+    /// value itself always stays. Returns the number of fields spilled.
+    pub fn spill(&self, value: &mut Value, threshold: usize) -> Result<usize, ArtifactError> {
+        let children: Vec<&mut Value> = match value {
+            Value::Object(fields) => fields.values_mut().collect(),
+            Value::Array(items) => items.iter_mut().collect(),
+            _ => return Ok(0),
+        };
+        let mut spilled = 0;
+        for child in children {
+            if ArtifactRef::from_value(child).is_some() {
+                continue;
+            }
+            spilled += self.spill(child, threshold)?;
+            let json = serde_json::to_vec(child)?;
+            if json.len() > threshold {
+                *child = self.put(&json)?.to_value();
+                spilled += 1;
+            }
+        }
+        Ok(spilled)
+    }
+
+    /// Replace every [`ArtifactRef`] in `value` by the value it stores
+    ///
+    /// Returns the number of refs resolved.
+    pub fn resolve(&self, value: &mut Value) -> Result<usize, ArtifactError> {
+        if let Some(artifact) = ArtifactRef::from_value(value) {
+            *value = serde_json::from_slice(&self.read(&artifact)?)?;
+            return Ok(1 + self.resolve(value)?);
+        }
+        let children: Vec<&mut Value> = match value {
+            Value::Object(fields) => fields.values_mut().collect(),
+            Value::Array(items) => items.iter_mut().collect(),
+            _ => return Ok(0),
+        };
+        children
+            .into_iter()
+            .map(|child| self.resolve(child))
+            .sum()
+    }
+
+    /// File holding the content hashed `sha256`, sharded by its first byte
+    fn object_path(&self, sha256: &str) -> Result<PathBuf, ArtifactError> {
+        check_hash(sha256)?;
+        Ok(self.dir.join("objects").join(&sha256[..2]).join(sha256))
+    }
+
+    fn pin_path(&self, sha256: &str) -> Result<PathBuf, ArtifactError> {
+        check_hash(sha256)?;
+        Ok(self.dir.join("pins").join(sha256))
+    }
+}
+
+/// Refs come from outputs, so their hash must not be able to name other paths
+fn check_hash(sha256: &str) -> Result<(), ArtifactError> {
+    let valid = sha256.len() == 64
+        && sha256
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c));
+    if valid {
+        Ok(())
+    } else {
+        Err(ArtifactError::InvalidRef(sha256.to_string()))
+    }
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{:02x}", b);
+        hex
+    })
+}
+
+/// Copy `reader` into `path`, hashing it on the way
+fn write_hashed(reader: &mut impl Read, path: &Path) -> Result<ArtifactRef, ArtifactError> {
+    let mut file = File::create(path).map_err(io_error(path))?;
+    let mut hasher = Context::new(&SHA256);
+    let mut buffer = vec![0; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(io_error(path)(e)),
+        };
+        hasher.update(&buffer[..read]);
+        file.write_all(&buffer[..read]).map_err(io_error(path))?;
+        size += read as u64;
+    }
+    file.sync_all().map_err(io_error(path))?;
+    Ok(ArtifactRef {
+        sha256: hex(hasher.finish().as_ref()),
+        size,
+    })
+}
+
+/// Mark `path` as modified now
+fn touch(path: &Path) -> Result<(), ArtifactError> {
+    File::options()
+        .append(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+        .map_err(io_error(path))
+}
+
+/// Entries of `dir`, none when it doesn't exist
+fn list(dir: &Path) -> Result<Vec<PathBuf>, ArtifactError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(dir)(e)),
+    };
+    entries
+        .map(|entry| entry.map(|entry| entry.path()).map_err(io_error(dir)))
+        .collect()
+}
+
+/// Reader failing at the end when the content doesn't match its ref
+struct VerifiedReader {
+    inner: File,
+    hasher: Context,
+    read: u64,
+    expected: ArtifactRef,
+}
+
+impl Read for VerifiedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            let sha256 = hex(self.hasher.clone().finish().as_ref());
+            if self.read != self.expected.size || sha256 != self.expected.sha256 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    ArtifactError::Corrupted(self.expected.sha256.clone()),
+                ));
+            }
+        }
+        self.hasher.update(&buf[..read]);
+        self.read += read as u64;
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn objects(store: &ArtifactStore) -> usize {
+        list(&store.dir().join("objects"))
+            .unwrap()
+            .iter()
+            .map(|dir| list(dir).unwrap().len())
+            .sum()
+    }
+
+    #[test]
+    fn test_identical_content_is_stored_once() {
+        let dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(dir.path());
+
+        let first = store.put(b"contract bytecode").unwrap();
+        let second = store.put_reader(&b"contract bytecode"[..]).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.size, 17);
+        assert_eq!(
+            first.sha256,
+            hex(ring::digest::digest(&SHA256, b"contract bytecode").as_ref())
+        );
+        store.put(b"dataset").unwrap();
+
+        assert_eq!(objects(&store), 2);
+        assert!(list(&store.dir().join("tmp")).unwrap().is_empty());
+        assert_eq!(store.read(&first).unwrap(), b"contract bytecode");
+    }
+
+    #[test]
+    fn test_gc_keeps_pinned_and_recent_artifacts() {
+        let dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(dir.path());
+        let pinned = store.put(b"pinned").unwrap();
+        let unpinned = store.put(b"unpinned").unwrap();
+        store.pin(&pinned).unwrap();
+        let ttl = Duration::from_secs(3600);
+
+        assert_eq!(store.gc(ttl).unwrap(), GcReport::default());
+        let later = SystemTime::now() + ttl;
+        assert_eq!(
+            store.gc_at(ttl, later).unwrap(),
+            GcReport {
+                removed: 1,
+                freed_bytes: 8,
+            }
+        );
+        assert!(matches!(store.read(&unpinned), Err(ArtifactError::NotFound(_))));
+        assert_eq!(store.read(&pinned).unwrap(), b"pinned");
+
+        store.unpin(&pinned).unwrap();
+        assert!(!store.is_pinned(&pinned).unwrap());
+        assert_eq!(store.gc_at(ttl, later).unwrap().removed, 1);
+    }
+
+    #[test]
+    fn test_spilled_fields_resolve_to_the_original() {
+        let dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(dir.path());
+        let original = json!({
+            "status": "ok",
+            "bytecode": "60".repeat(200),
+            "rows": [{"id": 1, "blob": "x".repeat(300)}, {"id": 2}],
+        });
+
+        let mut value = original.clone();
+        assert_eq!(store.spill(&mut value, 200).unwrap(), 2);
+        assert_eq!(value["status"], "ok");
+        assert!(ArtifactRef::from_value(&value["bytecode"]).is_some());
+        // The row lost its large field, which made it small enough to stay inline
+        assert!(ArtifactRef::from_value(&value["rows"][0]["blob"]).is_some());
+        assert_eq!(value["rows"][0]["id"], 1);
+        assert!(serde_json::to_vec(&value).unwrap().len() < 400);
+
+        assert_eq!(store.resolve(&mut value).unwrap(), 2);
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn test_corrupted_artifact_is_detected() {
+        let dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(dir.path());
+        let artifact = store.put(b"original content").unwrap();
+        let path = store.object_path(&artifact.sha256).unwrap();
+        fs::write(&path, b"tampered content").unwrap();
+
+        let mut content = Vec::new();
+        let error = store.get(&artifact).unwrap().read_to_end(&mut content).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(store.read(&artifact), Err(ArtifactError::Corrupted(_))));
+
+        let forged = ArtifactRef {
+            sha256: "../../etc/passwd".to_string(),
+            size: 0,
+        };
+        assert!(matches!(store.read(&forged), Err(ArtifactError::InvalidRef(_))));
+    }
+}
@@ -0,0 +1,356 @@
+//! Command bus for NEXUS
+//!
+//! Commands are plain types implementing [`Command`]; each command type has
+//! one handler, registered by type. Cross-cutting concerns such as rate
+//! limiting, audit logging and timing are [`BusMiddleware`] layers that run
+//! around every dispatch in registration order. A middleware that rejects a
+//! dispatch short-circuits it, and the layers that already ran still see the
+//! rejection in their `after` hook.
+//!
+//! The bus is where a user action enters the system, so each dispatch
+//! carries a [`CorrelationId`]: the one already in effect, or a fresh one.
+//! The handler runs with that id in scope.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use thiserror::Error;
+
+use crate::correlation::CorrelationId;
+
+/// Errors produced by a dispatch
+#[derive(Debug, Error)]
+pub enum BusError {
+    /// No handler is registered for the command type
+    #[error("no handler registered for command '{0}'")]
+    NoHandler(&'static str),
+
+    /// A middleware refused the dispatch
+    #[error("command '{command}' rejected: {reason}")]
+    Rejected {
+        /// Command name
+        command: &'static str,
+        /// Why the middleware refused it
+        reason: String,
+    },
+
+    /// The handler failed
+    #[error("command '{command}' failed: {source}")]
+    Failed {
+        /// Command name
+        command: &'static str,
+        /// Handler error
+        source: anyhow::Error,
+    },
+}
+
+/// A request that can be dispatched on the bus
+pub trait Command: Send + 'static {
+    /// What the handler returns
+    type Response: Send + 'static;
+
+    /// Name used in errors, logs and metrics
+    fn name() -> &'static str {
+        let full = std::any::type_name::<Self>();
+        full.rsplit("::").next().unwrap_or(full)
+    }
+}
+
+/// A dispatch as seen by middleware
+#[derive(Debug, Clone)]
+pub struct Dispatch {
+    /// Sequence number of the dispatch on this bus
+    pub id: u64,
+    /// Command name
+    pub command: &'static str,
+    /// When the dispatch started
+    pub started: Instant,
+    /// Id shared with everything the dispatch causes
+    pub correlation_id: CorrelationId,
+}
+
+/// Hooks run around every dispatch
+pub trait BusMiddleware: Send + Sync {
+    /// Called before the handler; an error rejects the dispatch
+    fn before(&self, dispatch: &Dispatch) -> Result<(), BusError> {
+        let _ = dispatch;
+        Ok(())
+    }
+
+    /// Called once the dispatch finished, was rejected, or failed
+    fn after(&self, dispatch: &Dispatch, result: Result<(), &BusError>) {
+        let _ = (dispatch, result);
+    }
+}
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+type HandlerFn<C> =
+    Arc<dyn Fn(C) -> BoxFuture<anyhow::Result<<C as Command>::Response>> + Send + Sync>;
+
+/// Dispatches commands to their handlers through the middleware stack
+#[derive(Default)]
+pub struct CommandBus {
+    handlers: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    middleware: Vec<Arc<dyn BusMiddleware>>,
+    next_id: AtomicU64,
+}
+
+impl std::fmt::Debug for CommandBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandBus")
+            .field("handlers", &self.handlers.len())
+            .field("middleware", &self.middleware.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CommandBus {
+    /// Create a bus with no handlers or middleware
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a middleware layer; layers run in the order they are added
+    pub fn layer(mut self, middleware: impl BusMiddleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Register the handler for command type `C`, replacing any previous one
+    pub fn register<C, F, Fut>(&mut self, handler: F)
+    where
+        C: Command,
+        F: Fn(C) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<C::Response>> + Send + 'static,
+    {
+        let handler: HandlerFn<C> = Arc::new(move |command| Box::pin(handler(command)));
+        if self
+            .handlers
+            .insert(TypeId::of::<C>(), Box::new(handler))
+            .is_some()
+        {
+            tracing::warn!("Replaced handler for command '{}'", C::name());
+        }
+    }
+
+    /// Whether a handler is registered for `C`
+    pub fn handles<C: Command>(&self) -> bool {
+        self.handlers.contains_key(&TypeId::of::<C>())
+    }
+
+    /// Run `command` through the middleware and its handler
+    pub async fn dispatch<C: Command>(&self, command: C) -> Result<C::Response, BusError> {
+        let dispatch = Dispatch {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            command: C::name(),
+            started: Instant::now(),
+            correlation_id: CorrelationId::current_or_new(),
+        };
+
+        let mut entered = 0;
+        let mut outcome = Ok(());
+        for middleware in &self.middleware {
+            if let Err(e) = middleware.before(&dispatch) {
+                outcome = Err(e);
+                break;
+            }
+            entered += 1;
+        }
+
+        let response = match outcome {
+            Ok(()) => match self.handler::<C>() {
+                Some(handler) => dispatch
+                    .correlation_id
+                    .scope(handler(command))
+                    .await
+                    .map_err(|source| BusError::Failed {
+                    command: dispatch.command,
+                    source,
+                }),
+                None => Err(BusError::NoHandler(dispatch.command)),
+            },
+            Err(e) => Err(e),
+        };
+
+        // Unwind through the layers that saw the dispatch start
+        for middleware in self.middleware[..entered].iter().rev() {
+            middleware.after(&dispatch, response.as_ref().map(|_| ()));
+        }
+
+        response
+    }
+
+    fn handler<C: Command>(&self) -> Option<HandlerFn<C>> {
+        self.handlers
+            .get(&TypeId::of::<C>())
+            .and_then(|handler| handler.downcast_ref::<HandlerFn<C>>())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Echo(String);
+
+    impl Command for Echo {
+        type Response = String;
+    }
+
+    struct Unhandled;
+
+    impl Command for Unhandled {
+        type Response = ();
+    }
+
+    /// Allows a fixed number of dispatches
+    struct RateLimit {
+        remaining: Mutex<u32>,
+    }
+
+    impl BusMiddleware for RateLimit {
+        fn before(&self, dispatch: &Dispatch) -> Result<(), BusError> {
+            let mut remaining = self.remaining.lock().unwrap();
+            if *remaining == 0 {
+                return Err(BusError::Rejected {
+                    command: dispatch.command,
+                    reason: "rate limit exceeded".to_string(),
+                });
+            }
+            *remaining -= 1;
+            Ok(())
+        }
+    }
+
+    /// Records every dispatch and how it ended
+    #[derive(Clone, Default)]
+    struct Audit {
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl BusMiddleware for Audit {
+        fn after(&self, dispatch: &Dispatch, result: Result<(), &BusError>) {
+            let entry = match result {
+                Ok(()) => format!("{} ok", dispatch.command),
+                Err(e) => format!("{} error: {}", dispatch.command, e),
+            };
+            self.log.lock().unwrap().push(entry);
+        }
+    }
+
+    fn echo_bus(audit: &Audit) -> CommandBus {
+        let mut bus = CommandBus::new().layer(audit.clone()).layer(RateLimit {
+            remaining: Mutex::new(2),
+        });
+        bus.register(|Echo(text)| async move { Ok(text) });
+        bus
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_rejects_third_call() {
+        let audit = Audit::default();
+        let bus = echo_bus(&audit);
+
+        assert_eq!(bus.dispatch(Echo("a".into())).await.unwrap(), "a");
+        assert_eq!(bus.dispatch(Echo("b".into())).await.unwrap(), "b");
+        assert!(matches!(
+            bus.dispatch(Echo("c".into())).await,
+            Err(BusError::Rejected {
+                command: "Echo",
+                ..
+            })
+        ));
+
+        assert_eq!(
+            *audit.log.lock().unwrap(),
+            vec![
+                "Echo ok",
+                "Echo ok",
+                "Echo error: command 'Echo' rejected: rate limit exceeded",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_in_registration_order() {
+        struct Tag(&'static str, Arc<Mutex<Vec<String>>>);
+
+        impl BusMiddleware for Tag {
+            fn before(&self, _: &Dispatch) -> Result<(), BusError> {
+                self.1.lock().unwrap().push(format!("before {}", self.0));
+                Ok(())
+            }
+
+            fn after(&self, _: &Dispatch, _: Result<(), &BusError>) {
+                self.1.lock().unwrap().push(format!("after {}", self.0));
+            }
+        }
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = CommandBus::new()
+            .layer(Tag("outer", Arc::clone(&order)))
+            .layer(Tag("inner", Arc::clone(&order)));
+        bus.register(|Echo(text)| async move { Ok(text) });
+        bus.dispatch(Echo("x".into())).await.unwrap();
+
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["before outer", "before inner", "after inner", "after outer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handler_runs_with_dispatch_correlation_id() {
+        struct Seen(Arc<Mutex<Vec<CorrelationId>>>);
+
+        impl BusMiddleware for Seen {
+            fn before(&self, dispatch: &Dispatch) -> Result<(), BusError> {
+                self.0.lock().unwrap().push(dispatch.correlation_id);
+                Ok(())
+            }
+        }
+
+        struct WhoAmI;
+
+        impl Command for WhoAmI {
+            type Response = Option<CorrelationId>;
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = CommandBus::new().layer(Seen(Arc::clone(&seen)));
+        bus.register(|_: WhoAmI| async { Ok(CorrelationId::current()) });
+
+        // Each top-level dispatch gets its own id
+        let first = bus.dispatch(WhoAmI).await.unwrap().unwrap();
+        let second = bus.dispatch(WhoAmI).await.unwrap().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(*seen.lock().unwrap(), vec![first, second]);
+
+        // A dispatch inside an existing action keeps its id
+        let outer = CorrelationId::new();
+        let inner = outer.scope(bus.dispatch(WhoAmI)).await.unwrap();
+        assert_eq!(inner, Some(outer));
+    }
+
+    #[tokio::test]
+    async fn test_missing_handler_and_handler_failure() {
+        let audit = Audit::default();
+        let mut bus = CommandBus::new().layer(audit.clone());
+        assert!(matches!(
+            bus.dispatch(Unhandled).await,
+            Err(BusError::NoHandler("Unhandled"))
+        ));
+
+        bus.register(|_: Echo| async { Err::<String, _>(anyhow::anyhow!("boom")) });
+        assert!(bus.handles::<Echo>());
+        let error = bus.dispatch(Echo("x".into())).await.unwrap_err();
+        assert_eq!(error.to_string(), "command 'Echo' failed: boom");
+        assert_eq!(audit.log.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,582 @@
+//! Canonical JSON serialization for NEXUS
+//!
+//! Anything that hashes JSON (cache keys, replay comparison, idempotency
+//! keys, audit chaining) must agree on one byte representation, regardless
+//! of map insertion order or `serde_json` version. The canonical form is a
+//! stable contract; changing it invalidates every stored hash.
+//!
+//! Canonical form:
+//! - no insignificant whitespace
+//! - object keys sorted by their UTF-8 bytes, recursively; array order kept
+//! - strings escaped exactly as `serde_json` escapes them
+//! - integers written in decimal; floats with no fractional part and a
+//!   magnitude below 1e21 written as integers (`1.0` becomes `1`), other
+//!   floats in shortest round-trip form; `-0` is written as `0`
+//! - NaN and infinities are rejected
+//! - optionally, strings and keys normalized to Unicode NFC
+
+use ring::digest;
+use serde::ser::{self, Serialize};
+use serde_json::Value;
+use std::fmt;
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+/// Errors produced while canonicalizing a value
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CanonicalError {
+    /// A float was NaN or infinite
+    #[error("non-finite number cannot be canonicalized")]
+    NonFiniteNumber,
+
+    /// Two object keys became identical after Unicode normalization
+    #[error("duplicate object key after normalization: {0}")]
+    DuplicateKey(String),
+
+    /// The value could not be serialized to JSON
+    #[error("serialization failed: {0}")]
+    Serialization(String),
+}
+
+impl ser::Error for CanonicalError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Serialization(msg.to_string())
+    }
+}
+
+/// Produces the canonical form of JSON values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Canonicalizer {
+    normalize_unicode: bool,
+}
+
+impl Canonicalizer {
+    /// Create a canonicalizer with default options
+    pub const fn new() -> Self {
+        Self {
+            normalize_unicode: false,
+        }
+    }
+
+    /// Normalize all strings and object keys to Unicode NFC
+    pub const fn with_unicode_normalization(mut self, enabled: bool) -> Self {
+        self.normalize_unicode = enabled;
+        self
+    }
+
+    /// Canonical string form of a JSON value
+    pub fn to_canonical_string(self, value: &Value) -> Result<String, CanonicalError> {
+        let mut output = String::new();
+        self.write_value(value, &mut output)?;
+        Ok(output)
+    }
+
+    /// Canonical string form of any serializable value
+    ///
+    /// Unlike `serde_json::to_value`, which silently turns NaN into `null`,
+    /// non-finite floats are rejected.
+    pub fn serialize<T: Serialize + ?Sized>(self, value: &T) -> Result<String, CanonicalError> {
+        value.serialize(FiniteCheck)?;
+        let value = serde_json::to_value(value)
+            .map_err(|e| CanonicalError::Serialization(e.to_string()))?;
+        self.to_canonical_string(&value)
+    }
+
+    /// SHA-256 of the canonical form of a JSON value
+    pub fn hash(self, value: &Value) -> Result<[u8; 32], CanonicalError> {
+        Ok(sha256(self.to_canonical_string(value)?.as_bytes()))
+    }
+
+    fn write_value(self, value: &Value, output: &mut String) -> Result<(), CanonicalError> {
+        match value {
+            Value::Null => output.push_str("null"),
+            Value::Bool(b) => output.push_str(if *b { "true" } else { "false" }),
+            Value::Number(number) => write_number(number, output)?,
+            Value::String(s) => self.write_string(s, output),
+            Value::Array(items) => {
+                output.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    self.write_value(item, output)?;
+                }
+                output.push(']');
+            }
+            Value::Object(map) => {
+                let mut entries = map
+                    .iter()
+                    .map(|(key, value)| (self.normalize(key), value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+                if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+                    return Err(CanonicalError::DuplicateKey(pair[0].0.clone()));
+                }
+
+                output.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    write_escaped(key, output);
+                    output.push(':');
+                    self.write_value(value, output)?;
+                }
+                output.push('}');
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_string(self, s: &str, output: &mut String) {
+        write_escaped(&self.normalize(s), output);
+    }
+
+    fn normalize(self, s: &str) -> String {
+        if self.normalize_unicode {
+            s.nfc().collect()
+        } else {
+            s.to_string()
+        }
+    }
+}
+
+/// Canonical string form of a JSON value with default options
+pub fn to_canonical_string(value: &Value) -> Result<String, CanonicalError> {
+    Canonicalizer::new().to_canonical_string(value)
+}
+
+/// SHA-256 of the canonical form of a JSON value with default options
+pub fn canonical_hash(value: &Value) -> Result<[u8; 32], CanonicalError> {
+    Canonicalizer::new().hash(value)
+}
+
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(digest::digest(&digest::SHA256, bytes).as_ref());
+    hash
+}
+
+fn write_escaped(s: &str, output: &mut String) {
+    // serde_json's string escaping is fixed by the JSON spec and infallible for &str
+    output.push_str(&serde_json::to_string(s).unwrap_or_default());
+}
+
+fn write_number(number: &serde_json::Number, output: &mut String) -> Result<(), CanonicalError> {
+    if let Some(n) = number.as_u64() {
+        output.push_str(&n.to_string());
+    } else if let Some(n) = number.as_i64() {
+        output.push_str(&n.to_string());
+    } else {
+        let f = number.as_f64().ok_or(CanonicalError::NonFiniteNumber)?;
+        write_float(f, output)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_float(f: f64, output: &mut String) -> Result<(), CanonicalError> {
+    if !f.is_finite() {
+        return Err(CanonicalError::NonFiniteNumber);
+    }
+
+    if f == 0.0 {
+        // Covers -0.0 as well
+        output.push('0');
+    } else if f.fract() == 0.0 && f.abs() < 1e21 {
+        // Exact: integral floats below 1e21 fit in i128 without rounding
+        output.push_str(&(f as i128).to_string());
+    } else {
+        output.push_str(
+            &serde_json::Number::from_f64(f)
+                .ok_or(CanonicalError::NonFiniteNumber)?
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Serializer that only checks a value for non-finite floats
+struct FiniteCheck;
+
+type CheckResult = Result<(), CanonicalError>;
+
+macro_rules! accept {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(fn $method(self, _v: $ty) -> CheckResult { Ok(()) })*
+    };
+}
+
+impl ser::Serializer for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    accept!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+    );
+
+    fn serialize_f32(self, v: f32) -> CheckResult {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> CheckResult {
+        if v.is_finite() {
+            Ok(())
+        } else {
+            Err(CanonicalError::NonFiniteNumber)
+        }
+    }
+
+    fn serialize_none(self) -> CheckResult {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> CheckResult {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> CheckResult {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> CheckResult {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+    ) -> CheckResult {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> CheckResult {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> CheckResult {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, CanonicalError> {
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> CheckResult {
+        key.serialize(Self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for FiniteCheck {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> CheckResult {
+        value.serialize(Self)
+    }
+
+    fn end(self) -> CheckResult {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use serde::Serialize;
+    use serde_json::json;
+
+    fn arb_json() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            any::<i64>().prop_map(Value::from),
+            // Exact binary fractions that are never integral, so they survive text round-trips
+            (any::<i32>(), 1..8i32)
+                .prop_map(|(n, frac)| Value::from(f64::from(n) + f64::from(frac) / 8.0)),
+            "\\PC{0,12}".prop_map(Value::String),
+        ];
+
+        leaf.prop_recursive(4, 64, 6, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..6).prop_map(Value::Array),
+                prop::collection::btree_map("[a-z]{0,6}", inner, 0..6)
+                    .prop_map(|map| Value::Object(map.into_iter().collect())),
+            ]
+        })
+    }
+
+    /// Render an object's entries in reverse order to simulate a different insertion order
+    fn render_reversed(value: &Value) -> String {
+        match value {
+            Value::Object(map) => {
+                let entries = map
+                    .iter()
+                    .rev()
+                    .map(|(k, v)| {
+                        format!(
+                            "{}:{}",
+                            serde_json::to_string(k).unwrap(),
+                            render_reversed(v)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", entries.join(","))
+            }
+            Value::Array(items) => {
+                let items = items.iter().map(render_reversed).collect::<Vec<_>>();
+                format!("[{}]", items.join(","))
+            }
+            other => other.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sorted_keys_and_compact_output() {
+        let value = json!({"b": 1, "a": {"d": [3, 2], "c": null}});
+        assert_eq!(
+            to_canonical_string(&value).unwrap(),
+            r#"{"a":{"c":null,"d":[3,2]},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_number_formatting() {
+        assert_eq!(to_canonical_string(&json!(1.0)).unwrap(), "1");
+        assert_eq!(to_canonical_string(&json!(-0.0)).unwrap(), "0");
+        assert_eq!(to_canonical_string(&json!(0.5)).unwrap(), "0.5");
+        assert_eq!(to_canonical_string(&json!(-42)).unwrap(), "-42");
+        assert_eq!(canonical_hash(&json!(-0.0)), canonical_hash(&json!(0)));
+    }
+
+    #[test]
+    fn test_non_finite_rejected() {
+        #[derive(Serialize)]
+        struct Reading {
+            value: f64,
+        }
+
+        let canonicalizer = Canonicalizer::new();
+        assert_eq!(
+            canonicalizer.serialize(&Reading { value: f64::NAN }),
+            Err(CanonicalError::NonFiniteNumber)
+        );
+        assert_eq!(
+            canonicalizer.serialize(&vec![1.0, f64::INFINITY]),
+            Err(CanonicalError::NonFiniteNumber)
+        );
+        assert_eq!(
+            canonicalizer.serialize(&Reading { value: 2.5 }).unwrap(),
+            r#"{"value":2.5}"#
+        );
+    }
+
+    #[test]
+    fn test_unicode_normalization() {
+        let composed = json!({"caf\u{e9}": "\u{e9}"});
+        let decomposed = json!({"cafe\u{301}": "e\u{301}"});
+
+        assert_ne!(canonical_hash(&composed), canonical_hash(&decomposed));
+
+        let nfc = Canonicalizer::new().with_unicode_normalization(true);
+        assert_eq!(nfc.hash(&composed), nfc.hash(&decomposed));
+    }
+
+    #[test]
+    fn test_duplicate_keys_after_normalization() {
+        let value = json!({"caf\u{e9}": 1, "cafe\u{301}": 2});
+        let nfc = Canonicalizer::new().with_unicode_normalization(true);
+
+        assert!(matches!(
+            nfc.to_canonical_string(&value),
+            Err(CanonicalError::DuplicateKey(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_permuted_keys_hash_identically(value in arb_json()) {
+            let reordered: Value = serde_json::from_str(&render_reversed(&value)).unwrap();
+            prop_assert_eq!(canonical_hash(&value), canonical_hash(&reordered));
+        }
+
+        #[test]
+        fn prop_different_values_hash_differently(a in arb_json(), b in arb_json()) {
+            prop_assume!(a != b);
+            prop_assert_ne!(canonical_hash(&a), canonical_hash(&b));
+        }
+
+        #[test]
+        fn prop_serde_round_trip_preserves_hash(value in arb_json()) {
+            let text = serde_json::to_string(&value).unwrap();
+            let parsed: Value = serde_json::from_str(&text).unwrap();
+            prop_assert_eq!(canonical_hash(&value), canonical_hash(&parsed));
+
+            let canonical = to_canonical_string(&value).unwrap();
+            let reparsed: Value = serde_json::from_str(&canonical).unwrap();
+            prop_assert_eq!(canonical_hash(&value), canonical_hash(&reparsed));
+        }
+    }
+}
@@ -5,13 +5,21 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use tracing::{info, warn};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
 
-use crate::security::SecurityConfig;
+use crate::error::NexusError;
+use crate::paths::{PathResolver, NEXUS_HOME_ENV};
+use crate::schema::{is_schema_key, ConfigKeyDoc};
+use crate::SecurityConfig;
+use crate::watch::WatchConfig;
+#[cfg(feature = "web3")]
+use crate::web3::{ChainInfo, ChainRegistry};
 
 /// Main configuration structure for NEXUS
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     /// Security configuration
     pub security: SecurityConfig,
@@ -21,6 +29,8 @@ pub struct Config {
     pub plugin: PluginConfig,
     /// Logging configuration
     pub logging: LoggingConfig,
+    /// File watching for config and plugin hot reload
+    pub watch: WatchConfig,
     /// Web3 configuration
     #[cfg(feature = "web3")]
     pub web3: Web3Config,
@@ -33,6 +43,7 @@ impl Default for Config {
             agent: AgentConfig::default(),
             plugin: PluginConfig::default(),
             logging: LoggingConfig::default(),
+            watch: WatchConfig::default(),
             #[cfg(feature = "web3")]
             web3: Web3Config::default(),
         }
@@ -41,6 +52,7 @@ impl Default for Config {
 
 /// Agent system configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AgentConfig {
     /// Maximum number of concurrent agents
     pub max_concurrent_agents: usize,
@@ -52,6 +64,10 @@ pub struct AgentConfig {
     pub enable_sandboxing: bool,
     /// Default resource limits
     pub default_resource_limits: AgentResourceLimits,
+    /// Golden-run regression checks
+    pub golden: GoldenConfig,
+    /// Storage of large output values
+    pub artifacts: ArtifactConfig,
 }
 
 impl Default for AgentConfig {
@@ -62,12 +78,71 @@ impl Default for AgentConfig {
             data_dir: PathBuf::from("./data/agents"),
             enable_sandboxing: true,
             default_resource_limits: AgentResourceLimits::default(),
+            golden: GoldenConfig::default(),
+            artifacts: ArtifactConfig::default(),
+        }
+    }
+}
+
+/// Golden-run configuration for agents expected to be deterministic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoldenConfig {
+    /// Whether outputs are recorded, verified against recordings, or neither
+    pub mode: GoldenMode,
+    /// Output fields that differ between identical runs, as path patterns
+    /// such as `metadata.duration_ms` or `**.timestamp`
+    pub volatile_fields: Vec<String>,
+    /// Differences listed in the error of a failed verification
+    pub max_reported_diffs: usize,
+}
+
+/// What to do with golden outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoldenMode {
+    /// Neither record nor verify
+    Off,
+    /// Store each output as the golden copy for its input
+    Record,
+    /// Fail runs whose output differs from the golden copy
+    Verify,
+}
+
+impl Default for GoldenConfig {
+    fn default() -> Self {
+        Self {
+            mode: GoldenMode::Off,
+            volatile_fields: vec![
+                "**.timestamp".to_string(),
+                "**.duration_ms".to_string(),
+                "**.execution_time_ms".to_string(),
+            ],
+            max_reported_diffs: 5,
+        }
+    }
+}
+
+/// Storage of large output values in the artifact store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtifactConfig {
+    /// Output fields whose serialized size exceeds this are stored as
+    /// artifacts when outputs are persisted; 0 keeps them inline
+    pub spill_threshold_bytes: usize,
+}
+
+impl Default for ArtifactConfig {
+    fn default() -> Self {
+        Self {
+            spill_threshold_bytes: 64 * 1024,
         }
     }
 }
 
 /// Agent resource limits configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AgentResourceLimits {
     /// Maximum memory usage in MB
     pub max_memory_mb: u64,
@@ -77,6 +152,12 @@ pub struct AgentResourceLimits {
     pub max_file_ops_per_sec: u32,
     /// Maximum network requests per minute
     pub max_network_requests_per_min: u32,
+    /// Maximum serialized size of an agent's output data
+    pub max_output_bytes: usize,
+    /// Maximum size of each output metadata value
+    pub max_metadata_bytes: usize,
+    /// Truncate oversized output instead of rejecting it
+    pub truncate_output: bool,
 }
 
 impl Default for AgentResourceLimits {
@@ -86,12 +167,16 @@ impl Default for AgentResourceLimits {
             max_cpu_percent: 50.0,
             max_file_ops_per_sec: 100,
             max_network_requests_per_min: 1000,
+            max_output_bytes: 4 * 1024 * 1024,
+            max_metadata_bytes: 64 * 1024,
+            truncate_output: false,
         }
     }
 }
 
 /// Plugin system configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PluginConfig {
     /// Plugin directories to scan
     pub plugin_dirs: Vec<PathBuf>,
@@ -101,6 +186,10 @@ pub struct PluginConfig {
     pub security_policy: PluginSecurityPolicy,
     /// Maximum plugin load time in seconds
     pub max_load_time_secs: u64,
+    /// Plugins found in the plugin directories but not loaded
+    pub disabled: Vec<String>,
+    /// Health monitoring and restart policy
+    pub supervision: PluginSupervisionConfig,
 }
 
 impl Default for PluginConfig {
@@ -110,21 +199,70 @@ impl Default for PluginConfig {
             enable_hot_reload: false, // Disabled by default for security
             security_policy: PluginSecurityPolicy::default(),
             max_load_time_secs: 30,
+            disabled: Vec::new(),
+            supervision: PluginSupervisionConfig::default(),
+        }
+    }
+}
+
+/// How loaded plugins are health checked and restarted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSupervisionConfig {
+    /// Seconds between health checks; 0 turns supervision off
+    pub health_check_interval_secs: u64,
+    /// Unhealthy results in a row before a plugin is restarted
+    pub failure_threshold: u32,
+    /// Restarts tried before a plugin is unloaded for good
+    pub max_restarts: u32,
+    /// Wait before the second restart in milliseconds, doubled for each one after
+    pub restart_backoff_ms: u64,
+    /// Longest wait between restarts, in milliseconds
+    pub max_restart_backoff_ms: u64,
+}
+
+impl PluginSupervisionConfig {
+    /// Wait after restart number `attempt` before another may be tried
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 1u64.checked_shl(attempt.saturating_sub(1)).unwrap_or(u64::MAX);
+        let delay = self.restart_backoff_ms.saturating_mul(factor);
+        std::time::Duration::from_millis(delay.min(self.max_restart_backoff_ms))
+    }
+}
+
+impl Default for PluginSupervisionConfig {
+    fn default() -> Self {
+        Self {
+            health_check_interval_secs: 30,
+            failure_threshold: 3,
+            max_restarts: 5,
+            restart_backoff_ms: 1_000,
+            max_restart_backoff_ms: 60_000,
         }
     }
 }
 
 /// Plugin security policy
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PluginSecurityPolicy {
     /// Require signed plugins
     pub require_signed: bool,
     /// Trusted plugin publishers
     pub trusted_publishers: Vec<String>,
+    /// Base64 ed25519 public keys of trusted publishers, keyed by publisher name
+    pub trusted_keys: std::collections::HashMap<String, String>,
     /// Allow unsigned plugins from local development
     pub allow_local_unsigned: bool,
     /// Plugin isolation level
     pub isolation_level: PluginIsolationLevel,
+    /// Workspace that, with the home directory, counts as local for
+    /// `allow_local_unsigned`
+    ///
+    /// Set by the loader to the directory of the config file, or to
+    /// `NEXUS_HOME` when there is none; never read from the file.
+    #[serde(skip)]
+    pub workspace_dir: Option<PathBuf>,
 }
 
 impl Default for PluginSecurityPolicy {
@@ -132,8 +270,10 @@ impl Default for PluginSecurityPolicy {
         Self {
             require_signed: true,
             trusted_publishers: vec!["nexus-official".to_string()],
+            trusted_keys: std::collections::HashMap::new(),
             allow_local_unsigned: false, // Secure by default
             isolation_level: PluginIsolationLevel::Strict,
+            workspace_dir: None,
         }
     }
 }
@@ -153,57 +293,96 @@ pub enum PluginIsolationLevel {
 
 /// Logging configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LoggingConfig {
     /// Log level
     pub level: String,
+    /// Per-module level overrides, e.g. `nexus_core::plugin=debug`
+    pub directives: Vec<String>,
     /// Log format (json, pretty, compact)
     pub format: String,
     /// Log to file
     pub log_to_file: bool,
     /// Log file path
     pub log_file_path: Option<PathBuf>,
+    /// When to start a new log file
+    pub rotation: LogRotation,
+    /// File size that triggers a rotation with `rotation = "size"`
+    pub max_file_size: u64,
+    /// Rotated log files to keep
+    pub max_files: usize,
     /// Enable structured logging
     pub structured: bool,
     /// Enable security event logging
     pub security_events: bool,
+    /// File receiving a copy of events tagged `security = true`
+    pub audit_log_path: Option<PathBuf>,
+}
+
+/// Log file rotation policies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// Keep appending to one file
+    Never,
+    /// Start a new file every day, suffixed with the date
+    Daily,
+    /// Start a new file once the current one reaches `max_file_size`
+    Size,
 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             level: "info".to_string(),
+            directives: Vec::new(),
             format: "pretty".to_string(),
             log_to_file: false,
             log_file_path: None,
+            rotation: LogRotation::Daily,
+            max_file_size: 10 * 1024 * 1024,
+            max_files: 7,
             structured: true,
             security_events: true,
+            audit_log_path: None,
         }
     }
 }
 
+/// RPC endpoints configured out of the box, by network
+#[cfg(feature = "web3")]
+const DEFAULT_RPC_ENDPOINTS: [(&str, &str); 3] = [
+    ("ethereum", "https://eth.llamarpc.com"),
+    ("polygon", "https://polygon.llamarpc.com"),
+    ("base", "https://base.llamarpc.com"),
+];
+
 /// Web3 configuration
 #[cfg(feature = "web3")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Web3Config {
     /// Default network to connect to
     pub default_network: String,
-    /// RPC endpoints
-    pub rpc_endpoints: std::collections::HashMap<String, String>,
+    /// RPC endpoints by network, a URL or a list of URLs in order of preference
+    pub rpc_endpoints: std::collections::HashMap<String, RpcEndpoints>,
     /// Enable transaction simulation
     pub enable_simulation: bool,
     /// Gas limit multiplier for safety
     pub gas_limit_multiplier: f64,
     /// Private key storage method
     pub key_storage: KeyStorageConfig,
+    /// Chains besides the built-in ones, or replacing them
+    pub chains: Vec<ChainInfo>,
 }
 
 #[cfg(feature = "web3")]
 impl Default for Web3Config {
     fn default() -> Self {
-        let mut rpc_endpoints = std::collections::HashMap::new();
-        rpc_endpoints.insert("ethereum".to_string(), "https://eth.llamarpc.com".to_string());
-        rpc_endpoints.insert("polygon".to_string(), "https://polygon.llamarpc.com".to_string());
-        rpc_endpoints.insert("base".to_string(), "https://base.llamarpc.com".to_string());
+        let rpc_endpoints = DEFAULT_RPC_ENDPOINTS
+            .into_iter()
+        .map(|(network, url)| (network.to_string(), RpcEndpoints::One(url.to_string())))
+        .collect();
         
         Self {
             default_network: "ethereum".to_string(),
@@ -211,13 +390,88 @@ impl Default for Web3Config {
             enable_simulation: true,
             gas_limit_multiplier: 1.2,
             key_storage: KeyStorageConfig::default(),
+            chains: Vec::new(),
         }
     }
 }
 
+#[cfg(feature = "web3")]
+impl Web3Config {
+    /// Built-in chains plus the configured ones
+    pub fn chain_registry(&self) -> ChainRegistry {
+        ChainRegistry::with_chains(self.chains.iter().cloned())
+    }
+
+    /// Preferred RPC endpoint configured for `chain`, under its name, an alias or its id
+    pub fn rpc_endpoint(&self, registry: &ChainRegistry, chain: &ChainInfo) -> Option<&str> {
+        self.rpc_urls(registry, chain).first().map(String::as_str)
+    }
+
+    /// Every RPC endpoint configured for `chain`, the preferred one first
+    ///
+    /// Endpoints can be listed under any name of the chain, so a user's
+    /// `eth` sits next to the built-in `ethereum` entry after merging; a
+    /// built-in entry left unchanged gives way to the user's.
+    pub fn rpc_urls(&self, registry: &ChainRegistry, chain: &ChainInfo) -> &[String] {
+        self.rpc_entries(registry, chain)
+            .into_iter()
+            .min_by_key(|(name, endpoints)| (endpoints.is_builtin(name), name.as_str()))
+            .map_or(&[], |(_, endpoints)| endpoints.urls())
+    }
+
+    /// The `rpc_endpoints` entries naming `chain`
+    fn rpc_entries(
+        &self,
+        registry: &ChainRegistry,
+        chain: &ChainInfo,
+    ) -> Vec<(&String, &RpcEndpoints)> {
+        self.rpc_endpoints
+            .iter()
+            .filter(|(name, _)| {
+                registry
+                    .resolve(name)
+                    .is_ok_and(|found| found.id == chain.id)
+            })
+            .collect()
+    }
+}
+
+/// RPC endpoints of one network
+///
+/// Written as a single URL, or as a list whose first URL is preferred and
+/// the rest are fallbacks.
+#[cfg(feature = "web3")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RpcEndpoints {
+    /// One endpoint
+    One(String),
+    /// Endpoints in order of preference
+    Many(Vec<String>),
+}
+
+#[cfg(feature = "web3")]
+impl RpcEndpoints {
+    /// The URLs in order of preference
+    pub fn urls(&self) -> &[String] {
+        match self {
+            Self::One(url) => std::slice::from_ref(url),
+            Self::Many(urls) => urls,
+        }
+    }
+
+    /// Whether these are the built-in endpoints of `network`, unchanged
+    fn is_builtin(&self, network: &str) -> bool {
+        DEFAULT_RPC_ENDPOINTS
+            .iter()
+            .any(|&(name, url)| name == network && self.urls() == [url])
+    }
+}
+
 /// Key storage configuration
 #[cfg(feature = "web3")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct KeyStorageConfig {
     /// Storage type
     pub storage_type: KeyStorageType,
@@ -252,80 +506,769 @@ pub enum KeyStorageType {
     Environment,
 }
 
+/// Maximum nesting depth for `include` directives
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Prefix of environment variables that override configuration keys
+///
+/// Nested keys are separated by double underscores, so
+/// `NEXUS__AGENT__MAX_CONCURRENT_AGENTS=4` sets `agent.max_concurrent_agents`.
+pub const ENV_PREFIX: &str = "NEXUS__";
+
+/// Environment variable naming the profile to apply
+pub const PROFILE_ENV: &str = "NEXUS_PROFILE";
+
+/// Table of named profiles in a configuration file
+///
+/// Each `[profile.<name>]` section is a partial configuration merged onto
+/// the rest of the file when that profile is selected.
+const PROFILE_KEY: &str = "profile";
+
+/// Layer that supplied a configuration value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// A configuration file, or a file it includes
+    File(PathBuf),
+    /// A `[profile.<name>]` section of the configuration file
+    Profile(String),
+    /// An environment variable
+    Env(String),
+    /// A programmatic override, e.g. `--set` on the command line
+    Override,
+}
+
+impl std::fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "file {}", path.display()),
+            Self::Profile(name) => write!(f, "profile {}", name),
+            Self::Env(name) => write!(f, "env {}", name),
+            Self::Override => write!(f, "override"),
+        }
+    }
+}
+
+/// Configuration merged from defaults, a file, the environment and overrides
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    /// Merged configuration
+    pub config: Config,
+    /// Layer that last set each key, by dotted path; keys not listed are defaults
+    pub origins: BTreeMap<String, ConfigLayer>,
+}
+
+impl LayeredConfig {
+    /// Render the origins as aligned `key  layer` lines
+    pub fn render_origins(&self) -> String {
+        use std::fmt::Write as _;
+
+        let width = self.origins.keys().map(String::len).max().unwrap_or(0);
+        let mut output = String::from("# Keys not listed use their defaults\n");
+        for (key, layer) in &self.origins {
+            let _ = writeln!(output, "{:width$}  {}", key, layer, width = width);
+        }
+        output
+    }
+}
+
+/// Configuration document with all `include` directives resolved
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedConfig {
+    /// Merged TOML document
+    pub document: toml::Table,
+    /// Files that contributed to each top-level key, in merge order
+    pub provenance: BTreeMap<String, Vec<PathBuf>>,
+    /// File that last set each dotted leaf key
+    pub leaf_provenance: BTreeMap<String, PathBuf>,
+}
+
+impl ResolvedConfig {
+    /// Deserialize the merged document into a configuration
+    pub fn to_config(&self) -> Result<Config> {
+        toml::Value::Table(self.document.clone())
+            .try_into()
+            .context("Failed to deserialize merged configuration")
+    }
+
+    /// Render the merged document as TOML, annotating each section with its source files
+    pub fn render_with_provenance(&self) -> Result<String> {
+        let mut output = String::new();
+
+        // Top-level scalars must precede any table header
+        let (tables, scalars): (Vec<_>, Vec<_>) = self
+            .document
+            .iter()
+            .partition(|(_, value)| value.is_table());
+
+        for (key, value) in scalars.into_iter().chain(tables) {
+            output.push_str("# from: ");
+            output.push_str(&self.sources_for(key));
+            output.push('\n');
+
+            let mut section = toml::Table::new();
+            section.insert(key.clone(), value.clone());
+            let rendered = toml::to_string_pretty(&section)
+                .with_context(|| format!("Failed to render configuration section '{}'", key))?;
+            output.push_str(&rendered);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn sources_for(&self, key: &str) -> String {
+        self.provenance.get(key).map_or_else(
+            || "<unknown>".to_string(),
+            |paths| {
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+        )
+    }
+
+    /// Merge a document into this one, recording where its keys came from
+    fn merge(&mut self, table: toml::Table, origin: &Path) {
+        for key in table.keys() {
+            let sources = self.provenance.entry(key.clone()).or_default();
+            if !sources.iter().any(|source| source == origin) {
+                sources.push(origin.to_path_buf());
+            }
+        }
+        let mut leaves = Vec::new();
+        leaf_keys(&table, "", &mut leaves);
+        for key in leaves {
+            self.leaf_provenance.insert(key, origin.to_path_buf());
+        }
+
+        merge_tables(&mut self.document, table);
+    }
+}
+
+/// Deep-merge `overlay` into `base`: tables merge recursively, other values are replaced
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Dotted paths of every non-table value in `table`
+fn leaf_keys(table: &toml::Table, prefix: &str, keys: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(nested) => leaf_keys(nested, &path, keys),
+            _ => keys.push(path),
+        }
+    }
+}
+
+/// Configuration key named by an environment variable, if it has the prefix
+fn env_key(name: &str) -> Option<String> {
+    let segments: Vec<String> = name
+        .strip_prefix(ENV_PREFIX)?
+        .split("__")
+        .map(str::to_lowercase)
+        .collect();
+    if segments.iter().any(String::is_empty) {
+        return None;
+    }
+    Some(segments.join("."))
+}
+
+/// Parse an override as a TOML value, falling back to a plain string
+fn parse_override(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Set `key` to `raw` in `document`, checking that the result still deserializes
+///
+/// A raw value replacing a string stays a string; anything else is parsed as
+/// a TOML literal so numbers, booleans and arrays keep their type.
+fn apply_override(
+    document: &mut toml::Table,
+    key: &str,
+    raw: &str,
+    layer: &ConfigLayer,
+) -> Result<()> {
+    let invalid = |reason: String| -> anyhow::Error {
+        NexusError::Config(format!(
+            "invalid value '{}' for '{}' from {}: {}",
+            raw, key, layer, reason
+        ))
+        .into()
+    };
+
+    let segments: Vec<&str> = key.split('.').collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return Err(invalid("empty key".to_string()));
+    };
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(invalid("empty key segment".to_string()));
+    }
+
+    let mut table = &mut *document;
+    for segment in parents {
+        table = match table
+            .entry(segment.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        {
+            toml::Value::Table(nested) => nested,
+            _ => return Err(invalid(format!("'{}' is not a section", segment))),
+        };
+    }
+    let value = match table.get(*last) {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        Some(toml::Value::Table(_)) => {
+            return Err(invalid("it is a section; set one of its keys instead".to_string()))
+        }
+        _ => parse_override(raw),
+    };
+    table.insert(last.to_string(), value);
+
+    // Check each key on its own so the error names the one that is wrong
+    let config: Config = toml::Value::Table(document.clone())
+        .try_into()
+        .map_err(|e: toml::de::Error| invalid(e.message().to_string()))?;
+
+    if !is_known_key(&config, key)? {
+        return Err(invalid("unknown configuration key".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Whether `config` has a value at the dotted `key`
+///
+/// Unknown keys are dropped by deserialization, so this looks for the key
+/// again in the deserialized configuration.
+fn is_known_key(config: &Config, key: &str) -> Result<bool> {
+    let mut known =
+        toml::Table::try_from(config).context("Failed to serialize configuration")?;
+    let segments: Vec<&str> = key.split('.').collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return Ok(false);
+    };
+    for segment in parents {
+        known = match known.remove(*segment) {
+            Some(toml::Value::Table(nested)) => nested,
+            _ => return Ok(false),
+        };
+    }
+    Ok(known.contains_key(*last))
+}
+
+/// Remove the profiles from `document` and merge the one named `profile` onto it
+///
+/// Returns the keys the profile set, so they can be checked once the merged
+/// document is deserialized.
+fn apply_profile(document: &mut toml::Table, profile: Option<&str>) -> Result<Vec<String>> {
+    let mut profiles = match document.remove(PROFILE_KEY) {
+        None => toml::Table::new(),
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(other) => {
+            return Err(NexusError::Config(format!(
+                "'{}' must be a table of profiles, found {}",
+                PROFILE_KEY,
+                other.type_str()
+            ))
+            .into())
+        }
+    };
+    let Some(name) = profile else {
+        return Ok(Vec::new());
+    };
+
+    match profiles.remove(name) {
+        Some(toml::Value::Table(overlay)) => {
+            let mut keys = Vec::new();
+            leaf_keys(&overlay, "", &mut keys);
+            merge_tables(document, overlay);
+            Ok(keys)
+        }
+        Some(other) => Err(NexusError::Config(format!(
+            "profile '{}' must be a table, found {}",
+            name,
+            other.type_str()
+        ))
+        .into()),
+        None => Err(unknown_profile(name, profiles.keys())),
+    }
+}
+
+/// Error for a profile that isn't defined, listing the ones that are
+fn unknown_profile<'a>(
+    name: &str,
+    available: impl IntoIterator<Item = &'a String>,
+) -> anyhow::Error {
+    let available: Vec<&str> = available.into_iter().map(String::as_str).collect();
+    let message = if available.is_empty() {
+        format!("unknown profile '{}': no profiles are defined", name)
+    } else {
+        format!(
+            "unknown profile '{}', available profiles: {}",
+            name,
+            available.join(", ")
+        )
+    };
+    NexusError::Config(message).into()
+}
+
+/// Fail if a key set by `profile` is not a configuration key
+fn check_profile_keys(config: &Config, profile: &str, keys: &[String]) -> Result<()> {
+    for key in keys {
+        if !is_known_key(config, key)? {
+            return Err(NexusError::Config(format!(
+                "invalid key '{}' in profile '{}': unknown configuration key",
+                key, profile
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Remove and return the `include` directive from a document
+fn take_includes(table: &mut toml::Table, origin: &Path) -> Result<Vec<String>> {
+    match table.remove("include") {
+        None => Ok(Vec::new()),
+        Some(toml::Value::String(pattern)) => Ok(vec![pattern]),
+        Some(toml::Value::Array(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                toml::Value::String(pattern) => Ok(pattern),
+                other => Err(anyhow::anyhow!(
+                    "Invalid include entry {} in {:?}: expected a string",
+                    other,
+                    origin
+                )),
+            })
+            .collect(),
+        Some(other) => Err(anyhow::anyhow!(
+            "Invalid include directive in {:?}: expected a string or array of strings, found {}",
+            origin,
+            other.type_str()
+        )),
+    }
+}
+
+/// Expand an include pattern relative to the including file's directory
+///
+/// Glob patterns are expanded in sorted order so merge order is deterministic;
+/// a glob matching nothing is not an error, but a missing literal path is.
+fn expand_include(base_dir: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let target = base_dir.join(pattern);
+
+    if !pattern.contains(['*', '?', '[']) {
+        return Ok(vec![target]);
+    }
+
+    let target = target
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("Include pattern is not valid UTF-8: {:?}", target))?;
+    let mut matches = glob::glob(target)
+        .with_context(|| format!("Invalid include pattern: {}", pattern))?
+        .filter_map(std::result::Result::ok)
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    matches.sort();
+
+    if matches.is_empty() {
+        debug!("Include pattern '{}' matched no files", pattern);
+    }
+
+    Ok(matches)
+}
+
+/// Default configuration search paths, most specific first
+///
+/// The workspace entries are relative to `workspace`. `nexus/nexus.toml` is
+/// where `nexus init` writes the configuration.
+fn default_search_paths(workspace: &Path) -> Vec<PathBuf> {
+    vec![
+        workspace.join("nexus.toml"),
+        workspace.join("nexus").join("nexus.toml"),
+        workspace.join("config").join("nexus.toml"),
+        PathBuf::from("~/.config/nexus/config.toml"),
+        PathBuf::from("/etc/nexus/config.toml"),
+    ]
+}
+
 /// Configuration loader
 pub struct ConfigLoader {
     search_paths: Vec<PathBuf>,
+    home: Option<PathBuf>,
+    profile: Option<String>,
 }
 
 impl ConfigLoader {
     /// Create a new configuration loader
+    ///
+    /// The workspace search paths are relative to `NEXUS_HOME` when it is
+    /// set, and to the current directory otherwise.
     pub fn new() -> Self {
+        Self::in_dir(std::env::var_os(NEXUS_HOME_ENV).map_or_else(|| PathBuf::from("."), PathBuf::from))
+    }
+
+    /// Create a loader searching `dir` for workspace configuration, as one
+    /// started there without `NEXUS_HOME` would
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
         Self {
-            search_paths: vec![
-                PathBuf::from("./nexus.toml"),
-                PathBuf::from("./config/nexus.toml"),
-                PathBuf::from("~/.config/nexus/config.toml"),
-                PathBuf::from("/etc/nexus/config.toml"),
-            ],
+            search_paths: default_search_paths(&dir.into()),
+            home: dirs::home_dir(),
+            profile: None,
         }
     }
+
+    /// Apply the named profile, taking precedence over `NEXUS_PROFILE`
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Profile to apply: the one set with [`with_profile`](Self::with_profile),
+    /// or else the one named in `env_profile`
+    fn selected_profile(&self, env_profile: Option<String>) -> Option<String> {
+        self.profile
+            .clone()
+            .or(env_profile)
+            .filter(|profile| !profile.is_empty())
+    }
     
     /// Add a search path for configuration files
     pub fn add_search_path(&mut self, path: PathBuf) {
         self.search_paths.push(path);
     }
+
+    /// Add a search path that takes precedence over all existing ones
+    pub fn prepend_search_path(&mut self, path: PathBuf) {
+        self.search_paths.insert(0, path);
+    }
     
     /// Load configuration from file or use defaults
+    ///
+    /// The profile named by [`with_profile`](Self::with_profile) or
+    /// `NEXUS_PROFILE` is applied, if any.
     pub fn load(&self) -> Result<Config> {
+        let profile = self.selected_profile(std::env::var(PROFILE_ENV).ok());
+        self.load_selected(profile.as_deref())
+    }
+
+    /// Load configuration with the `[profile.<profile>]` section merged onto it
+    ///
+    /// Merging is deep, so a profile only needs the keys it changes. An
+    /// unknown profile fails with [`NexusError::Config`] listing the
+    /// available ones.
+    pub fn load_with_profile(&self, profile: &str) -> Result<Config> {
+        self.load_selected(Some(profile))
+    }
+
+    fn load_selected(&self, profile: Option<&str>) -> Result<Config> {
         // Try to find and load configuration file
-        for path in &self.search_paths {
-            if path.exists() {
-                info!("Loading configuration from: {:?}", path);
-                return self.load_from_file(path);
-            }
+        if let Some(path) = self.find_config_file() {
+            info!("Loading configuration from: {:?}", path);
+            return self.load_file(&path, profile);
+        }
+
+        if let Some(profile) = profile {
+            return Err(unknown_profile(profile, []));
         }
-        
         warn!("No configuration file found, using defaults");
-        Ok(Config::default())
+        let mut config = Config::default();
+        self.validate_config(&mut config, None)?;
+        Ok(config)
     }
     
+    /// Find the first configuration file present on the search path
+    ///
+    /// A leading `~` in a search path expands to the home directory.
+    pub fn find_config_file(&self) -> Option<PathBuf> {
+        let resolver = self.path_resolver(None);
+        self.search_paths
+            .iter()
+            .map(|path| resolver.expand_tilde(path))
+            .find(|path| path.exists())
+    }
+
     /// Load configuration from a specific file
+    ///
+    /// Relative paths in the configuration, including those from included
+    /// files, are resolved against the directory of `path`.
     pub fn load_from_file(&self, path: &PathBuf) -> Result<Config> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {:?}", path))?;
-        
-        let config: Config = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {:?}", path))?;
-        
-        self.validate_config(&config)?;
-        
+        let profile = self.selected_profile(std::env::var(PROFILE_ENV).ok());
+        self.load_file(path, profile.as_deref())
+    }
+
+    fn load_file(&self, path: &Path, profile: Option<&str>) -> Result<Config> {
+        let resolved = self.resolve_includes(path)?;
+        self.finish(
+            resolved.document,
+            profile,
+            Some(path),
+            || format!("Failed to parse config file: {:?}", path),
+        )
+    }
+
+    /// Apply `profile` to a merged document, then deserialize and validate it
+    fn finish(
+        &self,
+        mut document: toml::Table,
+        profile: Option<&str>,
+        config_file: Option<&Path>,
+        context: impl Fn() -> String,
+    ) -> Result<Config> {
+        let profile_keys = apply_profile(&mut document, profile)?;
+
+        let mut config: Config = toml::Value::Table(document)
+            .try_into()
+            .with_context(|| match profile {
+                Some(profile) => format!("{} with profile '{}'", context(), profile),
+                None => context(),
+            })?;
+        if let Some(profile) = profile {
+            check_profile_keys(&config, profile, &profile_keys)?;
+        }
+
+        // Validation sees the merged result, not the profile on its own
+        self.validate_config(&mut config, config_file)?;
+
         Ok(config)
     }
-    
+
+    /// Resolver for paths in the config file at `config_file`, or in no file
+    fn path_resolver(&self, config_file: Option<&Path>) -> PathResolver {
+        let base_dir = config_file
+            .and_then(|file| file.canonicalize().ok())
+            .and_then(|file| file.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| PathBuf::from("."));
+        PathResolver::new(base_dir).with_home(self.home.clone())
+    }
+
     /// Load configuration from string
+    ///
+    /// Relative `include` paths are resolved against the current directory.
     pub fn load_from_string(&self, content: &str) -> Result<Config> {
-        let config: Config = toml::from_str(content)
+        let table: toml::Table = toml::from_str(content)
             .context("Failed to parse configuration string")?;
-        
-        self.validate_config(&config)?;
-        
-        Ok(config)
+
+        let mut resolved = ResolvedConfig::default();
+        self.merge_document(
+            table,
+            Path::new("<string>"),
+            Path::new("."),
+            &mut Vec::new(),
+            &mut resolved,
+        )?;
+
+        let profile = self.selected_profile(std::env::var(PROFILE_ENV).ok());
+        self.finish(
+            resolved.document,
+            profile.as_deref(),
+            None,
+            || "Failed to parse configuration string".to_string(),
+        )
     }
-    
-    /// Validate configuration
-    fn validate_config(&self, config: &Config) -> Result<()> {
-        // Validate security configuration
-        if config.security.rate_limit.max_requests == 0 {
-            return Err(anyhow::anyhow!("Rate limit max_requests must be greater than 0"));
+
+    /// Load configuration in layers: defaults, the first configuration file on
+    /// the search path, the selected profile, `NEXUS__*` environment
+    /// variables, then `overrides`
+    ///
+    /// Each later layer wins. `overrides` are `(key, value)` pairs with dotted
+    /// keys, as passed with `--set key=value`. A value that doesn't fit its
+    /// key fails with [`NexusError::Config`] naming the key and the value.
+    pub fn load_layered(&self, overrides: &[(String, String)]) -> Result<LayeredConfig> {
+        self.load_layered_with_env(std::env::vars(), overrides)
+    }
+
+    /// [`load_layered`](Self::load_layered) with an explicit environment
+    pub fn load_layered_with_env(
+        &self,
+        env: impl IntoIterator<Item = (String, String)>,
+        overrides: &[(String, String)],
+    ) -> Result<LayeredConfig> {
+        let env: Vec<(String, String)> = env.into_iter().collect();
+        let profile = self.selected_profile(
+            env.iter()
+                .find(|(name, _)| name == PROFILE_ENV)
+                .map(|(_, value)| value.clone()),
+        );
+
+        let mut document = toml::Table::try_from(Config::default())
+            .context("Failed to serialize default configuration")?;
+        let mut origins = BTreeMap::new();
+
+        let config_file = self.find_config_file();
+        if let Some(path) = &config_file {
+            info!("Loading configuration from: {:?}", path);
+            let mut resolved = self.resolve_includes(path)?;
+            let mut profile_document = toml::Table::new();
+            if let Some(profiles) = resolved.document.remove(PROFILE_KEY) {
+                profile_document.insert(PROFILE_KEY.to_string(), profiles);
+            }
+
+            let mut keys = Vec::new();
+            leaf_keys(&resolved.document, "", &mut keys);
+            for key in keys {
+                let file = resolved
+                    .leaf_provenance
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or_else(|| path.clone());
+                origins.insert(key, ConfigLayer::File(file));
+            }
+
+            merge_tables(&mut document, resolved.document);
+
+            let profile_keys = apply_profile(&mut profile_document, profile.as_deref())?;
+            merge_tables(&mut document, profile_document);
+            if let Some(profile) = &profile {
+                // Check the profile now, so its mistakes aren't blamed on a later layer
+                let config: Config = toml::Value::Table(document.clone())
+                    .try_into()
+                    .with_context(|| format!("Failed to apply profile '{}'", profile))?;
+                check_profile_keys(&config, profile, &profile_keys)?;
+                for key in profile_keys {
+                    origins.insert(key, ConfigLayer::Profile(profile.clone()));
+                }
+            }
+        } else if let Some(profile) = &profile {
+            return Err(unknown_profile(profile, []));
         }
-        
-        // Validate agent configuration
-        if config.agent.max_concurrent_agents == 0 {
-            return Err(anyhow::anyhow!("max_concurrent_agents must be greater than 0"));
+
+        // Sorted so the outcome doesn't depend on the environment's order
+        let mut env: Vec<(String, String, String)> = env
+            .into_iter()
+            .filter_map(|(name, value)| Some((env_key(&name)?, value, name)))
+            .collect();
+        env.sort();
+
+        let layers = env
+            .into_iter()
+            .map(|(key, value, name)| (key, value, ConfigLayer::Env(name)))
+            .chain(
+                overrides
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone(), ConfigLayer::Override)),
+            );
+        for (key, value, layer) in layers {
+            apply_override(&mut document, &key, &value, &layer)?;
+            origins.insert(key, layer);
+        }
+
+        let mut config: Config = toml::Value::Table(document)
+            .try_into()
+            .context("Failed to deserialize layered configuration")?;
+        self.validate_config(&mut config, config_file.as_deref())?;
+
+        Ok(LayeredConfig { config, origins })
+    }
+
+    /// Read a configuration file and merge in everything it includes
+    ///
+    /// The file's own keys are applied first, then each include in the order
+    /// listed, so later includes override earlier ones and the including file.
+    pub fn resolve_includes(&self, path: &Path) -> Result<ResolvedConfig> {
+        let mut resolved = ResolvedConfig::default();
+        self.resolve_file(path, &mut Vec::new(), &mut resolved)?;
+        Ok(resolved)
+    }
+
+    fn resolve_file(
+        &self,
+        path: &Path,
+        chain: &mut Vec<PathBuf>,
+        resolved: &mut ResolvedConfig,
+    ) -> Result<()> {
+        let canonical = path
+            .canonicalize()
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+
+        if let Some(start) = chain.iter().position(|seen| *seen == canonical) {
+            let cycle = chain[start..]
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(anyhow::anyhow!("Config include cycle detected: {}", cycle));
+        }
+
+        let content = std::fs::read_to_string(&canonical)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {:?}", path))?;
+
+        let base_dir = canonical
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+
+        self.merge_document(table, &canonical, &base_dir, chain, resolved)
+    }
+
+    fn merge_document(
+        &self,
+        mut table: toml::Table,
+        origin: &Path,
+        base_dir: &Path,
+        chain: &mut Vec<PathBuf>,
+        resolved: &mut ResolvedConfig,
+    ) -> Result<()> {
+        let includes = take_includes(&mut table, origin)?;
+        resolved.merge(table, origin);
+
+        if includes.is_empty() {
+            return Ok(());
         }
+
+        if chain.len() >= MAX_INCLUDE_DEPTH {
+            return Err(anyhow::anyhow!(
+                "Config includes nested deeper than {} levels at {:?}",
+                MAX_INCLUDE_DEPTH,
+                origin
+            ));
+        }
+
+        chain.push(origin.to_path_buf());
+        for pattern in &includes {
+            for included in expand_include(base_dir, pattern)? {
+                debug!("Including config file {:?} from {:?}", included, origin);
+                self.resolve_file(&included, chain, resolved)?;
+            }
+        }
+        chain.pop();
+
+        Ok(())
+    }
+    
+    /// Validate configuration, making its paths absolute relative to `config_file`
+    ///
+    /// Also records the workspace the plugin security policy treats as local.
+    fn validate_config(&self, config: &mut Config, config_file: Option<&Path>) -> Result<()> {
+        let resolver = self.path_resolver(config_file);
+        resolve_paths(config, &resolver);
+        config.plugin.security_policy.workspace_dir = match config_file {
+            Some(_) => Some(resolver.base_dir().to_path_buf()),
+            None => std::env::var_os(NEXUS_HOME_ENV).map(|home| resolver.resolve(Path::new(&home))),
+        };
         
-        if config.agent.default_timeout_secs == 0 {
-            return Err(anyhow::anyhow!("default_timeout_secs must be greater than 0"));
+        if let Some(problem) = config_problems(config).into_iter().next() {
+            return Err(anyhow::anyhow!(problem.message));
         }
         
         // Validate plugin configuration
@@ -333,19 +1276,111 @@ impl ConfigLoader {
             warn!("No plugin directories configured");
         }
         
-        // Validate logging configuration
-        let valid_levels = ["trace", "debug", "info", "warn", "error"];
-        if !valid_levels.contains(&config.logging.level.as_str()) {
-            return Err(anyhow::anyhow!(
-                "Invalid log level '{}', must be one of: {}", 
-                config.logging.level,
-                valid_levels.join(", ")
-            ));
-        }
-        
         Ok(())
     }
     
+    /// Check the configuration file at `path`, collecting every problem
+    ///
+    /// Unlike loading, this doesn't stop at the first invalid value: each
+    /// key is checked on its own, and unknown keys, which loading ignores,
+    /// are reported as warnings with the closest known key. A file that
+    /// can't be read or parsed, or an unknown profile, is still an error.
+    pub fn validate_file(&self, path: &Path) -> Result<ValidationReport> {
+        let mut document = self.resolve_includes(path)?.document;
+        let profile = self.selected_profile(std::env::var(PROFILE_ENV).ok());
+        let profile_keys = apply_profile(&mut document, profile.as_deref())?;
+
+        let schema = Config::schema();
+        let defaults = toml::Table::try_from(Config::default())
+            .context("Failed to serialize configuration")?;
+        let mut report = ValidationReport::default();
+
+        let mut keys = Vec::new();
+        leaf_keys(&document, "", &mut keys);
+        for key in keys {
+            if !is_schema_key(&schema, &key) {
+                // Loading ignores unknown keys in the file but rejects them in a profile
+                let in_profile = profile_keys.contains(&key);
+                let known = schema.iter().map(|doc| doc.key.as_str());
+                let problem = ConfigProblem {
+                    suggestions: crate::suggest::suggest(&key, known),
+                    message: match profile.as_deref() {
+                        Some(name) if in_profile => {
+                            format!("unknown configuration key in profile '{}'", name)
+                        },
+                        _ => "unknown configuration key".to_string(),
+                    },
+                    key,
+                };
+                if in_profile {
+                    report.errors.push(problem);
+                } else {
+                    report.warnings.push(problem);
+                }
+                continue;
+            }
+            if let Some(problem) = check_key(&defaults, &document, &key, &schema) {
+                report.errors.push(problem);
+            }
+        }
+
+        // Checks of the whole configuration only make sense once every value fits
+        if report.errors.is_empty() {
+            match toml::Value::Table(document).try_into::<Config>() {
+                Ok(config) => report.errors.extend(config_problems(&config)),
+                Err(e) => report.errors.push(ConfigProblem {
+                    key: String::new(),
+                    message: e.message().trim().to_string(),
+                    suggestions: Vec::new(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+    
+    /// Add `name` to or remove it from `plugin.disabled` in the file at `path`
+    ///
+    /// The rest of the file, comments included, is left as it is. Returns
+    /// whether the file changed.
+    pub fn set_plugin_disabled(&self, path: &Path, name: &str, disabled: bool) -> Result<bool> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+        let mut document: toml_edit::DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse config file: {:?}", path))?;
+
+        let plugin = document
+            .entry("plugin")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| NexusError::Config("'plugin' is not a table".to_string()))?;
+        let list = plugin
+            .entry("disabled")
+            .or_insert(toml_edit::value(toml_edit::Array::new()))
+            .as_array_mut()
+            .ok_or_else(|| NexusError::Config("'plugin.disabled' is not an array".to_string()))?;
+
+        let position = list.iter().position(|entry| entry.as_str() == Some(name));
+        match (position, disabled) {
+            (None, true) => list.push(name),
+            (Some(index), false) => {
+                list.remove(index);
+            }
+            _ => return Ok(false),
+        }
+
+        std::fs::write(path, document.to_string())
+            .with_context(|| format!("Failed to write config file: {:?}", path))?;
+        info!(
+            "Plugin '{}' {} in {:?}",
+            name,
+            if disabled { "disabled" } else { "enabled" },
+            path
+        );
+        Ok(true)
+    }
+
     /// Save configuration to file
     pub fn save_to_file(&self, config: &Config, path: &PathBuf) -> Result<()> {
         let content = toml::to_string_pretty(config)
@@ -359,6 +1394,210 @@ impl ConfigLoader {
     }
 }
 
+/// A problem with one configuration key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// Dotted key the problem is about; empty when it can't be pinned to one
+    pub key: String,
+    /// What is wrong
+    pub message: String,
+    /// Close matches for a mistyped key or value
+    pub suggestions: Vec<String>,
+}
+
+impl std::fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.key.is_empty() {
+            write!(f, "{}: ", self.key)?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(hint) = crate::suggest::did_you_mean(&self.suggestions) {
+            write!(f, ". {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything [`ConfigLoader::validate_file`] found
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// Problems that make loading the file fail
+    pub errors: Vec<ConfigProblem>,
+    /// Problems loading ignores, such as unknown keys
+    pub warnings: Vec<ConfigProblem>,
+}
+
+impl ValidationReport {
+    /// Whether the file would load
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Values the configuration accepts syntactically but can't work with
+///
+/// [`ConfigLoader`] fails with the first of these; `validate_file` reports them all.
+fn config_problems(config: &Config) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let mut problem = |key: &str, message: String, suggestions: Vec<String>| {
+        problems.push(ConfigProblem {
+            key: key.to_string(),
+            message,
+            suggestions,
+        });
+    };
+
+    // Validate security configuration
+    if config.security.rate_limit.max_requests == 0 {
+        problem(
+            "security.rate_limit.max_requests",
+            "Rate limit max_requests must be greater than 0".to_string(),
+            Vec::new(),
+        );
+    }
+
+    // Validate agent configuration
+    if config.agent.max_concurrent_agents == 0 {
+        problem(
+            "agent.max_concurrent_agents",
+            "max_concurrent_agents must be greater than 0".to_string(),
+            Vec::new(),
+        );
+    }
+
+    if config.agent.default_timeout_secs == 0 {
+        problem(
+            "agent.default_timeout_secs",
+            "default_timeout_secs must be greater than 0".to_string(),
+            Vec::new(),
+        );
+    }
+
+    // Validate logging configuration
+    let valid_levels = ["trace", "debug", "info", "warn", "error"];
+    if !valid_levels.contains(&config.logging.level.as_str()) {
+        problem(
+            "logging.level",
+            format!(
+                "Invalid log level '{}', must be one of: {}",
+                config.logging.level,
+                valid_levels.join(", ")
+            ),
+            crate::suggest::suggest(&config.logging.level, valid_levels),
+        );
+    }
+
+    // Validate web3 chain names
+    #[cfg(feature = "web3")]
+    {
+        let registry = config.web3.chain_registry();
+        if let Err(e) = registry.resolve(&config.web3.default_network) {
+            problem("web3.default_network", e.to_string(), e.suggestions);
+        }
+        let mut networks: Vec<_> = config.web3.rpc_endpoints.keys().collect();
+        networks.sort();
+        for network in networks {
+            let key = format!("web3.rpc_endpoints.{}", network);
+            if let Err(e) = registry.resolve(network) {
+                problem(&key, e.to_string(), e.suggestions);
+            } else if config.web3.rpc_endpoints[network].urls().is_empty() {
+                problem(&key, "no RPC endpoint listed".to_string(), Vec::new());
+            }
+        }
+        // One chain listed under two names would leave the endpoint to chance
+        for chain in registry.chains() {
+            let mut names: Vec<_> = config
+                .web3
+                .rpc_entries(&registry, chain)
+                .into_iter()
+                .filter(|(name, endpoints)| !endpoints.is_builtin(name))
+                .map(|(name, _)| name)
+                .collect();
+            names.sort();
+            if let [first, rest @ ..] = names.as_slice() {
+                for name in rest {
+                    problem(
+                        &format!("web3.rpc_endpoints.{}", name),
+                        format!(
+                            "RPC endpoints of chain '{}' are already listed under '{}'",
+                            chain.name, first
+                        ),
+                        Vec::new(),
+                    );
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+/// Check the value of `key` in `document` on its own, against the defaults
+///
+/// A value naming one of a fixed set gets the closest accepted values as
+/// suggestions, so `"strict"` points at `"Strict"`.
+fn check_key(
+    defaults: &toml::Table,
+    document: &toml::Table,
+    key: &str,
+    schema: &[ConfigKeyDoc],
+) -> Option<ConfigProblem> {
+    let segments: Vec<&str> = key.split('.').collect();
+    let (last, parents) = segments.split_last()?;
+
+    let mut source = document;
+    for segment in parents {
+        source = source.get(*segment)?.as_table()?;
+    }
+    let value = source.get(*last)?.clone();
+
+    let mut isolated = defaults.clone();
+    let mut table = &mut isolated;
+    for segment in parents {
+        let entry = table
+            .entry(segment.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut()?;
+    }
+    table.insert(last.to_string(), value.clone());
+
+    let error = toml::Value::Table(isolated).try_into::<Config>().err()?;
+    let suggestions = match (&value, schema.iter().find(|doc| doc.key == key)) {
+        (toml::Value::String(given), Some(doc)) => {
+            crate::suggest::suggest(given, doc.allowed.iter().map(String::as_str))
+        },
+        _ => Vec::new(),
+    };
+    Some(ConfigProblem {
+        key: key.to_string(),
+        message: error.message().trim().to_string(),
+        suggestions,
+    })
+}
+
+/// Replace the paths in `config` with their resolved absolute forms
+///
+/// Plugin directories are expected to exist, but a missing one is only a
+/// warning: it is skipped when plugins are loaded.
+fn resolve_paths(config: &mut Config, resolver: &PathResolver) {
+    config.agent.data_dir = resolver.resolve(&config.agent.data_dir);
+    for path in [&mut config.logging.log_file_path, &mut config.logging.audit_log_path]
+        .into_iter()
+        .flatten()
+    {
+        *path = resolver.resolve(path);
+    }
+    for dir in &mut config.plugin.plugin_dirs {
+        *dir = resolver.resolve_existing(dir).unwrap_or_else(|e| {
+            warn!("Could not resolve plugin directory {:?}: {}", dir, e);
+            resolver.resolve(dir)
+        });
+    }
+}
+
 impl Default for ConfigLoader {
     fn default() -> Self {
         Self::new()
@@ -368,8 +1607,8 @@ impl Default for ConfigLoader {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::NamedTempFile;
-    use std::io::Write;
+    use crate::testing::Fixture;
+    use tempfile::TempDir;
     
     #[test]
     fn test_default_config() {
@@ -414,11 +1653,10 @@ level = "debug"
 format = "json"
         "#;
         
-        let mut temp_file = NamedTempFile::new().unwrap();
-        write!(temp_file, "{}", config_content).unwrap();
+        let fixture = Fixture::new().unwrap();
+        std::fs::write(fixture.config_path(), config_content).unwrap();
         
-        let loader = ConfigLoader::new();
-        let config = loader.load_from_file(&temp_file.path().to_path_buf()).unwrap();
+        let config = fixture.loader().load().unwrap();
         
         assert!(!config.security.encryption_enabled);
         assert_eq!(config.security.min_password_length, 8);
@@ -442,4 +1680,553 @@ level = "invalid"
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid log level"));
     }
+
+    #[test]
+    fn test_include_glob_order() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("conf.d")).unwrap();
+        std::fs::write(
+            dir.path().join("nexus.toml"),
+            "include = [\"conf.d/*.toml\"]\n[agent]\nmax_concurrent_agents = 1\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("conf.d/20-late.toml"),
+            "[agent]\nmax_concurrent_agents = 3\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("conf.d/10-early.toml"),
+            "[agent]\nmax_concurrent_agents = 2\ndefault_timeout_secs = 42\n",
+        )
+        .unwrap();
+
+        let loader = ConfigLoader::new();
+        let config = loader
+            .load_from_file(&dir.path().join("nexus.toml"))
+            .unwrap();
+
+        // Sorted expansion: 10-early then 20-late, later wins
+        assert_eq!(config.agent.max_concurrent_agents, 3);
+        assert_eq!(config.agent.default_timeout_secs, 42);
+    }
+
+    #[test]
+    fn test_nested_includes_resolve_relative_to_including_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("env/prod")).unwrap();
+        std::fs::write(dir.path().join("nexus.toml"), "include = \"env/base.toml\"\n").unwrap();
+        std::fs::write(
+            dir.path().join("env/base.toml"),
+            "include = [\"prod/logging.toml\"]\n[logging]\nlevel = \"warn\"\nformat = \"json\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("env/prod/logging.toml"),
+            "[logging]\nlevel = \"error\"\n",
+        )
+        .unwrap();
+
+        let loader = ConfigLoader::new();
+        let config = loader
+            .load_from_file(&dir.path().join("nexus.toml"))
+            .unwrap();
+
+        assert_eq!(config.logging.level, "error");
+        assert_eq!(config.logging.format, "json");
+    }
+
+    #[test]
+    fn test_include_cycle_detected() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.toml"), "include = \"b.toml\"\n").unwrap();
+        std::fs::write(dir.path().join("b.toml"), "include = \"a.toml\"\n").unwrap();
+
+        let loader = ConfigLoader::new();
+        let err = loader
+            .load_from_file(&dir.path().join("a.toml"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("include cycle detected"));
+        assert!(err.contains("a.toml -> "));
+        assert!(err.contains("b.toml -> "));
+    }
+
+    #[test]
+    fn test_include_depth_capped() {
+        let dir = TempDir::new().unwrap();
+        for i in 0..=MAX_INCLUDE_DEPTH + 1 {
+            std::fs::write(
+                dir.path().join(format!("{}.toml", i)),
+                format!("include = \"{}.toml\"\n", i + 1),
+            )
+            .unwrap();
+        }
+
+        let loader = ConfigLoader::new();
+        let err = loader
+            .load_from_file(&dir.path().join("0.toml"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("nested deeper than"));
+    }
+
+    #[test]
+    fn test_include_provenance_output() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("nexus.toml"),
+            "include = [\"security.toml\"]\n[logging]\nlevel = \"debug\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("security.toml"),
+            "[security]\nencryption_enabled = false\n",
+        )
+        .unwrap();
+
+        let loader = ConfigLoader::new();
+        let resolved = loader
+            .resolve_includes(&dir.path().join("nexus.toml"))
+            .unwrap();
+        let rendered = resolved.render_with_provenance().unwrap();
+
+        let logging = rendered.find("[logging]").unwrap();
+        let security = rendered.find("[security]").unwrap();
+        let logging_origin = rendered[..logging].rfind("# from: ").unwrap();
+        let security_origin = rendered[..security].rfind("# from: ").unwrap();
+
+        assert!(rendered[logging_origin..logging].contains("nexus.toml"));
+        assert!(rendered[security_origin..security].contains("security.toml"));
+        assert!(!rendered.contains("include"));
+    }
+
+    #[test]
+    fn test_origins_name_the_file_of_each_key() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("nexus.toml"),
+            "include = [\"logging.toml\"]\n[logging]\nlevel = \"debug\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("logging.toml"), "[logging]\nformat = \"json\"\n").unwrap();
+
+        let layered = ConfigLoader::in_dir(dir.path())
+            .load_layered_with_env(env(&[]), &[])
+            .unwrap();
+        let file = |name: &str| ConfigLayer::File(dir.path().join(name).canonicalize().unwrap());
+        assert_eq!(layered.origins["logging.level"], file("nexus.toml"));
+        assert_eq!(layered.origins["logging.format"], file("logging.toml"));
+    }
+
+    #[test]
+    fn test_relative_paths_resolve_next_to_config_file() {
+        let dir = TempDir::new().unwrap();
+        let workspace = dir.path().join("project").join("config");
+        std::fs::create_dir_all(workspace.join("plugins")).unwrap();
+        let config_path = workspace.join("nexus.toml");
+        std::fs::write(
+            &config_path,
+            "[agent]\ndata_dir = \"data\"\n\n[plugin]\nplugin_dirs = [\"plugins\", \"../missing\"]\n",
+        )
+        .unwrap();
+
+        let config = ConfigLoader::new().load_from_file(&config_path).unwrap();
+        let canonical = workspace.canonicalize().unwrap();
+        assert_eq!(config.agent.data_dir, canonical.join("data"));
+        assert_eq!(
+            config.plugin.plugin_dirs,
+            vec![
+                canonical.join("plugins"),
+                canonical.parent().unwrap().join("missing")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_path_tilde_and_nexus_home() {
+        let home = TempDir::new().unwrap();
+        std::fs::create_dir_all(home.path().join(".config/nexus")).unwrap();
+        std::fs::write(home.path().join(".config/nexus/config.toml"), "").unwrap();
+
+        let loader = ConfigLoader {
+            search_paths: vec![PathBuf::from("~/.config/nexus/config.toml")],
+            home: Some(home.path().to_path_buf()),
+            profile: None,
+        };
+        assert_eq!(
+            loader.find_config_file(),
+            Some(home.path().join(".config/nexus/config.toml"))
+        );
+
+        let paths = default_search_paths(Path::new("/srv/nexus"));
+        assert_eq!(paths[0], PathBuf::from("/srv/nexus/nexus.toml"));
+        assert_eq!(paths[1], PathBuf::from("/srv/nexus/nexus/nexus.toml"));
+        assert_eq!(paths[2], PathBuf::from("/srv/nexus/config/nexus.toml"));
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_overrides_nested_security_field() {
+        let fixture = Fixture::new().unwrap();
+        let layered = fixture
+            .loader()
+            .load_layered_with_env(
+                env(&[
+                    ("NEXUS__SECURITY__RATE_LIMIT__MAX_REQUESTS", "7"),
+                    ("NEXUS__LOGGING__LEVEL", "debug"),
+                    ("NEXUS_UNRELATED", "ignored"),
+                ]),
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(layered.config.security.rate_limit.max_requests, 7);
+        assert_eq!(layered.config.logging.level, "debug");
+        assert_eq!(
+            layered.origins["security.rate_limit.max_requests"],
+            ConfigLayer::Env("NEXUS__SECURITY__RATE_LIMIT__MAX_REQUESTS".to_string())
+        );
+        assert!(layered
+            .render_origins()
+            .contains("env NEXUS__SECURITY__RATE_LIMIT__MAX_REQUESTS"));
+    }
+
+    #[test]
+    fn test_bad_override_names_key_and_value() {
+        let fixture = Fixture::new().unwrap();
+        let loader = fixture.loader();
+        let set = |key: &str, value: &str| vec![(key.to_string(), value.to_string())];
+
+        let error = loader
+            .load_layered_with_env(Vec::new(), &set("agent.max_concurrent_agents", "lots"))
+            .unwrap_err();
+        let Some(NexusError::Config(message)) = error.downcast_ref::<NexusError>() else {
+            panic!("expected a config error, got {:#}", error);
+        };
+        assert!(message.contains("'agent.max_concurrent_agents'"));
+        assert!(message.contains("'lots'"));
+
+        let error = loader
+            .load_layered_with_env(env(&[("NEXUS__AGENT__MAX_AGENTS", "4")]), &[])
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown configuration key"));
+        assert!(error.to_string().contains("NEXUS__AGENT__MAX_AGENTS"));
+
+        assert!(loader
+            .load_layered_with_env(Vec::new(), &set("agent", "4"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        let fixture = Fixture::builder()
+            .set("agent.max_concurrent_agents", 2)
+            .set("agent.default_timeout_secs", 10)
+            .build()
+            .unwrap();
+        let layered = fixture
+            .loader()
+            .load_layered_with_env(
+                env(&[
+                    ("NEXUS__AGENT__MAX_CONCURRENT_AGENTS", "3"),
+                    ("NEXUS__AGENT__DEFAULT_TIMEOUT_SECS", "20"),
+                ]),
+                &[("agent.max_concurrent_agents".to_string(), "4".to_string())],
+            )
+            .unwrap();
+
+        assert_eq!(layered.config.agent.max_concurrent_agents, 4);
+        assert_eq!(layered.config.agent.default_timeout_secs, 20);
+        assert_eq!(
+            layered.origins["agent.max_concurrent_agents"],
+            ConfigLayer::Override
+        );
+        assert_eq!(
+            layered.origins["agent.data_dir"],
+            ConfigLayer::File(fixture.config_path().canonicalize().unwrap())
+        );
+    }
+
+    fn profile_fixture() -> Fixture {
+        Fixture::builder()
+            .set("agent.default_timeout_secs", 10)
+            .set("profile.prod.agent.max_concurrent_agents", 2)
+            .set("profile.prod.security.rate_limit.max_requests", 5)
+            .set("profile.dev.logging.level", "debug")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_profile_deep_merge() {
+        let fixture = profile_fixture();
+        let loader = fixture.loader();
+
+        let config = loader.load_with_profile("prod").unwrap();
+        assert_eq!(config.agent.max_concurrent_agents, 2);
+        assert_eq!(config.agent.default_timeout_secs, 10);
+        assert_eq!(config.security.rate_limit.max_requests, 5);
+        assert_eq!(config.logging.level, "info");
+
+        let base = loader.load_from_file(&fixture.config_path()).unwrap();
+        assert_eq!(
+            base.agent.max_concurrent_agents,
+            AgentConfig::default().max_concurrent_agents
+        );
+        assert_eq!(base.agent.default_timeout_secs, 10);
+
+        let layered = fixture
+            .loader()
+            .with_profile("prod")
+            .load_layered_with_env(Vec::new(), &[])
+            .unwrap();
+        assert_eq!(layered.config.agent.max_concurrent_agents, 2);
+        assert_eq!(
+            layered.origins["agent.max_concurrent_agents"],
+            ConfigLayer::Profile("prod".to_string())
+        );
+        assert!(!layered.origins.keys().any(|key| key.starts_with("profile.")));
+    }
+
+    #[test]
+    fn test_bad_profiles_are_config_errors() {
+        let fixture = profile_fixture();
+        let error = fixture.loader().load_with_profile("staging").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Configuration error: unknown profile 'staging', available profiles: dev, prod"
+        );
+
+        let fixture = Fixture::builder()
+            .set("profile.dev.agent.max_agents", 4)
+            .build()
+            .unwrap();
+        let error = fixture.loader().load_with_profile("dev").unwrap_err();
+        assert!(error.to_string().contains("'agent.max_agents' in profile 'dev'"));
+        let error = fixture
+            .loader()
+            .with_profile("dev")
+            .load_layered_with_env(Vec::new(), &[])
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown configuration key"));
+
+        // Validation runs on the merged result
+        let fixture = Fixture::builder()
+            .set("profile.ci.agent.max_concurrent_agents", 0)
+            .build()
+            .unwrap();
+        assert!(fixture.config().is_ok());
+        assert!(fixture.loader().load_with_profile("ci").is_err());
+    }
+
+    #[test]
+    fn test_profile_selected_by_env() {
+        let fixture = profile_fixture();
+        let layered = fixture
+            .loader()
+            .load_layered_with_env(env(&[(PROFILE_ENV, "dev")]), &[])
+            .unwrap();
+        assert_eq!(layered.config.logging.level, "debug");
+        assert_eq!(
+            layered.config.agent.max_concurrent_agents,
+            AgentConfig::default().max_concurrent_agents
+        );
+
+        // An explicit profile wins over the environment
+        let layered = fixture
+            .loader()
+            .with_profile("prod")
+            .load_layered_with_env(env(&[(PROFILE_ENV, "dev")]), &[])
+            .unwrap();
+        assert_eq!(layered.config.logging.level, "info");
+        assert_eq!(layered.config.agent.max_concurrent_agents, 2);
+    }
+
+    #[test]
+    fn test_set_plugin_disabled_keeps_comments() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nexus.toml");
+        std::fs::write(&path, "# Workspace settings\n[plugin]\n# Hot reload is off\nenable_hot_reload = false\n").unwrap();
+        let loader = ConfigLoader::new();
+        
+        assert!(loader.set_plugin_disabled(&path, "weather", true).unwrap());
+        assert!(!loader.set_plugin_disabled(&path, "weather", true).unwrap());
+        assert!(loader.set_plugin_disabled(&path, "trader", true).unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Workspace settings\n[plugin]\n# Hot reload is off\n"));
+        assert_eq!(loader.load_from_file(&path).unwrap().plugin.disabled, vec!["weather", "trader"]);
+        
+        assert!(loader.set_plugin_disabled(&path, "weather", false).unwrap());
+        assert!(!loader.set_plugin_disabled(&path, "weather", false).unwrap());
+        assert_eq!(loader.load_from_file(&path).unwrap().plugin.disabled, vec!["trader"]);
+        
+        // A file without a [plugin] table gets one
+        std::fs::write(&path, "[logging]\nlevel = \"warn\"\n").unwrap();
+        assert!(loader.set_plugin_disabled(&path, "weather", true).unwrap());
+        assert_eq!(loader.load_from_file(&path).unwrap().plugin.disabled, vec!["weather"]);
+    }
+
+    #[test]
+    fn test_validate_file_reports_every_problem() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nexus.toml");
+        std::fs::write(
+            &path,
+            "[agent]\nmax_concurrent_agents = \"many\"\ndefault_timeout_secs = -1\n\n\
+             [plugin.security_policy]\nisolation_level = \"strict\"\n",
+        )
+        .unwrap();
+
+        let report = ConfigLoader::new().validate_file(&path).unwrap();
+        assert!(!report.is_valid());
+        let keys: Vec<&str> = report
+            .errors
+            .iter()
+            .map(|problem| problem.key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "agent.default_timeout_secs",
+                "agent.max_concurrent_agents",
+                "plugin.security_policy.isolation_level",
+            ]
+        );
+        assert_eq!(report.errors[2].suggestions, ["Strict"]);
+        assert!(report.errors[2]
+            .to_string()
+            .ends_with("Did you mean 'Strict'?"));
+
+        // Checks of the whole configuration run once every value fits
+        std::fs::write(
+            &path,
+            "[agent]\nmax_concurrent_agents = 0\n[logging]\nlevel = \"Info\"\n",
+        )
+        .unwrap();
+        let report = ConfigLoader::new().validate_file(&path).unwrap();
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.errors[1].key, "logging.level");
+        assert_eq!(report.errors[1].suggestions, ["info"]);
+    }
+
+    #[test]
+    fn test_validate_file_warns_about_unknown_keys() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nexus.toml");
+        std::fs::write(
+            &path,
+            "[agent]\nmax_concurent_agents = 4\n\n\
+             [plugin.security_policy.trusted_keys]\nacme = \"key\"\n",
+        )
+        .unwrap();
+
+        let report = ConfigLoader::new().validate_file(&path).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(
+            report.warnings[0].to_string(),
+            "agent.max_concurent_agents: unknown configuration key. \
+             Did you mean 'agent.max_concurrent_agents'?"
+        );
+
+        // Loading rejects unknown keys in a profile, so they are errors
+        std::fs::write(&path, "[profile.ci.agent]\nmax_concurent_agents = 4\n").unwrap();
+        let report = ConfigLoader::new()
+            .with_profile("ci")
+            .validate_file(&path)
+            .unwrap();
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].message.contains("in profile 'ci'"));
+    }
+    
+    #[cfg(feature = "web3")]
+    #[test]
+    fn test_rpc_endpoints_accept_fallback_lists() {
+        let config = ConfigLoader::new()
+            .load_from_string(
+                "[web3.rpc_endpoints]\n\
+                 ethereum = [\"https://primary.example\", \"https://fallback.example\"]\n\
+                 base = \"https://base.example\"\n",
+            )
+            .unwrap();
+        let registry = config.web3.chain_registry();
+        let ethereum = registry.resolve("eth").unwrap();
+        assert_eq!(
+            config.web3.rpc_urls(&registry, ethereum),
+            ["https://primary.example", "https://fallback.example"]
+        );
+        assert_eq!(config.web3.rpc_endpoint(&registry, ethereum), Some("https://primary.example"));
+        let base = registry.resolve("base").unwrap();
+        assert_eq!(config.web3.rpc_urls(&registry, base), ["https://base.example"]);
+        let sepolia = registry.resolve("sepolia").unwrap();
+        assert!(config.web3.rpc_urls(&registry, sepolia).is_empty());
+    }
+
+    #[cfg(feature = "web3")]
+    #[test]
+    fn test_rpc_endpoints_must_name_known_chains() {
+        let loader = ConfigLoader::new();
+        let config = loader
+            .load_from_string(
+                "[web3.rpc_endpoints]\neth = \"https://rpc.example\"\nlocal = \"http://localhost:8545\"\n\n\
+                 [[web3.chains]]\nid = 31337\nname = \"anvil\"\naliases = [\"local\"]\ncurrency = \"ETH\"\n",
+            )
+            .unwrap();
+        let registry = config.web3.chain_registry();
+        let anvil = registry.resolve("anvil").unwrap();
+        assert_eq!(config.web3.rpc_endpoint(&registry, anvil), Some("http://localhost:8545"));
+        
+        let error = loader
+            .load_from_string("[web3.rpc_endpoints]\nbase = []\n")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "no RPC endpoint listed");
+
+        let error = loader
+            .load_from_string("[web3.rpc_endpoints]\npolygn = \"https://rpc.example\"\n")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "chain 'polygn' not found. Did you mean 'polygon'?"
+        );
+    }
+
+    #[cfg(feature = "web3")]
+    #[test]
+    fn test_user_alias_overrides_default_rpc_endpoint() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("nexus.toml"),
+            "[web3.rpc_endpoints]\neth = \"https://mine.example\"\n",
+        )
+        .unwrap();
+        let loader = ConfigLoader::in_dir(dir.path());
+
+        // The default `ethereum` entry is merged in next to the user's `eth`
+        let config = loader.load_layered_with_env(env(&[]), &[]).unwrap().config;
+        assert!(config.web3.rpc_endpoints.contains_key("ethereum"));
+        let registry = config.web3.chain_registry();
+        let ethereum = registry.resolve("ethereum").unwrap();
+        assert_eq!(config.web3.rpc_endpoint(&registry, ethereum), Some("https://mine.example"));
+
+        // Two entries of the user's own for one chain are a mistake
+        std::fs::write(
+            dir.path().join("nexus.toml"),
+            "[web3.rpc_endpoints]\neth = \"https://mine.example\"\n\
+             ethereum = \"https://other.example\"\n",
+        )
+        .unwrap();
+        let error = loader.load_layered_with_env(env(&[]), &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "RPC endpoints of chain 'ethereum' are already listed under 'eth'"
+        );
+    }
 }
@@ -0,0 +1,104 @@
+//! Correlation IDs for NEXUS
+//!
+//! A [`CorrelationId`] names one user action, from the CLI or command bus
+//! boundary down to every error and audit event it causes. The id travels
+//! as a tokio task-local: [`CorrelationId::scope`] runs a future with an id
+//! in effect and [`CorrelationId::current`] reads it back, so the code in
+//! between doesn't need an extra parameter. Spawned tasks don't inherit
+//! task-locals, so fan-out code wraps each sub-task in `scope` again.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+/// Identifier shared by everything done on behalf of one user action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CorrelationId(Uuid);
+
+impl CorrelationId {
+    /// Generate a fresh id
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Id in effect for the current task, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|id| *id).ok()
+    }
+
+    /// Id in effect for the current task, or a fresh one at a boundary
+    pub fn current_or_new() -> Self {
+        Self::current().unwrap_or_default()
+    }
+
+    /// Run `future` with this id in effect
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for CorrelationId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope_sets_current_id() {
+        assert_eq!(CorrelationId::current(), None);
+
+        let id = CorrelationId::new();
+        let seen = id
+            .scope(async {
+                tokio::task::yield_now().await;
+                CorrelationId::current_or_new()
+            })
+            .await;
+        assert_eq!(seen, id);
+        assert_eq!(CorrelationId::current(), None);
+
+        // Sub-tasks keep the id only when scoped explicitly
+        let (inherited, scoped) = id
+            .scope(async {
+                let inherited = tokio::spawn(async { CorrelationId::current() });
+                let scoped = tokio::spawn(id.scope(async { CorrelationId::current() }));
+                (inherited.await.unwrap(), scoped.await.unwrap())
+            })
+            .await;
+        assert_eq!(inherited, None);
+        assert_eq!(scoped, Some(id));
+    }
+
+    #[test]
+    fn test_round_trips_as_string() {
+        let id = CorrelationId::new();
+        assert_eq!(id.to_string().parse::<CorrelationId>().unwrap(), id);
+        assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{}\"", id));
+        assert!("not-an-id".parse::<CorrelationId>().is_err());
+    }
+}
@@ -0,0 +1,451 @@
+//! Crash reporting for NEXUS
+//!
+//! Installs a panic hook that writes a size-capped crash report into the
+//! data directory, and provides access to previously written reports so the
+//! CLI can list them and point users at the newest one on the next start.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default upper bound for a serialized crash report
+pub const DEFAULT_MAX_REPORT_BYTES: usize = 64 * 1024;
+
+/// Number of recent log lines kept for crash reports
+const RECENT_LOG_CAPACITY: usize = 200;
+
+/// Appended to a panic message shortened to fit the size cap
+const TRUNCATED_MARKER: &str = " [truncated]";
+
+/// Marker file recording the newest report the user has already been told about
+const ACKNOWLEDGED_MARKER: &str = ".acknowledged";
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Reports written by this process, keeping ids unique within a millisecond
+static NEXT_REPORT: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Record a log line so it can be included in a future crash report
+pub fn record_log_line(line: impl Into<String>) {
+    if let Ok(mut logs) = RECENT_LOGS.lock() {
+        if logs.len() == RECENT_LOG_CAPACITY {
+            logs.pop_front();
+        }
+        logs.push_back(line.into());
+    }
+}
+
+fn recent_log_lines() -> Vec<String> {
+    // A poisoned lock still holds usable lines; never panic from the hook
+    match RECENT_LOGS.lock() {
+        Ok(logs) => logs.iter().cloned().collect(),
+        Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+    }
+}
+
+/// A single crash report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Report identifier, also the file stem
+    pub id: String,
+    /// Unix timestamp of the crash in seconds
+    pub timestamp_secs: u64,
+    /// Panic message
+    pub message: String,
+    /// Source location of the panic
+    pub location: Option<String>,
+    /// Name of the panicking thread
+    pub thread: Option<String>,
+    /// Captured backtrace
+    pub backtrace: String,
+    /// NEXUS version
+    pub version: String,
+    /// Operating system and architecture
+    pub platform: String,
+    /// Enabled compile-time features
+    pub features: Vec<String>,
+    /// Most recent log lines before the crash
+    pub recent_logs: Vec<String>,
+    /// Whether the report was shortened to fit the size cap
+    pub truncated: bool,
+}
+
+impl CrashReport {
+    fn from_panic(info: &PanicHookInfo<'_>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "non-string panic payload".to_string()
+        };
+
+        Self {
+            id: report_id(now),
+            timestamp_secs: now.as_secs(),
+            message,
+            location: info.location().map(ToString::to_string),
+            thread: std::thread::current().name().map(ToString::to_string),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            features: enabled_features(),
+            recent_logs: recent_log_lines(),
+            truncated: false,
+        }
+    }
+
+    /// Serialize the report, dropping old log lines, backtrace frames and
+    /// finally the end of the message until it fits
+    fn serialize_capped(&mut self, max_bytes: usize) -> Result<String> {
+        loop {
+            let json = serde_json::to_string_pretty(self)?;
+            if json.len() <= max_bytes {
+                return Ok(json);
+            }
+
+            self.truncated = true;
+            if !self.recent_logs.is_empty() {
+                let excess = self.recent_logs.len().div_ceil(2);
+                self.recent_logs.drain(..excess);
+            } else if !self.backtrace.is_empty() {
+                let keep = self.backtrace.lines().count() / 2;
+                self.backtrace = self
+                    .backtrace
+                    .lines()
+                    .take(keep)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            } else if !self.message.is_empty() && self.message != TRUNCATED_MARKER {
+                let text = self
+                    .message
+                    .strip_suffix(TRUNCATED_MARKER)
+                    .unwrap_or(&self.message);
+                let mut keep = text.len() / 2;
+                while !text.is_char_boundary(keep) {
+                    keep -= 1;
+                }
+                self.message = format!("{}{}", &text[..keep], TRUNCATED_MARKER);
+            } else {
+                return Ok(json);
+            }
+        }
+    }
+}
+
+/// Id of a report written `since_epoch`, unique even for threads panicking together
+///
+/// Ids start with the time, so they sort oldest first.
+fn report_id(since_epoch: Duration) -> String {
+    format!(
+        "crash-{}-{}-{}",
+        since_epoch.as_millis(),
+        std::process::id(),
+        NEXT_REPORT.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+fn enabled_features() -> Vec<String> {
+    let mut features = Vec::new();
+    if cfg!(feature = "security") {
+        features.push("security".to_string());
+    }
+    if cfg!(feature = "web3") {
+        features.push("web3".to_string());
+    }
+    features
+}
+
+/// Writes and manages crash reports in a directory
+#[derive(Debug, Clone)]
+pub struct CrashReporter {
+    dir: PathBuf,
+    max_bytes: usize,
+}
+
+impl CrashReporter {
+    /// Create a reporter storing reports in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes: DEFAULT_MAX_REPORT_BYTES,
+        }
+    }
+
+    /// Set the maximum size of a single report
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Directory holding the reports
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Install the panic hook, chaining to the previously installed one
+    ///
+    /// Panics inside tokio tasks run the same hook, so they are captured too.
+    /// Report writing never panics, and a panic raised while a report is
+    /// being written on the same thread is not reported again.
+    pub fn install(&self) {
+        let reporter = self.clone();
+        let previous = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            let reentered = IN_HOOK.with(|flag| flag.replace(true));
+            if !reentered {
+                if let Err(e) = reporter.write(&mut CrashReport::from_panic(info)) {
+                    eprintln!("Failed to write crash report: {}", e);
+                }
+                IN_HOOK.with(|flag| flag.set(false));
+            }
+            previous(info);
+        }));
+    }
+
+    /// Write a report to disk, returning its path
+    fn write(&self, report: &mut CrashReport) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create crash directory: {:?}", self.dir))?;
+
+        let json = report.serialize_capped(self.max_bytes)?;
+        let path = self.report_path(&report.id);
+        let temp = path.with_extension("json.tmp");
+
+        std::fs::write(&temp, json)
+            .with_context(|| format!("Failed to write crash report: {:?}", temp))?;
+        std::fs::rename(&temp, &path)
+            .with_context(|| format!("Failed to finalize crash report: {:?}", path))?;
+
+        Ok(path)
+    }
+
+    fn report_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    /// List all reports, newest first
+    pub fn list(&self) -> Result<Vec<CrashReport>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut reports = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read crash directory: {:?}", self.dir))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            match Self::read_report(&path) {
+                Ok(report) => reports.push(report),
+                Err(e) => tracing::warn!("Skipping unreadable crash report {:?}: {}", path, e),
+            }
+        }
+
+        reports.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(reports)
+    }
+
+    fn read_report(path: &Path) -> Result<CrashReport> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read crash report: {:?}", path))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse crash report: {:?}", path))
+    }
+
+    /// Load a report by id
+    pub fn load(&self, id: &str) -> Result<CrashReport> {
+        Self::read_report(&self.report_path(id))
+    }
+
+    /// Delete a report by id
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let path = self.report_path(id);
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to delete crash report: {:?}", path))
+    }
+
+    /// Delete all reports, returning how many were removed
+    pub fn delete_all(&self) -> Result<usize> {
+        let reports = self.list()?;
+        for report in &reports {
+            self.delete(&report.id)?;
+        }
+        Ok(reports.len())
+    }
+
+    /// Return the newest report the user hasn't been told about yet, marking it as seen
+    pub fn take_notice(&self) -> Result<Option<CrashReport>> {
+        let Some(newest) = self.list()?.into_iter().next() else {
+            return Ok(None);
+        };
+
+        let marker = self.dir.join(ACKNOWLEDGED_MARKER);
+        let acknowledged = std::fs::read_to_string(&marker).unwrap_or_default();
+        if acknowledged.trim() >= newest.id.as_str() {
+            return Ok(None);
+        }
+
+        std::fs::write(&marker, &newest.id)
+            .with_context(|| format!("Failed to update crash marker: {:?}", marker))?;
+        Ok(Some(newest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoggingConfig;
+    use crate::logging;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_panic_produces_report() {
+        let dir = TempDir::new().unwrap();
+        let reporter = CrashReporter::new(dir.path());
+        reporter.install();
+
+        record_log_line("starting controlled crash test");
+        let result = std::thread::Builder::new()
+            .name("crash-test".to_string())
+            .spawn(|| panic!("controlled crash"))
+            .unwrap()
+            .join();
+        assert!(result.is_err());
+
+        // Restore the default hook for the rest of the test binary
+        let _ = std::panic::take_hook();
+
+        let reports = reporter.list().unwrap();
+        let report = reports
+            .iter()
+            .find(|r| r.message == "controlled crash")
+            .unwrap();
+        assert_eq!(report.thread.as_deref(), Some("crash-test"));
+        assert!(report.location.as_deref().unwrap().contains("crash.rs"));
+        assert!(report
+            .recent_logs
+            .iter()
+            .any(|l| l.contains("controlled crash test")));
+        assert_eq!(report.version, env!("CARGO_PKG_VERSION"));
+
+        // Surfaced once on the next run, then acknowledged
+        assert!(reporter.take_notice().unwrap().is_some());
+        assert!(reporter.take_notice().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_log_lines_reach_crash_reports() {
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            ..LoggingConfig::default()
+        };
+        let (subscriber, _guard) = logging::subscriber(&config).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(plugin = "weather", "kept for crash reports");
+            tracing::debug!("below the configured level");
+        });
+
+        let lines = recent_log_lines();
+        let line = lines
+            .iter()
+            .find(|line| line.contains("kept for crash reports"))
+            .unwrap();
+        assert!(line.contains("plugin=\"weather\""));
+        assert!(!line.ends_with('\n'));
+        assert!(!lines.iter().any(|line| line.contains("below the configured level")));
+    }
+
+    #[test]
+    fn test_report_ids_are_unique_within_a_millisecond() {
+        let now = Duration::from_secs(1_700_000_000);
+        let first = report_id(now);
+        assert!(first.starts_with("crash-1700000000000-"));
+        assert_ne!(first, report_id(now));
+    }
+
+    #[test]
+    fn test_report_size_cap() {
+        let dir = TempDir::new().unwrap();
+        let reporter = CrashReporter::new(dir.path()).with_max_bytes(2048);
+
+        let mut report = CrashReport {
+            id: "crash-1".to_string(),
+            timestamp_secs: 1,
+            message: "boom".to_string(),
+            location: None,
+            thread: None,
+            backtrace: "frame\n".repeat(500),
+            version: "0.0.0".to_string(),
+            platform: "test".to_string(),
+            features: Vec::new(),
+            recent_logs: vec!["x".repeat(100); 100],
+            truncated: false,
+        };
+
+        let path = reporter.write(&mut report).unwrap();
+        let written = std::fs::read_to_string(path).unwrap();
+        assert!(written.len() <= 2048);
+
+        let loaded = reporter.load("crash-1").unwrap();
+        assert!(loaded.truncated);
+        assert_eq!(loaded.message, "boom");
+
+        // A huge panic payload is cut too, at a char boundary
+        report.id = "crash-2".to_string();
+        report.message = "é".repeat(1024 * 1024);
+        let path = reporter.write(&mut report).unwrap();
+        assert!(std::fs::metadata(path).unwrap().len() <= 2048);
+        let loaded = reporter.load("crash-2").unwrap();
+        assert!(loaded.message.ends_with(TRUNCATED_MARKER));
+        assert!(loaded.message.starts_with('é'));
+    }
+
+    #[test]
+    fn test_list_and_delete() {
+        let dir = TempDir::new().unwrap();
+        let reporter = CrashReporter::new(dir.path());
+        assert!(reporter.list().unwrap().is_empty());
+
+        for id in ["crash-1", "crash-2"] {
+            let mut report = CrashReport {
+                id: id.to_string(),
+                timestamp_secs: 1,
+                message: "boom".to_string(),
+                location: None,
+                thread: None,
+                backtrace: String::new(),
+                version: "0.0.0".to_string(),
+                platform: "test".to_string(),
+                features: Vec::new(),
+                recent_logs: Vec::new(),
+                truncated: false,
+            };
+            reporter.write(&mut report).unwrap();
+        }
+
+        let reports = reporter.list().unwrap();
+        assert_eq!(reports[0].id, "crash-2");
+
+        reporter.delete("crash-2").unwrap();
+        assert_eq!(reporter.delete_all().unwrap(), 1);
+        assert!(reporter.list().unwrap().is_empty());
+    }
+}
@@ -6,6 +6,8 @@
 use std::fmt;
 use thiserror::Error;
 
+use crate::correlation::CorrelationId;
+
 /// Main result type for NEXUS operations
 pub type Result<T> = std::result::Result<T, NexusError>;
 
@@ -149,6 +151,9 @@ pub enum PluginError {
     
     #[error("Plugin configuration invalid: {0}")]
     ConfigurationInvalid(String),
+    
+    #[error("Plugin disabled: {0}")]
+    Disabled(String),
 }
 
 /// Web3-related errors
@@ -222,19 +227,30 @@ pub struct ErrorContext {
     pub details: std::collections::HashMap<String, String>,
     /// Timestamp when the error occurred
     pub timestamp: std::time::SystemTime,
+    /// User action the error belongs to
+    pub correlation_id: Option<CorrelationId>,
 }
 
 impl ErrorContext {
     /// Create a new error context
+    ///
+    /// Picks up the correlation id in effect for the current task, if any.
     pub fn new(component: &str, operation: &str) -> Self {
         Self {
             component: component.to_string(),
             operation: operation.to_string(),
             details: std::collections::HashMap::new(),
             timestamp: std::time::SystemTime::now(),
+            correlation_id: CorrelationId::current(),
         }
     }
     
+    /// Attach the correlation id of the action the error belongs to
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+    
     /// Add detail to the error context
     pub fn with_detail(mut self, key: &str, value: &str) -> Self {
         self.details.insert(key.to_string(), value.to_string());
@@ -264,6 +280,10 @@ impl fmt::Display for ContextualError {
             write!(f, ")")?;
         }
         
+        if let Some(correlation_id) = &self.context.correlation_id {
+            write!(f, " [correlation_id: {}]", correlation_id)?;
+        }
+        
         Ok(())
     }
 }
@@ -351,6 +371,20 @@ mod tests {
         assert!(error_string.contains("config::load"));
         assert!(error_string.contains("Configuration error: Test error"));
         assert!(error_string.contains("file: test.toml"));
+        assert!(!error_string.contains("correlation_id"));
+    }
+    
+    #[tokio::test]
+    async fn test_contextual_error_carries_correlation_id() {
+        let id = CorrelationId::new();
+        let result: Result<()> = Err(NexusError::Config("Test".to_string()));
+        let error = id
+            .scope(async { result.with_simple_context("agent", "execute") })
+            .await
+            .unwrap_err();
+        
+        assert_eq!(error.context.correlation_id, Some(id));
+        assert!(error.to_string().ends_with(&format!("[correlation_id: {}]", id)));
     }
     
     #[test]
@@ -0,0 +1,263 @@
+//! Typed events for observing NEXUS
+//!
+//! Components publish a [`NexusEvent`] on an [`EventBus`] when something
+//! worth observing happens: an agent run starts or ends, a plugin is loaded,
+//! a request is rate limited. Anything that wants to react (metrics, an
+//! editor connected to NEXUS, a test) subscribes instead of being called
+//! inline, so publishing stays cheap and never waits for a subscriber.
+//!
+//! Each subscriber sees the events published after it subscribed, in order.
+//! One that falls more than the bus capacity behind loses the oldest events
+//! and is told how many with [`EventError::Lagged`].
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::correlation::CorrelationId;
+
+/// Events a subscriber can fall behind by before losing the oldest
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Something that happened in NEXUS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NexusEvent {
+    /// An agent became available
+    AgentRegistered {
+        /// Agent name
+        agent: String,
+    },
+    /// An agent run started
+    ExecutionStarted {
+        /// Run id, repeated by the run's other events
+        run_id: Uuid,
+        /// Agent name
+        agent: String,
+        /// Correlation id of the request that caused the run
+        correlation_id: CorrelationId,
+    },
+    /// An agent run returned an output
+    ExecutionFinished {
+        /// Run id
+        run_id: Uuid,
+        /// Whether the output reports success
+        success: bool,
+        /// Run time
+        duration_ms: u64,
+    },
+    /// An agent run returned an error, instead of finishing
+    ExecutionFailed {
+        /// Run id
+        run_id: Uuid,
+        /// Error variant, e.g. `timeout`
+        error_kind: String,
+    },
+    /// A plugin was loaded or reloaded
+    PluginLoaded {
+        /// Plugin name
+        plugin: String,
+        /// Plugin version
+        version: String,
+    },
+    /// A plugin was unloaded
+    PluginUnloaded {
+        /// Plugin name
+        plugin: String,
+    },
+    /// The rate limiter turned a request away
+    RateLimitRejected {
+        /// Key the limit applies to, e.g. a client or agent
+        key: String,
+    },
+    /// The configuration was loaded again
+    ConfigReloaded {
+        /// File it was loaded from
+        path: PathBuf,
+    },
+}
+
+/// Why [`EventSubscription::recv`] returned no event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum EventError {
+    /// This many events were lost; the next `recv` returns the oldest kept
+    #[error("missed {0} events")]
+    Lagged(u64),
+
+    /// Every [`EventBus`] handle was dropped
+    #[error("event bus closed")]
+    Closed,
+}
+
+/// Broadcasts [`NexusEvent`]s to every subscriber
+///
+/// Cloning gives another handle to the same bus.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<NexusEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// Create a bus keeping up to `capacity` events for slow subscribers
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Send `event` to the current subscribers, if any
+    pub fn publish(&self, event: NexusEvent) {
+        // No subscribers is not an error: nobody is watching
+        let _ = self.sender.send(event);
+    }
+
+    /// Receive the events published from now on
+    pub fn subscribe(&self) -> EventSubscription {
+        EventSubscription {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// Subscriptions currently open
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+/// Events of an [`EventBus`], in publication order
+#[derive(Debug)]
+pub struct EventSubscription {
+    receiver: broadcast::Receiver<NexusEvent>,
+}
+
+impl EventSubscription {
+    /// Wait for the next event
+    pub async fn recv(&mut self) -> Result<NexusEvent, EventError> {
+        self.receiver.recv().await.map_err(|e| match e {
+            broadcast::error::RecvError::Lagged(missed) => EventError::Lagged(missed),
+            broadcast::error::RecvError::Closed => EventError::Closed,
+        })
+    }
+
+    /// The next event if one is waiting
+    pub fn try_recv(&mut self) -> Option<Result<NexusEvent, EventError>> {
+        match self.receiver.try_recv() {
+            Ok(event) => Some(Ok(event)),
+            Err(broadcast::error::TryRecvError::Empty) => None,
+            Err(broadcast::error::TryRecvError::Lagged(missed)) => {
+                Some(Err(EventError::Lagged(missed)))
+            },
+            Err(broadcast::error::TryRecvError::Closed) => Some(Err(EventError::Closed)),
+        }
+    }
+}
+
+/// Collects the events of a bus, for tests
+#[derive(Debug)]
+pub struct EventRecorder {
+    subscription: EventSubscription,
+    events: Vec<NexusEvent>,
+    missed: u64,
+}
+
+impl EventRecorder {
+    /// Record the events published on `bus` from now on
+    pub fn new(bus: &EventBus) -> Self {
+        Self {
+            subscription: bus.subscribe(),
+            events: Vec::new(),
+            missed: 0,
+        }
+    }
+
+    /// Every event recorded so far, including ones published since the last call
+    pub fn events(&mut self) -> &[NexusEvent] {
+        while let Some(received) = self.subscription.try_recv() {
+            match received {
+                Ok(event) => self.events.push(event),
+                Err(EventError::Lagged(missed)) => self.missed += missed,
+                Err(EventError::Closed) => break,
+            }
+        }
+        &self.events
+    }
+
+    /// Events lost because the recorder fell behind
+    pub fn missed(&mut self) -> u64 {
+        self.events();
+        self.missed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unloaded(plugin: &str) -> NexusEvent {
+        NexusEvent::PluginUnloaded {
+            plugin: plugin.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_see_later_events_in_order() {
+        let bus = EventBus::default();
+        bus.publish(unloaded("before"));
+        let mut subscription = bus.subscribe();
+        let mut recorder = EventRecorder::new(&bus);
+        assert_eq!(bus.subscriber_count(), 2);
+
+        let run_id = Uuid::new_v4();
+        let started = NexusEvent::ExecutionStarted {
+            run_id,
+            agent: "echo".to_string(),
+            correlation_id: CorrelationId::new(),
+        };
+        let finished = NexusEvent::ExecutionFinished {
+            run_id,
+            success: true,
+            duration_ms: 12,
+        };
+        bus.publish(started.clone());
+        bus.publish(finished.clone());
+
+        assert_eq!(subscription.recv().await, Ok(started.clone()));
+        assert_eq!(subscription.recv().await, Ok(finished.clone()));
+        assert_eq!(subscription.try_recv(), None);
+        assert_eq!(recorder.events(), [started, finished]);
+
+        drop(bus);
+        assert_eq!(subscription.recv().await, Err(EventError::Closed));
+    }
+
+    #[test]
+    fn test_slow_subscriber_is_told_what_it_missed() {
+        let bus = EventBus::new(2);
+        let mut recorder = EventRecorder::new(&bus);
+        for plugin in ["a", "b", "c", "d", "e"] {
+            bus.publish(unloaded(plugin));
+        }
+
+        assert_eq!(recorder.events(), [unloaded("d"), unloaded("e")]);
+        assert_eq!(recorder.missed(), 3);
+    }
+
+    #[test]
+    fn test_events_serialize_with_their_type() {
+        let event = NexusEvent::ExecutionFailed {
+            run_id: Uuid::nil(),
+            error_kind: "timeout".to_string(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "execution_failed");
+        assert_eq!(json["error_kind"], "timeout");
+        assert_eq!(serde_json::from_value::<NexusEvent>(json).unwrap(), event);
+    }
+}
@@ -0,0 +1,608 @@
+//! Golden runs for deterministic agents
+//!
+//! An agent such as a code analyzer should produce the same output for the
+//! same input. [`GoldenStore`] keeps one copy of an agent's output per input
+//! under `<data_dir>/golden`, canonicalized and with volatile fields such as
+//! durations stripped, and compares later outputs against it. The result is
+//! an [`OutputDiff`] naming each added, removed or changed JSON path.
+//!
+//! With [`GoldenStore::with_artifacts`], large output fields are recorded in
+//! the [`ArtifactStore`] and the golden file keeps only their refs.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use crate::artifacts::ArtifactStore;
+use crate::canonical::{canonical_hash, to_canonical_string};
+use crate::config::{GoldenConfig, GoldenMode};
+use crate::error::{AgentError, NexusError, Result};
+
+/// Directory under the agent data directory holding golden outputs
+pub const GOLDEN_DIR: &str = "golden";
+
+/// Digest identifying an agent input, for [`GoldenStore`] lookups
+///
+/// The hex SHA-256 of the input's canonical JSON, so inputs that differ
+/// only in key order share a digest.
+pub fn input_digest(input: &Value) -> Result<String> {
+    let hash = canonical_hash(input).map_err(|e| NexusError::Internal(e.to_string()))?;
+    Ok(hash.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// One step of a path into a JSON value
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+fn render_path(path: &[PathSegment]) -> String {
+    let mut rendered = String::from("$");
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                rendered.push('.');
+                rendered.push_str(key);
+            }
+            PathSegment::Index(index) => rendered.push_str(&format!("[{}]", index)),
+        }
+    }
+    rendered
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternSegment {
+    /// A key, or an array index written as a number
+    Literal(String),
+    /// `*`: any one key or index
+    Any,
+    /// `**`: any number of keys or indices, including none
+    AnyDepth,
+}
+
+impl PatternSegment {
+    fn matches(&self, segment: &PathSegment) -> bool {
+        match (self, segment) {
+            (Self::Literal(literal), PathSegment::Key(key)) => literal == key,
+            (Self::Literal(literal), PathSegment::Index(index)) => {
+                literal.parse::<usize>().ok() == Some(*index)
+            }
+            (Self::Any | Self::AnyDepth, _) => true,
+        }
+    }
+}
+
+/// Pattern selecting fields of an output by path
+///
+/// Segments are separated by dots, with an optional leading `$.`. `*`
+/// matches any one key or array index and `**` any number of them, so
+/// `**.timestamp` matches a `timestamp` field at any depth. Array indices
+/// may also be written in brackets: `items[*].id` is `items.*.id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPattern {
+    segments: Vec<PatternSegment>,
+}
+
+impl FieldPattern {
+    /// Parse a pattern
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let body = pattern.strip_prefix("$.").unwrap_or(pattern);
+        let body = body.replace('[', ".").replace(']', "");
+        let segments = body
+            .split('.')
+            .map(|segment| match segment {
+                "" => Err(NexusError::Config(format!(
+                    "invalid field pattern '{}': empty segment",
+                    pattern
+                ))),
+                "*" => Ok(PatternSegment::Any),
+                "**" => Ok(PatternSegment::AnyDepth),
+                literal => Ok(PatternSegment::Literal(literal.to_string())),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { segments })
+    }
+
+    fn matches(&self, path: &[PathSegment]) -> bool {
+        fn matches_from(pattern: &[PatternSegment], path: &[PathSegment]) -> bool {
+            match pattern.split_first() {
+                None => path.is_empty(),
+                Some((PatternSegment::AnyDepth, rest)) => {
+                    (0..=path.len()).any(|skip| matches_from(rest, &path[skip..]))
+                }
+                Some((segment, rest)) => path.split_first().is_some_and(|(first, tail)| {
+                    segment.matches(first) && matches_from(rest, tail)
+                }),
+            }
+        }
+        matches_from(&self.segments, path)
+    }
+}
+
+/// Remove the object fields matching any of `patterns` from `value`
+fn strip(value: &mut Value, patterns: &[FieldPattern], path: &mut Vec<PathSegment>) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|key, _| {
+                path.push(PathSegment::Key(key.clone()));
+                let volatile = patterns.iter().any(|pattern| pattern.matches(path));
+                path.pop();
+                !volatile
+            });
+            for (key, child) in fields.iter_mut() {
+                path.push(PathSegment::Key(key.clone()));
+                strip(child, patterns, path);
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                path.push(PathSegment::Index(index));
+                strip(child, patterns, path);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// One difference between a golden output and a new one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum DiffEntry {
+    /// The new output has a value the golden one lacks
+    Added {
+        /// Path of the value, e.g. `$.findings[2].line`
+        path: String,
+        /// New value
+        after: Value,
+    },
+    /// The golden output has a value the new one lacks
+    Removed {
+        /// Path of the value
+        path: String,
+        /// Golden value
+        before: Value,
+    },
+    /// The value at a path differs
+    Changed {
+        /// Path of the value
+        path: String,
+        /// Golden value
+        before: Value,
+        /// New value
+        after: Value,
+    },
+}
+
+impl DiffEntry {
+    /// Path of the differing value
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Changed { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+impl fmt::Display for DiffEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { path, after } => write!(f, "+ {}: {}", path, after),
+            Self::Removed { path, before } => write!(f, "- {}: {}", path, before),
+            Self::Changed {
+                path,
+                before,
+                after,
+            } => write!(f, "~ {}: {} -> {}", path, before, after),
+        }
+    }
+}
+
+/// Differences between a golden output and a new one, in path order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputDiff {
+    /// Each differing path
+    pub entries: Vec<DiffEntry>,
+}
+
+impl OutputDiff {
+    /// Compare two outputs
+    pub fn between(golden: &Value, new: &Value) -> Self {
+        let mut diff = Self::default();
+        diff.compare(golden, new, &mut Vec::new());
+        diff
+    }
+
+    /// Whether the outputs match
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The first `max` differences on one line, noting how many were left out
+    pub fn summary(&self, max: usize) -> String {
+        let mut summary = self
+            .entries
+            .iter()
+            .take(max)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        if self.entries.len() > max {
+            summary.push_str(&format!("; and {} more", self.entries.len() - max));
+        }
+        summary
+    }
+
+    fn compare(&mut self, golden: &Value, new: &Value, path: &mut Vec<PathSegment>) {
+        match (golden, new) {
+            (Value::Object(before), Value::Object(after)) => {
+                let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+                keys.sort();
+                keys.dedup();
+                for key in keys {
+                    path.push(PathSegment::Key(key.clone()));
+                    self.compare_entry(before.get(key), after.get(key), path);
+                    path.pop();
+                }
+            }
+            (Value::Array(before), Value::Array(after)) => {
+                for index in 0..before.len().max(after.len()) {
+                    path.push(PathSegment::Index(index));
+                    self.compare_entry(before.get(index), after.get(index), path);
+                    path.pop();
+                }
+            }
+            (before, after) if before != after => self.entries.push(DiffEntry::Changed {
+                path: render_path(path),
+                before: before.clone(),
+                after: after.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    fn compare_entry(
+        &mut self,
+        before: Option<&Value>,
+        after: Option<&Value>,
+        path: &mut Vec<PathSegment>,
+    ) {
+        match (before, after) {
+            (Some(before), Some(after)) => self.compare(before, after, path),
+            (Some(before), None) => self.entries.push(DiffEntry::Removed {
+                path: render_path(path),
+                before: before.clone(),
+            }),
+            (None, Some(after)) => self.entries.push(DiffEntry::Added {
+                path: render_path(path),
+                after: after.clone(),
+            }),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Golden outputs of agents, one per agent and input digest
+#[derive(Debug, Clone)]
+pub struct GoldenStore {
+    dir: PathBuf,
+    volatile_fields: Vec<FieldPattern>,
+    artifacts: Option<(ArtifactStore, usize)>,
+}
+
+impl GoldenStore {
+    /// Store under `<data_dir>/golden`, stripping no fields
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(GOLDEN_DIR),
+            volatile_fields: Vec::new(),
+            artifacts: None,
+        }
+    }
+
+    /// Store under `<data_dir>/golden`, stripping the configured volatile fields
+    pub fn from_config(data_dir: &Path, config: &GoldenConfig) -> Result<Self> {
+        Self::new(data_dir).with_volatile_fields(config.volatile_fields.iter().map(String::as_str))
+    }
+
+    /// Also strip the fields matching these [`FieldPattern`]s
+    pub fn with_volatile_fields<'a>(
+        mut self,
+        patterns: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self> {
+        for pattern in patterns {
+            self.volatile_fields.push(FieldPattern::parse(pattern)?);
+        }
+        Ok(self)
+    }
+
+    /// Record output fields larger than `threshold` bytes in `artifacts`
+    ///
+    /// Golden files then hold [`ArtifactRef`](crate::artifacts::ArtifactRef)s
+    /// in their place, which are resolved before comparing. A threshold of 0
+    /// keeps every field inline.
+    pub fn with_artifacts(mut self, artifacts: ArtifactStore, threshold: usize) -> Self {
+        self.artifacts = Some((artifacts, threshold));
+        self
+    }
+
+    /// Directory holding the golden outputs
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File holding the golden output of `agent` for `input_digest`
+    ///
+    /// Namespaced agent names like `plugin/agent` become subdirectories.
+    pub fn path_for(&self, agent: &str, input_digest: &str) -> Result<PathBuf> {
+        let agent_dir = Path::new(agent);
+        let safe_agent = !agent.is_empty()
+            && !agent.contains('\\')
+            && agent_dir
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !safe_agent {
+            return Err(NexusError::Config(format!(
+                "invalid agent name '{}'",
+                agent
+            )));
+        }
+        let safe_digest = !input_digest.is_empty()
+            && input_digest
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !safe_digest {
+            return Err(NexusError::Config(format!(
+                "invalid input digest '{}'",
+                input_digest
+            )));
+        }
+        Ok(self
+            .dir
+            .join(agent_dir)
+            .join(format!("{}.json", input_digest)))
+    }
+
+    /// The form outputs are stored and compared in: JSON with volatile fields removed
+    pub fn canonicalize(&self, output: &impl Serialize) -> Result<Value> {
+        let mut value = serde_json::to_value(output)?;
+        strip(&mut value, &self.volatile_fields, &mut Vec::new());
+        Ok(value)
+    }
+
+    /// Store `output` as the golden output of `agent` for `input_digest`
+    pub fn record(
+        &self,
+        agent: &str,
+        input_digest: &str,
+        output: &impl Serialize,
+    ) -> Result<PathBuf> {
+        let path = self.path_for(agent, input_digest)?;
+        let mut value = self.canonicalize(output)?;
+        if let Some((artifacts, threshold)) = &self.artifacts {
+            if *threshold > 0 {
+                artifacts
+                    .spill(&mut value, *threshold)
+                    .map_err(|e| NexusError::Internal(e.to_string()))?;
+            }
+        }
+        let canonical =
+            to_canonical_string(&value).map_err(|e| NexusError::Internal(e.to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, canonical)?;
+        Ok(path)
+    }
+
+    /// Compare `output` with the golden output of `agent` for `input_digest`
+    ///
+    /// Fails with [`AgentError::NotFound`] when nothing was recorded.
+    pub fn compare(
+        &self,
+        agent: &str,
+        input_digest: &str,
+        output: &impl Serialize,
+    ) -> Result<OutputDiff> {
+        let path = self.path_for(agent, input_digest)?;
+        let golden = match std::fs::read_to_string(&path) {
+            Ok(golden) => golden,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(AgentError::NotFound(format!(
+                    "no golden output for '{}' with input {}",
+                    agent, input_digest
+                ))
+                .into())
+            }
+            Err(e) => return Err(e.into()),
+        };
+        // Fields made volatile since recording are ignored too
+        let mut golden: Value = serde_json::from_str(&golden)?;
+        if let Some((artifacts, _)) = &self.artifacts {
+            artifacts
+                .resolve(&mut golden)
+                .map_err(|e| NexusError::Internal(e.to_string()))?;
+        }
+        strip(&mut golden, &self.volatile_fields, &mut Vec::new());
+
+        Ok(OutputDiff::between(&golden, &self.canonicalize(output)?))
+    }
+
+    /// Record or verify `output` as `mode` asks
+    ///
+    /// In [`GoldenMode::Verify`] a difference fails with
+    /// [`AgentError::ExecutionFailed`] listing the first `max_reported` entries.
+    pub fn apply(
+        &self,
+        mode: GoldenMode,
+        agent: &str,
+        input_digest: &str,
+        output: &impl Serialize,
+        max_reported: usize,
+    ) -> Result<()> {
+        match mode {
+            GoldenMode::Off => Ok(()),
+            GoldenMode::Record => self.record(agent, input_digest, output).map(|_| ()),
+            GoldenMode::Verify => {
+                let diff = self.compare(agent, input_digest, output)?;
+                if diff.is_empty() {
+                    return Ok(());
+                }
+                Err(AgentError::ExecutionFailed(format!(
+                    "output of '{}' differs from its golden run in {} place(s): {}",
+                    agent,
+                    diff.entries.len(),
+                    diff.summary(max_reported)
+                ))
+                .into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn store(dir: &TempDir) -> GoldenStore {
+        GoldenStore::from_config(dir.path(), &GoldenConfig::default()).unwrap()
+    }
+
+    fn report(severity: &str, timestamp: &str) -> Value {
+        json!({
+            "findings": [
+                {"file": "lib.rs", "line": 3, "severity": "low"},
+                {"file": "main.rs", "line": 9, "severity": severity},
+            ],
+            "metadata": {"timestamp": timestamp, "duration_ms": 42, "tool": "audit"},
+        })
+    }
+
+    #[test]
+    fn test_record_then_verify() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let digest = input_digest(&json!({"path": "src"})).unwrap();
+        assert_eq!(digest, input_digest(&json!({"path": "src"})).unwrap());
+
+        let path = store
+            .record("auditor", &digest, &report("high", "2026-01-01"))
+            .unwrap();
+        assert!(path.starts_with(dir.path().join(GOLDEN_DIR).join("auditor")));
+        let stored = std::fs::read_to_string(&path).unwrap();
+        assert!(!stored.contains("timestamp"));
+        assert!(!stored.contains("duration_ms"));
+
+        // A later run differing only in volatile fields matches
+        let rerun = report("high", "2026-02-02");
+        assert!(store
+            .compare("auditor", &digest, &rerun)
+            .unwrap()
+            .is_empty());
+        store
+            .apply(GoldenMode::Verify, "auditor", &digest, &rerun, 5)
+            .unwrap();
+
+        let error = store.compare("auditor", "0000", &rerun).unwrap_err();
+        assert!(matches!(error, NexusError::Agent(AgentError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_nested_change_has_precise_path() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        store
+            .record("auditor", "abc", &report("high", "t1"))
+            .unwrap();
+
+        let mut changed = report("critical", "t2");
+        changed["metadata"]["tool"] = json!("audit-v2");
+        changed["findings"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("line");
+        changed["findings"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"file": "new.rs"}));
+
+        let diff = store.compare("auditor", "abc", &changed).unwrap();
+        let paths: Vec<&str> = diff.entries.iter().map(DiffEntry::path).collect();
+        assert_eq!(
+            paths,
+            [
+                "$.findings[0].line",
+                "$.findings[1].severity",
+                "$.findings[2]",
+                "$.metadata.tool"
+            ]
+        );
+        assert_eq!(
+            diff.entries[1],
+            DiffEntry::Changed {
+                path: "$.findings[1].severity".to_string(),
+                before: json!("high"),
+                after: json!("critical"),
+            }
+        );
+
+        let error = store
+            .apply(GoldenMode::Verify, "auditor", "abc", &changed, 2)
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("in 4 place(s)"));
+        assert!(message.contains("- $.findings[0].line: 3"));
+        assert!(message.contains("~ $.findings[1].severity: \"high\" -> \"critical\""));
+        assert!(message.ends_with("; and 2 more"));
+    }
+
+    #[test]
+    fn test_volatile_field_patterns() {
+        let dir = TempDir::new().unwrap();
+        let store = GoldenStore::new(dir.path())
+            .with_volatile_fields(["$.metadata.tool", "findings[*].line", "**.timestamp"])
+            .unwrap();
+
+        let value = store.canonicalize(&report("high", "t1")).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "findings": [
+                    {"file": "lib.rs", "severity": "low"},
+                    {"file": "main.rs", "severity": "high"},
+                ],
+                "metadata": {"duration_ms": 42},
+            })
+        );
+
+        assert!(FieldPattern::parse("metadata..tool").is_err());
+        assert!(store.path_for("../escape", "abc").is_err());
+        assert!(store.path_for("auditor", "../abc").is_err());
+        assert!(store.path_for("plugin/scan", "abc").is_ok());
+    }
+
+    #[test]
+    fn test_large_fields_are_recorded_as_artifacts() {
+        let dir = TempDir::new().unwrap();
+        let artifacts = ArtifactStore::new(dir.path());
+        let store = store(&dir).with_artifacts(artifacts, 256);
+        let output = json!({"bytecode": "60".repeat(1_000), "status": "ok"});
+
+        let path = store.record("compiler", "abc", &output).unwrap();
+        let stored = std::fs::read_to_string(&path).unwrap();
+        assert!(stored.len() < 256);
+        assert!(stored.contains("\"type\":\"artifact\""));
+        assert!(store.compare("compiler", "abc", &output).unwrap().is_empty());
+
+        let changed = json!({"bytecode": "61".repeat(1_000), "status": "ok"});
+        let diff = store.compare("compiler", "abc", &changed).unwrap();
+        assert_eq!(diff.entries.len(), 1);
+        assert_eq!(diff.entries[0].path(), "$.bytecode");
+    }
+}
@@ -0,0 +1,340 @@
+//! Health aggregation for NEXUS
+//!
+//! Agents, plugins and subsystems each report their own health. A
+//! [`HealthAggregator`] runs every registered check concurrently, each under
+//! its own timeout so one hung component can't stall the report, and folds
+//! the results into a [`SystemHealth`] whose overall status is the worst
+//! status seen.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Time each check gets before its component is reported unhealthy
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Status of a component, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Working normally
+    Healthy,
+    /// Working, with reduced capability
+    Degraded,
+    /// Not working
+    Unhealthy,
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Unhealthy => "unhealthy",
+        })
+    }
+}
+
+/// Health of one component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    /// Component status
+    pub status: HealthStatus,
+    /// Why the component is not healthy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ComponentHealth {
+    /// A healthy component
+    pub const fn healthy() -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            reason: None,
+        }
+    }
+
+    /// A degraded component
+    pub fn degraded(reason: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Degraded,
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// An unhealthy component
+    pub fn unhealthy(reason: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Unhealthy,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
+/// Health of the whole system
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemHealth {
+    /// Worst component status, healthy when there are no components
+    pub overall: HealthStatus,
+    /// Health of each component, by name
+    pub components: HashMap<String, ComponentHealth>,
+}
+
+impl SystemHealth {
+    /// Fold component reports into a system report
+    pub fn from_components(
+        components: impl IntoIterator<Item = (String, ComponentHealth)>,
+    ) -> Self {
+        let components: HashMap<String, ComponentHealth> = components.into_iter().collect();
+        let overall = components
+            .values()
+            .map(|component| component.status)
+            .max()
+            .unwrap_or(HealthStatus::Healthy);
+        Self {
+            overall,
+            components,
+        }
+    }
+
+    /// Render as an aligned table, one component per line, sorted by name
+    pub fn render_table(&self) -> String {
+        let mut names: Vec<&String> = self.components.keys().collect();
+        names.sort();
+        let width = names
+            .iter()
+            .map(|name| name.len())
+            .chain(std::iter::once("COMPONENT".len()))
+            .max()
+            .unwrap_or(0);
+
+        let mut output = String::new();
+        let _ = writeln!(output, "{:width$}  {:9}  REASON", "COMPONENT", "STATUS");
+        for name in names {
+            let component = &self.components[name];
+            let line = format!(
+                "{:width$}  {:9}  {}",
+                name,
+                component.status.to_string(),
+                component.reason.as_deref().unwrap_or("")
+            );
+            let _ = writeln!(output, "{}", line.trim_end());
+        }
+        let _ = writeln!(output, "overall: {}", self.overall);
+        output
+    }
+}
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+type CheckFn = Arc<dyn Fn() -> BoxFuture<Vec<(String, ComponentHealth)>> + Send + Sync>;
+
+/// Runs health checks concurrently and combines their reports
+///
+/// A check must not block its thread: synchronous health checks belong on
+/// [`tokio::task::spawn_blocking`], otherwise the timeout can't interrupt them.
+#[derive(Clone)]
+pub struct HealthAggregator {
+    checks: Vec<(String, CheckFn)>,
+    timeout: Duration,
+}
+
+impl fmt::Debug for HealthAggregator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthAggregator")
+            .field(
+                "checks",
+                &self.checks.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl Default for HealthAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthAggregator {
+    /// Create an aggregator with no checks and the default timeout
+    pub fn new() -> Self {
+        Self {
+            checks: Vec::new(),
+            timeout: DEFAULT_CHECK_TIMEOUT,
+        }
+    }
+
+    /// Give each check `timeout` before reporting it unhealthy
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Add a check reporting a single component called `name`
+    pub fn component<F, Fut>(self, name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ComponentHealth> + Send + 'static,
+    {
+        let name = name.into();
+        let component = name.clone();
+        self.add(name, move || {
+            let report = check();
+            let component = component.clone();
+            async move { vec![(component, report.await)] }
+        })
+    }
+
+    /// Add a check reporting several components, e.g. one per plugin
+    ///
+    /// Each component is reported as `<name>:<component>`. If the check
+    /// fails to finish, `name` itself is reported unhealthy.
+    pub fn group<F, Fut>(self, name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<(String, ComponentHealth)>> + Send + 'static,
+    {
+        let name = name.into();
+        let prefix = name.clone();
+        self.add(name, move || {
+            let report = check();
+            let prefix = prefix.clone();
+            async move {
+                report
+                    .await
+                    .into_iter()
+                    .map(|(component, health)| (format!("{}:{}", prefix, component), health))
+                    .collect()
+            }
+        })
+    }
+
+    fn add<F, Fut>(mut self, name: String, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<(String, ComponentHealth)>> + Send + 'static,
+    {
+        self.checks
+            .push((name, Arc::new(move || Box::pin(check()) as BoxFuture<_>)));
+        self
+    }
+
+    /// Run every check and combine the reports
+    pub async fn check(&self) -> SystemHealth {
+        let timeout = self.timeout;
+        let running: Vec<_> = self
+            .checks
+            .iter()
+            .map(|(name, check)| {
+                let report = check();
+                (
+                    name.clone(),
+                    tokio::spawn(async move { tokio::time::timeout(timeout, report).await }),
+                )
+            })
+            .collect();
+
+        let mut components = Vec::new();
+        for (name, handle) in running {
+            match handle.await {
+                Ok(Ok(reports)) => components.extend(reports),
+                Ok(Err(_)) => components.push((
+                    name,
+                    ComponentHealth::unhealthy(format!(
+                        "health check timed out after {:?}",
+                        timeout
+                    )),
+                )),
+                Err(e) => components.push((
+                    name,
+                    ComponentHealth::unhealthy(format!("health check failed: {}", e)),
+                )),
+            }
+        }
+
+        SystemHealth::from_components(components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_overall_is_worst_component() {
+        let health = HealthAggregator::new()
+            .component("config", || async { ComponentHealth::healthy() })
+            .component("security", || async {
+                ComponentHealth::degraded("audit log disabled")
+            })
+            .group("plugin", || async {
+                vec![
+                    ("weather".to_string(), ComponentHealth::healthy()),
+                    (
+                        "trader".to_string(),
+                        ComponentHealth::unhealthy("rpc unreachable"),
+                    ),
+                ]
+            })
+            .check()
+            .await;
+
+        assert_eq!(health.overall, HealthStatus::Unhealthy);
+        assert_eq!(health.components.len(), 4);
+        assert_eq!(
+            health.components["security"].reason.as_deref(),
+            Some("audit log disabled")
+        );
+        assert_eq!(
+            health.components["plugin:trader"].status,
+            HealthStatus::Unhealthy
+        );
+
+        let table = health.render_table();
+        assert!(table.contains("security        degraded   audit log disabled\n"));
+        assert!(table.ends_with("overall: unhealthy\n"));
+
+        let json = serde_json::to_value(&health).unwrap();
+        assert_eq!(json["overall"], "unhealthy");
+        assert_eq!(json["components"]["config"]["status"], "healthy");
+        assert!(json["components"]["config"].get("reason").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hung_check_times_out_alone() {
+        let started = Instant::now();
+        let health = HealthAggregator::new()
+            .with_timeout(Duration::from_millis(50))
+            .component("hung", || async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                ComponentHealth::healthy()
+            })
+            .component("fine", || async { ComponentHealth::healthy() })
+            .group("broken", || async {
+                panic!("health check bug");
+            })
+            .check()
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(health.components["fine"].status, HealthStatus::Healthy);
+        let hung = &health.components["hung"];
+        assert_eq!(hung.status, HealthStatus::Unhealthy);
+        assert!(hung.reason.as_deref().unwrap().contains("timed out"));
+        assert_eq!(health.components["broken"].status, HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_no_components_is_healthy() {
+        let health = HealthAggregator::new().check().await;
+        assert_eq!(health.overall, HealthStatus::Healthy);
+        assert!(health.components.is_empty());
+    }
+}
@@ -0,0 +1,400 @@
+//! Streaming JSONL reader for NEXUS
+//!
+//! Reads newline-delimited JSON one record at a time in constant memory, so
+//! multi-gigabyte history and audit files can be imported without loading
+//! them whole. Lines longer than a cap are rejected without being buffered,
+//! malformed lines either abort the read or are skipped and reported, and
+//! files ending in `.gz` are decompressed transparently.
+
+use flate2::read::GzDecoder;
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::marker::PhantomData;
+use std::path::Path;
+use thiserror::Error;
+
+/// Default maximum size of a single line
+pub const DEFAULT_MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Number of skipped line numbers remembered in lenient mode
+const MAX_REPORTED_SKIPS: usize = 100;
+
+/// Errors produced while reading JSONL
+#[derive(Debug, Error)]
+pub enum JsonlError {
+    /// The underlying reader failed
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// A line exceeded the size cap
+    #[error("line {line} exceeds the {limit} byte limit")]
+    LineTooLong {
+        /// One-based line number
+        line: u64,
+        /// Configured limit in bytes
+        limit: usize,
+    },
+
+    /// A line was not valid JSON for the target type
+    #[error("line {line} is malformed: {source}")]
+    Malformed {
+        /// One-based line number
+        line: u64,
+        /// Parse error
+        source: serde_json::Error,
+    },
+}
+
+/// How malformed or oversized lines are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadMode {
+    /// Stop at the first bad line
+    #[default]
+    Strict,
+    /// Skip bad lines, counting them and recording their line numbers
+    Lenient,
+}
+
+/// Reading progress passed to progress callbacks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Lines read so far, including skipped ones
+    pub lines: u64,
+    /// Decompressed bytes read so far
+    pub bytes: u64,
+}
+
+type ProgressCallback = Box<dyn FnMut(Progress) + Send>;
+
+/// Iterator over the records of a JSONL stream
+pub struct JsonlReader<T> {
+    reader: Box<dyn BufRead + Send>,
+    mode: ReadMode,
+    max_line_bytes: usize,
+    line: Vec<u8>,
+    progress: Progress,
+    progress_every: u64,
+    on_progress: Option<ProgressCallback>,
+    skipped: u64,
+    skipped_lines: Vec<u64>,
+    done: bool,
+    _record: PhantomData<fn() -> T>,
+}
+
+impl<T> std::fmt::Debug for JsonlReader<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonlReader")
+            .field("mode", &self.mode)
+            .field("max_line_bytes", &self.max_line_bytes)
+            .field("progress", &self.progress)
+            .field("skipped", &self.skipped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> JsonlReader<T> {
+    /// Read records from any reader
+    pub fn new(reader: impl Read + Send + 'static) -> Self {
+        Self {
+            reader: Box::new(BufReader::new(reader)),
+            mode: ReadMode::Strict,
+            max_line_bytes: DEFAULT_MAX_LINE_BYTES,
+            line: Vec::new(),
+            progress: Progress { lines: 0, bytes: 0 },
+            progress_every: 0,
+            on_progress: None,
+            skipped: 0,
+            skipped_lines: Vec::new(),
+            done: false,
+            _record: PhantomData,
+        }
+    }
+
+    /// Open a file, decompressing it when the name ends in `.gz`
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        if path.extension().and_then(|ext| ext.to_str()) == Some("gz") {
+            Ok(Self::new(GzDecoder::new(file)))
+        } else {
+            Ok(Self::new(file))
+        }
+    }
+
+    /// Set how bad lines are handled
+    pub fn mode(mut self, mode: ReadMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the maximum size of a single line
+    pub fn max_line_bytes(mut self, limit: usize) -> Self {
+        self.max_line_bytes = limit;
+        self
+    }
+
+    /// Call `callback` every `every_lines` lines and once at the end of the stream
+    pub fn on_progress(
+        mut self,
+        every_lines: u64,
+        callback: impl FnMut(Progress) + Send + 'static,
+    ) -> Self {
+        self.progress_every = every_lines.max(1);
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Number of lines skipped in lenient mode
+    pub const fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Line numbers of the first skipped lines, up to a fixed number
+    pub fn skipped_lines(&self) -> &[u64] {
+        &self.skipped_lines
+    }
+
+    /// Progress so far
+    pub const fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// Read the next line into the buffer, returning whether it was oversized, or `None` at end of stream
+    ///
+    /// Bytes beyond the size cap are consumed but not kept, so an oversized
+    /// line never grows the buffer past the cap.
+    fn read_line(&mut self) -> io::Result<Option<bool>> {
+        self.line.clear();
+        let mut oversized = false;
+        let mut any = false;
+
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok(any.then_some(oversized));
+            }
+            any = true;
+
+            let (chunk, consumed, finished) = match available.iter().position(|&b| b == b'\n') {
+                Some(newline) => (&available[..newline], newline + 1, true),
+                None => (available, available.len(), false),
+            };
+
+            if !oversized {
+                if self.line.len() + chunk.len() > self.max_line_bytes {
+                    oversized = true;
+                    self.line.clear();
+                } else {
+                    self.line.extend_from_slice(chunk);
+                }
+            }
+
+            self.reader.consume(consumed);
+            self.progress.bytes += consumed as u64;
+            if finished {
+                return Ok(Some(oversized));
+            }
+        }
+    }
+
+    fn report_progress(&mut self, force: bool) {
+        if let Some(callback) = self.on_progress.as_mut() {
+            if force || self.progress.lines % self.progress_every == 0 {
+                callback(self.progress);
+            }
+        }
+    }
+
+    fn skip(&mut self, error: JsonlError) -> Option<JsonlError> {
+        match self.mode {
+            ReadMode::Strict => {
+                self.done = true;
+                Some(error)
+            }
+            ReadMode::Lenient => {
+                tracing::warn!("Skipping JSONL record: {}", error);
+                self.skipped += 1;
+                if self.skipped_lines.len() < MAX_REPORTED_SKIPS {
+                    self.skipped_lines.push(self.progress.lines);
+                }
+                None
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for JsonlReader<T> {
+    type Item = Result<T, JsonlError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let oversized = match self.read_line() {
+                Ok(Some(oversized)) => oversized,
+                Ok(None) => {
+                    self.done = true;
+                    self.report_progress(true);
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            };
+
+            self.progress.lines += 1;
+            self.report_progress(false);
+            let line = self.progress.lines;
+
+            if oversized {
+                let error = JsonlError::LineTooLong {
+                    line,
+                    limit: self.max_line_bytes,
+                };
+                if let Some(error) = self.skip(error) {
+                    return Some(Err(error));
+                }
+                continue;
+            }
+
+            if self.line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+
+            match serde_json::from_slice(&self.line) {
+                Ok(record) => return Some(Ok(record)),
+                Err(source) => {
+                    if let Some(error) = self.skip(JsonlError::Malformed { line, source }) {
+                        return Some(Err(error));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use serde::Deserialize;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        id: u64,
+    }
+
+    /// Reader that generates `count` records on the fly, never materializing the file
+    struct Synthetic {
+        next: u64,
+        count: u64,
+        pending: Vec<u8>,
+    }
+
+    impl Read for Synthetic {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() && self.next < self.count {
+                self.pending = format!("{{\"id\":{}}}\n", self.next).into_bytes();
+                self.next += 1;
+            }
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    const CORRUPTED: &str = "{\"id\":1}\n{\"id\":2}\n{\"id\": oops\n\n{\"id\":4}\n";
+
+    #[test]
+    fn test_large_stream_in_bounded_memory() {
+        let source = Synthetic {
+            next: 0,
+            count: 200_000,
+            pending: Vec::new(),
+        };
+        let mut reader = JsonlReader::<Record>::new(source).max_line_bytes(64);
+
+        let mut count = 0;
+        while let Some(record) = reader.next() {
+            assert_eq!(record.unwrap().id, count);
+            // The line buffer never grows beyond the cap
+            assert!(reader.line.capacity() <= 128);
+            count += 1;
+        }
+        assert_eq!(count, 200_000);
+    }
+
+    #[test]
+    fn test_corrupted_line_strict() {
+        let reader = JsonlReader::<Record>::new(io::Cursor::new(CORRUPTED));
+        let results: Vec<_> = reader.collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok() && results[1].is_ok());
+        assert!(matches!(
+            results[2],
+            Err(JsonlError::Malformed { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_corrupted_line_lenient() {
+        let mut reader =
+            JsonlReader::<Record>::new(io::Cursor::new(CORRUPTED)).mode(ReadMode::Lenient);
+        let ids: Vec<u64> = reader.by_ref().map(|r| r.unwrap().id).collect();
+
+        assert_eq!(ids, vec![1, 2, 4]);
+        assert_eq!(reader.skipped(), 1);
+        assert_eq!(reader.skipped_lines(), &[3]);
+    }
+
+    #[test]
+    fn test_line_size_cap() {
+        let input = format!(
+            "{{\"id\":1}}\n{{\"id\":2,\"pad\":\"{}\"}}\n{{\"id\":3}}\n",
+            "x".repeat(100)
+        );
+
+        let results: Vec<_> = JsonlReader::<Record>::new(io::Cursor::new(input.clone()))
+            .max_line_bytes(32)
+            .collect();
+        assert!(matches!(
+            results[1],
+            Err(JsonlError::LineTooLong { line: 2, limit: 32 })
+        ));
+
+        let ids: Vec<u64> = JsonlReader::<Record>::new(io::Cursor::new(input))
+            .max_line_bytes(32)
+            .mode(ReadMode::Lenient)
+            .map(|r| r.unwrap().id)
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_gzip_file_and_progress() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("history.jsonl.gz");
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        for id in 0..10 {
+            writeln!(encoder, "{{\"id\":{}}}", id).unwrap();
+        }
+        encoder.finish().unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let ids: Vec<u64> = JsonlReader::<Record>::open(&path)
+            .unwrap()
+            .on_progress(4, move |p| sink.lock().unwrap().push(p.lines))
+            .map(|r| r.unwrap().id)
+            .collect();
+
+        assert_eq!(ids, (0..10).collect::<Vec<_>>());
+        assert_eq!(*seen.lock().unwrap(), vec![4, 8, 10]);
+    }
+}
@@ -2,13 +2,47 @@
 //! 
 //! Provides the foundational traits and types for the NEXUS terminal platform.
 
+pub mod artifacts;
+pub mod bus;
+pub mod canonical;
+pub mod config;
+pub mod correlation;
+pub mod crash;
+pub mod error;
+pub mod events;
+pub mod golden;
+pub mod health;
+pub mod jsonl;
+pub mod lifecycle;
+pub mod logging;
+pub mod manifest;
+pub mod metrics;
+pub mod op_counters;
+pub mod output_limit;
+pub mod paths;
+pub mod plugin;
+pub mod sandbox;
+pub mod schema;
+pub mod signature;
+pub mod simulation;
+pub mod suggest;
+pub mod task;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod watch;
+#[cfg(feature = "web3")]
+pub mod web3;
+
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 // Basic security configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityConfig {
     pub encryption_enabled: bool,
     pub min_password_length: usize,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
 }
 
 impl Default for SecurityConfig {
@@ -16,6 +50,25 @@ impl Default for SecurityConfig {
         Self {
             encryption_enabled: true,
             min_password_length: 12,
+            rate_limit: RateLimitConfig::default(),
+        }
+    }
+}
+
+/// Request rate limit, per client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Requests allowed per window
+    pub max_requests: u32,
+    /// Window length in seconds
+    pub window_secs: u64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_requests: 100,
+            window_secs: 60,
         }
     }
 }
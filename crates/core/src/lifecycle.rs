@@ -0,0 +1,433 @@
+//! Ordered shutdown of long-lived components
+//!
+//! Long-lived components depend on each other: agents provided by a plugin
+//! must stop before the plugin is unloaded, and logs are flushed last. Each
+//! component implements [`LifecycleComponent`] and is registered in a
+//! [`Lifecycle`] after the components it depends on. [`Lifecycle::shutdown`]
+//! stops them in reverse dependency order, one phase at a time; components of
+//! the same phase stop concurrently.
+//!
+//! Every phase has its own deadline. A component still stopping at the
+//! deadline is dropped and reported as timed out, and the remaining phases
+//! run anyway, so shutdown never hangs. Every outcome is collected in a
+//! [`ShutdownReport`] instead of stopping at the first error.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use crate::task::TaskGroup;
+
+/// Future returned by [`LifecycleComponent::shutdown`]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A component stopped by a [`Lifecycle`]
+pub trait LifecycleComponent: Send + Sync {
+    /// Name used to declare dependencies and in the report
+    fn name(&self) -> &str;
+
+    /// Stop the component by `deadline`
+    ///
+    /// Called at most once. The future is dropped if it is still running at
+    /// the deadline.
+    fn shutdown(&self, deadline: Instant) -> BoxFuture<'_, anyhow::Result<()>>;
+}
+
+/// Errors produced while registering a component
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LifecycleError {
+    /// Another component has the same name
+    #[error("component '{0}' is already registered")]
+    Duplicate(String),
+
+    /// A dependency has to be registered before its dependents
+    #[error("component '{component}' depends on '{dependency}', which is not registered")]
+    UnknownDependency {
+        /// Component being registered
+        component: String,
+        /// Dependency that is missing
+        dependency: String,
+    },
+
+    /// Components can't join a lifecycle that has been shut down
+    #[error("cannot register '{0}' after shutdown")]
+    ShutDown(String),
+}
+
+/// How one component's shutdown ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// It stopped cleanly
+    Stopped,
+    /// It returned an error or panicked
+    Failed(String),
+    /// It missed its phase's deadline and was dropped
+    TimedOut,
+}
+
+/// Shutdown of one component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentShutdown {
+    /// Component name
+    pub name: String,
+    /// Phase it stopped in; phases run from the highest to 0
+    pub phase: usize,
+    /// How it ended
+    pub outcome: ShutdownOutcome,
+    /// Time it took
+    pub elapsed: Duration,
+}
+
+/// Outcome of [`Lifecycle::shutdown`], in the order components were stopped
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// One entry per component
+    pub components: Vec<ComponentShutdown>,
+}
+
+impl ShutdownReport {
+    /// Whether every component stopped cleanly
+    pub fn is_clean(&self) -> bool {
+        self.components
+            .iter()
+            .all(|component| component.outcome == ShutdownOutcome::Stopped)
+    }
+
+    /// How the component called `name` stopped
+    pub fn outcome(&self, name: &str) -> Option<&ShutdownOutcome> {
+        self.components
+            .iter()
+            .find(|component| component.name == name)
+            .map(|component| &component.outcome)
+    }
+}
+
+struct Registered {
+    component: Arc<dyn LifecycleComponent>,
+    phase: usize,
+}
+
+/// Long-lived components, stopped in reverse dependency order
+#[derive(Default)]
+pub struct Lifecycle {
+    components: Mutex<Vec<Registered>>,
+    shut_down: AtomicBool,
+}
+
+impl fmt::Debug for Lifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components = self.lock();
+        f.debug_struct("Lifecycle")
+            .field(
+                "components",
+                &components
+                    .iter()
+                    .map(|registered| (registered.component.name(), registered.phase))
+                    .collect::<Vec<_>>(),
+            )
+            .field("shut_down", &self.shut_down.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+impl Lifecycle {
+    /// Create a lifecycle without components
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `component`, to be stopped before everything in `depends_on`
+    ///
+    /// Dependencies must already be registered, which rules out cycles.
+    pub fn register(
+        &self,
+        component: Arc<dyn LifecycleComponent>,
+        depends_on: &[&str],
+    ) -> Result<(), LifecycleError> {
+        let name = component.name().to_string();
+        let mut components = self.lock();
+        if self.shut_down.load(Ordering::SeqCst) {
+            return Err(LifecycleError::ShutDown(name));
+        }
+        if components
+            .iter()
+            .any(|registered| registered.component.name() == name)
+        {
+            return Err(LifecycleError::Duplicate(name));
+        }
+        let mut phase = 0;
+        for dependency in depends_on {
+            let registered = components
+                .iter()
+                .find(|registered| registered.component.name() == *dependency)
+                .ok_or_else(|| LifecycleError::UnknownDependency {
+                    component: name.clone(),
+                    dependency: (*dependency).to_string(),
+                })?;
+            phase = phase.max(registered.phase + 1);
+        }
+        components.push(Registered { component, phase });
+        Ok(())
+    }
+
+    /// Stop every component, giving each phase up to `timeout`
+    ///
+    /// Only the first call stops anything; later and concurrent calls,
+    /// including ones made by a component while it stops, return an empty
+    /// report at once.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        let mut components = {
+            // Under the lock, so no component can register after the take
+            let mut components = self.lock();
+            if self.shut_down.swap(true, Ordering::SeqCst) {
+                return report;
+            }
+            std::mem::take(&mut *components)
+        };
+        // Stable, so a phase stops in registration order
+        components.sort_by_key(|registered| std::cmp::Reverse(registered.phase));
+        tracing::info!("Shutting down {} components", components.len());
+
+        for phase in components.chunk_by(|a, b| a.phase == b.phase) {
+            let started = Instant::now();
+            let deadline = started + timeout;
+            let running: Vec<_> = phase
+                .iter()
+                .map(|registered| {
+                    let component = Arc::clone(&registered.component);
+                    let name = component.name().to_string();
+                    let stopping = tokio::spawn(async move {
+                        let result = component.shutdown(deadline).await;
+                        (result, started.elapsed())
+                    });
+                    (name, registered.phase, stopping)
+                })
+                .collect();
+
+            for (name, phase, mut stopping) in running {
+                let (outcome, elapsed) =
+                    match tokio::time::timeout_at(deadline.into(), &mut stopping).await {
+                        Ok(Ok((Ok(()), elapsed))) => (ShutdownOutcome::Stopped, elapsed),
+                        Ok(Ok((Err(e), elapsed))) => {
+                            (ShutdownOutcome::Failed(format!("{:#}", e)), elapsed)
+                        },
+                        Ok(Err(e)) => (ShutdownOutcome::Failed(e.to_string()), started.elapsed()),
+                        Err(_) => {
+                            stopping.abort();
+                            (ShutdownOutcome::TimedOut, timeout)
+                        },
+                    };
+                match &outcome {
+                    ShutdownOutcome::Stopped => {},
+                    ShutdownOutcome::Failed(reason) => {
+                        tracing::error!("Component '{}' failed to shut down: {}", name, reason);
+                    },
+                    ShutdownOutcome::TimedOut => {
+                        tracing::warn!("Component '{}' did not stop in time, dropping it", name);
+                    },
+                }
+                report.components.push(ComponentShutdown {
+                    name,
+                    phase,
+                    outcome,
+                    elapsed,
+                });
+            }
+        }
+        report
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Registered>> {
+        self.components
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl LifecycleComponent for TaskGroup {
+    fn name(&self) -> &str {
+        Self::name(self)
+    }
+
+    fn shutdown(&self, deadline: Instant) -> BoxFuture<'_, anyhow::Result<()>> {
+        Box::pin(async move {
+            let report =
+                Self::shutdown(self, deadline.saturating_duration_since(Instant::now())).await;
+            if report.is_clean() {
+                return Ok(());
+            }
+            let panicked: Vec<&str> = report
+                .panicked
+                .iter()
+                .map(|task| task.name.as_str())
+                .collect();
+            Err(anyhow::anyhow!(
+                "tasks timed out: {:?}, panicked: {:?}",
+                report.timed_out,
+                panicked
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records when it is stopped into a log shared with the test
+    struct Mock {
+        name: &'static str,
+        delay: Duration,
+        fail: bool,
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl LifecycleComponent for Mock {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn shutdown(&self, _deadline: Instant) -> BoxFuture<'_, anyhow::Result<()>> {
+            Box::pin(async move {
+                tokio::time::sleep(self.delay).await;
+                self.log.lock().unwrap().push(self.name);
+                if self.fail {
+                    anyhow::bail!("flush failed");
+                }
+                Ok(())
+            })
+        }
+    }
+
+    fn mock(name: &'static str, log: &Arc<Mutex<Vec<&'static str>>>) -> Mock {
+        Mock {
+            name,
+            delay: Duration::ZERO,
+            fail: false,
+            log: Arc::clone(log),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_components_stop_in_reverse_dependency_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let lifecycle = Lifecycle::new();
+        lifecycle
+            .register(Arc::new(mock("flush", &log)), &[])
+            .unwrap();
+        lifecycle
+            .register(Arc::new(mock("plugins", &log)), &["flush"])
+            .unwrap();
+        lifecycle
+            .register(Arc::new(mock("agents", &log)), &["plugins"])
+            .unwrap();
+        lifecycle
+            .register(Arc::new(mock("scheduler", &log)), &["agents"])
+            .unwrap();
+        let tasks = TaskGroup::new("workers");
+        tasks.spawn("loop", |token| async move { token.cancelled().await });
+        lifecycle.register(Arc::new(tasks), &["plugins"]).unwrap();
+
+        assert_eq!(
+            lifecycle.register(Arc::new(mock("agents", &log)), &[]),
+            Err(LifecycleError::Duplicate("agents".to_string()))
+        );
+        assert!(matches!(
+            lifecycle.register(Arc::new(mock("serve", &log)), &["missing"]),
+            Err(LifecycleError::UnknownDependency { .. })
+        ));
+
+        let report = lifecycle.shutdown(Duration::from_secs(5)).await;
+        assert!(report.is_clean(), "{:?}", report);
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["scheduler", "agents", "plugins", "flush"]
+        );
+        let phases: Vec<_> = report
+            .components
+            .iter()
+            .map(|c| (c.name.as_str(), c.phase))
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                ("scheduler", 3),
+                ("agents", 2),
+                ("workers", 2),
+                ("plugins", 1),
+                ("flush", 0)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_component_times_out_without_stopping_others() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let lifecycle = Lifecycle::new();
+        lifecycle
+            .register(
+                Arc::new(Mock {
+                    fail: true,
+                    ..mock("flush", &log)
+                }),
+                &[],
+            )
+            .unwrap();
+        lifecycle
+            .register(
+                Arc::new(Mock {
+                    delay: Duration::from_secs(60),
+                    ..mock("agents", &log)
+                }),
+                &["flush"],
+            )
+            .unwrap();
+        lifecycle
+            .register(Arc::new(mock("metrics", &log)), &["flush"])
+            .unwrap();
+
+        let started = Instant::now();
+        let report = lifecycle.shutdown(Duration::from_millis(100)).await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(report.outcome("agents"), Some(&ShutdownOutcome::TimedOut));
+        assert_eq!(report.outcome("metrics"), Some(&ShutdownOutcome::Stopped));
+        assert_eq!(
+            report.outcome("flush"),
+            Some(&ShutdownOutcome::Failed("flush failed".to_string()))
+        );
+        assert!(!report.is_clean());
+        assert_eq!(*log.lock().unwrap(), ["metrics", "flush"]);
+    }
+
+    #[tokio::test]
+    async fn test_second_shutdown_is_a_no_op() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let lifecycle = Lifecycle::new();
+        lifecycle
+            .register(Arc::new(mock("plugins", &log)), &[])
+            .unwrap();
+
+        assert_eq!(
+            lifecycle
+                .shutdown(Duration::from_secs(1))
+                .await
+                .components
+                .len(),
+            1
+        );
+        assert_eq!(
+            lifecycle.shutdown(Duration::from_secs(1)).await,
+            ShutdownReport::default()
+        );
+        assert_eq!(*log.lock().unwrap(), ["plugins"]);
+        assert_eq!(
+            lifecycle.register(Arc::new(mock("late", &log)), &[]),
+            Err(LifecycleError::ShutDown("late".to_string()))
+        );
+    }
+}
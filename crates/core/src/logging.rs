@@ -0,0 +1,411 @@
+//! Logging initialization for NEXUS
+//!
+//! [`init`] installs the global `tracing` subscriber described by a
+//! [`LoggingConfig`]: a level plus per-module directives, a pretty, compact
+//! or JSON format, an optional rotating log file written from a background
+//! thread, and an optional audit file that receives a copy of every event
+//! tagged `security = true`. Every line that passes the level filter is also
+//! kept for crash reports through [`crate::crash::record_log_line`].
+//! Binaries call it once at startup and hold on to the returned
+//! [`LoggingGuard`] until they exit.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{Directive, EnvFilter};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Filter, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{Layer, Registry};
+
+use crate::config::{LogRotation, LoggingConfig};
+use crate::crash;
+use crate::error::{NexusError, Result};
+
+/// Field marking an event for the audit log
+pub const SECURITY_FIELD: &str = "security";
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Keeps the background log writers running
+///
+/// Dropping the guard flushes buffered lines and stops the writer threads,
+/// so it must live as long as logging is needed.
+#[must_use = "dropping the guard stops file logging"]
+#[derive(Debug, Default)]
+pub struct LoggingGuard {
+    _writers: Vec<WorkerGuard>,
+}
+
+/// Output formats for log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Pretty,
+    Compact,
+    Json,
+}
+
+impl LogFormat {
+    fn parse(format: &str) -> Result<Self> {
+        match format {
+            "pretty" => Ok(Self::Pretty),
+            "compact" => Ok(Self::Compact),
+            "json" => Ok(Self::Json),
+            other => Err(NexusError::Config(format!(
+                "unknown log format '{}', must be one of: pretty, compact, json",
+                other
+            ))),
+        }
+    }
+}
+
+/// Install the global subscriber described by `config`
+///
+/// Fails if the configuration is invalid, a log file can't be opened, or a
+/// global subscriber is already installed.
+pub fn init(config: &LoggingConfig) -> Result<LoggingGuard> {
+    let (subscriber, guard) = subscriber(config)?;
+    subscriber
+        .try_init()
+        .map_err(|e| NexusError::Config(format!("logging is already initialized: {}", e)))?;
+    Ok(guard)
+}
+
+/// Build the subscriber described by `config` without installing it
+pub fn subscriber(config: &LoggingConfig) -> Result<(impl Subscriber + Send + Sync, LoggingGuard)> {
+    let format = LogFormat::parse(&config.format)?;
+    let mut writers = Vec::new();
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+
+    layers.push(
+        format_layer(format, io::stderr, io::stderr().is_terminal())
+            .with_filter(level_filter(config)?)
+            .boxed(),
+    );
+
+    layers.push(
+        format_layer(LogFormat::Compact, || CrashLog, false)
+            .with_filter(level_filter(config)?)
+            .boxed(),
+    );
+
+    if config.log_to_file {
+        let path = config.log_file_path.as_deref().ok_or_else(|| {
+            NexusError::Config("log_to_file is set but log_file_path is missing".to_string())
+        })?;
+        let (writer, worker) = tracing_appender::non_blocking(log_file(config, path)?);
+        writers.push(worker);
+        layers.push(
+            format_layer(format, writer, false)
+                .with_filter(level_filter(config)?)
+                .boxed(),
+        );
+    }
+
+    if let (true, Some(path)) = (config.security_events, &config.audit_log_path) {
+        let (writer, worker) = tracing_appender::non_blocking(append(path)?);
+        writers.push(worker);
+        // Audit events are kept whatever the configured level
+        layers.push(
+            format_layer(LogFormat::Json, writer, false)
+                .with_filter(SecurityEvents)
+                .boxed(),
+        );
+    }
+
+    Ok((
+        Registry::default().with(layers),
+        LoggingGuard { _writers: writers },
+    ))
+}
+
+/// Passes the events whose [`SECURITY_FIELD`] is `true`
+struct SecurityEvents;
+
+impl<S> Filter<S> for SecurityEvents {
+    fn enabled(&self, metadata: &Metadata<'_>, _: &Context<'_, S>) -> bool {
+        metadata.is_event() && metadata.fields().field(SECURITY_FIELD).is_some()
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, _: &Context<'_, S>) -> bool {
+        let mut flag = SecurityFlag(false);
+        event.record(&mut flag);
+        flag.0
+    }
+}
+
+/// Records whether an event has `security = true`
+struct SecurityFlag(bool);
+
+impl Visit for SecurityFlag {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == SECURITY_FIELD {
+            self.0 = value;
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+}
+
+/// Writer handing each formatted line to the crash report buffer
+struct CrashLog;
+
+impl Write for CrashLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The fmt layer writes a whole event at once
+        crash::record_log_line(String::from_utf8_lossy(buf).trim_end());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Level filter from `level` and the per-module `directives`
+fn level_filter(config: &LoggingConfig) -> Result<EnvFilter> {
+    let level: Directive = config
+        .level
+        .parse()
+        .map_err(|e| NexusError::Config(format!("invalid log level '{}': {}", config.level, e)))?;
+    config.directives.iter().try_fold(
+        EnvFilter::default().add_directive(level),
+        |filter, directive| {
+            let directive = directive.parse::<Directive>().map_err(|e| {
+                NexusError::Config(format!("invalid log directive '{}': {}", directive, e))
+            })?;
+            Ok(filter.add_directive(directive))
+        },
+    )
+}
+
+fn format_layer<W>(format: LogFormat, writer: W, ansi: bool) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi);
+    match format {
+        LogFormat::Pretty => layer.pretty().boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
+
+/// Log file writer honoring the rotation policy
+fn log_file(config: &LoggingConfig, path: &Path) -> Result<Box<dyn Write + Send>> {
+    match config.rotation {
+        LogRotation::Never => Ok(Box::new(append(path)?)),
+        LogRotation::Size => Ok(Box::new(SizeRotatingFile::open(
+            path,
+            config.max_file_size,
+            config.max_files,
+        )?)),
+        LogRotation::Daily => {
+            let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+                return Err(NexusError::Config(format!(
+                    "log_file_path {} is not a file path",
+                    path.display()
+                )));
+            };
+            let appender = RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(name.to_string_lossy())
+                .max_log_files(config.max_files.max(1))
+                .build(dir)
+                .map_err(|e| NexusError::Config(format!("log file {}: {}", path.display(), e)))?;
+            Ok(Box::new(appender))
+        }
+    }
+}
+
+fn append(path: &Path) -> Result<File> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    Ok(OpenOptions::new().create(true).append(true).open(path)?)
+}
+
+/// Log file that moves aside once it reaches a size limit
+///
+/// `nexus.log` becomes `nexus.log.1`, the previous `nexus.log.1` becomes
+/// `nexus.log.2`, and so on; files past `max_files` are deleted.
+struct SizeRotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl SizeRotatingFile {
+    fn open(path: &Path, max_bytes: u64, max_files: usize) -> Result<Self> {
+        let file = append(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            written,
+            max_bytes,
+            max_files,
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            let _ = std::fs::remove_file(self.rotated(self.max_files));
+            for n in (1..self.max_files).rev() {
+                let from = self.rotated(n);
+                if from.exists() {
+                    std::fs::rename(&from, self.rotated(n + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated(1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for SizeRotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Each write is one log line, so lines are never split across files
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn file_config(dir: &TempDir, format: &str) -> LoggingConfig {
+        LoggingConfig {
+            format: format.to_string(),
+            log_to_file: true,
+            log_file_path: Some(dir.path().join("logs/nexus.log")),
+            rotation: LogRotation::Never,
+            ..LoggingConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_json_file_output() {
+        let dir = TempDir::new().unwrap();
+        let config = LoggingConfig {
+            level: "warn".to_string(),
+            directives: vec!["nexus_core::logging=debug".to_string()],
+            audit_log_path: Some(dir.path().join("audit.log")),
+            ..file_config(&dir, "json")
+        };
+
+        let (subscriber, guard) = subscriber(&config).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(plugin = "weather", "plugin loaded");
+            tracing::debug!(target: "elsewhere", "filtered out");
+            tracing::info!(security = true, agent = "trader", "access denied");
+            tracing::info!(target: "elsewhere", security = false, "routine check");
+        });
+        drop(guard);
+
+        let log = std::fs::read_to_string(dir.path().join("logs/nexus.log")).unwrap();
+        let lines: Vec<serde_json::Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["level"], "DEBUG");
+        assert_eq!(lines[0]["target"], "nexus_core::logging::tests");
+        assert_eq!(lines[0]["fields"]["message"], "plugin loaded");
+        assert_eq!(lines[0]["fields"]["plugin"], "weather");
+        assert!(lines[0]["timestamp"].is_string());
+
+        let audit = std::fs::read_to_string(dir.path().join("audit.log")).unwrap();
+        assert_eq!(audit.lines().count(), 1);
+        let audit: serde_json::Value = serde_json::from_str(audit.trim()).unwrap();
+        assert_eq!(audit["fields"]["agent"], "trader");
+    }
+
+    #[test]
+    fn test_size_rotation() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nexus.log");
+        let mut file = SizeRotatingFile::open(&path, 10, 2).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        drop(file);
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("nexus.log"), "fourth\n");
+        assert_eq!(read("nexus.log.1"), "third\n");
+        assert_eq!(read("nexus.log.2"), "second\n");
+        assert!(!dir.path().join("nexus.log.3").exists());
+    }
+
+    #[test]
+    fn test_invalid_settings_are_config_errors() {
+        let dir = TempDir::new().unwrap();
+        let config = LoggingConfig {
+            directives: vec!["nexus_core=loud".to_string()],
+            ..file_config(&dir, "pretty")
+        };
+        let error = subscriber(&config).err().unwrap();
+        assert!(
+            matches!(&error, NexusError::Config(message) if message.contains("nexus_core=loud"))
+        );
+
+        assert!(matches!(
+            subscriber(&file_config(&dir, "xml")).err(),
+            Some(NexusError::Config(message)) if message.contains("xml")
+        ));
+
+        let config = LoggingConfig {
+            log_file_path: None,
+            ..file_config(&dir, "compact")
+        };
+        assert!(matches!(
+            subscriber(&config).err(),
+            Some(NexusError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_second_init_fails() {
+        let config = LoggingConfig {
+            level: "error".to_string(),
+            ..LoggingConfig::default()
+        };
+        let _guard = init(&config).unwrap();
+        let error = init(&config).unwrap_err();
+        assert!(error.to_string().contains("already initialized"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Permission manifests for plugins
+//!
+//! A permission manifest is a stable, normalized JSON description of what a
+//! plugin is allowed to do, meant for review by people who don't read Rust.
+//! An exported manifest can later be compared against the loaded plugin to
+//! catch any permission that broadened since the review.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::plugin::{PluginMetadata, PluginPermissions};
+
+/// Current manifest schema version
+pub const MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+/// Signature state of a plugin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureStatus {
+    /// The plugin carries no signature
+    Unsigned,
+    /// The plugin carries a signature
+    Signed,
+}
+
+/// Allowlists granted to a single agent
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentGrants {
+    /// Filesystem paths the agent may access
+    pub paths: BTreeSet<String>,
+    /// Network hosts the agent may contact
+    pub hosts: BTreeSet<String>,
+    /// System commands the agent may run
+    pub commands: BTreeSet<String>,
+}
+
+/// Normalized description of a plugin's permissions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionManifest {
+    /// Manifest schema version
+    pub schema_version: u32,
+    /// Plugin name
+    pub plugin: String,
+    /// Plugin version
+    pub version: String,
+    /// Required NEXUS version
+    pub required_nexus_version: String,
+    /// Signature state
+    pub signature: SignatureStatus,
+    /// SHA-256 of the plugin library, hex encoded, when the file is known
+    pub content_hash: Option<String>,
+    /// Granted plugin-level capabilities, sorted
+    pub capabilities: BTreeSet<String>,
+    /// Per-agent allowlists, keyed by agent name
+    pub agents: BTreeMap<String, AgentGrants>,
+}
+
+impl PermissionManifest {
+    /// Build a manifest from plugin metadata
+    pub fn from_metadata(metadata: &PluginMetadata) -> Self {
+        Self {
+            schema_version: MANIFEST_SCHEMA_VERSION,
+            plugin: metadata.name.clone(),
+            version: metadata.version.clone(),
+            required_nexus_version: metadata.required_nexus_version.clone(),
+            signature: if metadata.signature.is_some() {
+                SignatureStatus::Signed
+            } else {
+                SignatureStatus::Unsigned
+            },
+            content_hash: None,
+            capabilities: capabilities(&metadata.permissions),
+            agents: BTreeMap::new(),
+        }
+    }
+
+    /// Record the hash of the plugin library at `path`
+    pub fn with_content_hash(mut self, path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read plugin library: {:?}", path))?;
+        let digest = ring::digest::digest(&ring::digest::SHA256, &bytes);
+        self.content_hash = Some(digest.as_ref().iter().fold(String::new(), |mut hex, b| {
+            let _ = write!(hex, "{:02x}", b);
+            hex
+        }));
+        Ok(self)
+    }
+
+    /// Add an agent and its allowlists
+    pub fn with_agent(mut self, name: impl Into<String>, grants: AgentGrants) -> Self {
+        self.agents.insert(name.into(), grants);
+        self
+    }
+
+    /// Render the manifest as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize permission manifest")
+    }
+
+    /// Parse a manifest, rejecting schema versions this build doesn't understand
+    pub fn from_json(json: &str) -> Result<Self> {
+        let manifest: Self =
+            serde_json::from_str(json).context("Failed to parse permission manifest")?;
+        if manifest.schema_version > MANIFEST_SCHEMA_VERSION {
+            return Err(anyhow::anyhow!(
+                "Permission manifest schema version {} is newer than supported version {}",
+                manifest.schema_version,
+                MANIFEST_SCHEMA_VERSION
+            ));
+        }
+        Ok(manifest)
+    }
+
+    /// Everything this manifest grants beyond `reviewed`
+    ///
+    /// Allowlists are compared as sets, so reordering is not a change, and
+    /// any entry missing from the reviewed manifest counts as a broadening.
+    /// Content hash and version changes are reported too, since the reviewed
+    /// code is no longer the code that runs.
+    pub fn broadenings_since(&self, reviewed: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        for capability in self.capabilities.difference(&reviewed.capabilities) {
+            changes.push(format!("capability added: {}", capability));
+        }
+        if self.signature == SignatureStatus::Unsigned
+            && reviewed.signature == SignatureStatus::Signed
+        {
+            changes.push("signature removed".to_string());
+        }
+        if self.version != reviewed.version {
+            changes.push(format!(
+                "version changed: {} -> {}",
+                reviewed.version, self.version
+            ));
+        }
+        if self.content_hash != reviewed.content_hash && reviewed.content_hash.is_some() {
+            changes.push("content hash changed".to_string());
+        }
+
+        for (agent, grants) in &self.agents {
+            let Some(previous) = reviewed.agents.get(agent) else {
+                changes.push(format!("agent added: {}", agent));
+                continue;
+            };
+            for (kind, current, before) in [
+                ("path", &grants.paths, &previous.paths),
+                ("host", &grants.hosts, &previous.hosts),
+                ("command", &grants.commands, &previous.commands),
+            ] {
+                for entry in current.difference(before) {
+                    changes.push(format!("agent '{}' {} added: {}", agent, kind, entry));
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+/// Names of the granted plugin-level permissions
+fn capabilities(permissions: &PluginPermissions) -> BTreeSet<String> {
+    [
+        ("filesystem_access", permissions.filesystem_access),
+        ("network_access", permissions.network_access),
+        ("system_commands", permissions.system_commands),
+        ("web3_access", permissions.web3_access),
+        ("plugin_access", permissions.plugin_access),
+        ("config_access", permissions.config_access),
+    ]
+    .into_iter()
+    .filter(|(_, granted)| *granted)
+    .map(|(name, _)| name.to_string())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(permissions: PluginPermissions) -> PluginMetadata {
+        PluginMetadata {
+            name: "weather".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Weather lookups".to_string(),
+            author: "NEXUS Team".to_string(),
+            required_nexus_version: "0.1.0".to_string(),
+            dependencies: Vec::new(),
+            signature: Some("sig".to_string()),
+            permissions,
+        }
+    }
+
+    fn grants(hosts: &[&str]) -> AgentGrants {
+        AgentGrants {
+            hosts: hosts.iter().map(ToString::to_string).collect(),
+            ..AgentGrants::default()
+        }
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let manifest = PermissionManifest::from_metadata(&metadata(PluginPermissions {
+            network_access: true,
+            ..PluginPermissions::default()
+        }))
+        .with_agent("forecast", grants(&["api.weather.example"]));
+
+        let parsed = PermissionManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, manifest);
+        assert_eq!(parsed.schema_version, MANIFEST_SCHEMA_VERSION);
+        assert!(parsed.capabilities.contains("network_access"));
+    }
+
+    #[test]
+    fn test_reordered_allowlist_is_equal() {
+        let reviewed = PermissionManifest::from_json(
+            &serde_json::json!({
+                "schema_version": 1,
+                "plugin": "weather",
+                "version": "1.0.0",
+                "required_nexus_version": "0.1.0",
+                "signature": "signed",
+                "content_hash": null,
+                "capabilities": [],
+                "agents": { "forecast": { "hosts": ["b.example", "a.example"] } }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let current = PermissionManifest::from_metadata(&metadata(PluginPermissions::default()))
+            .with_agent("forecast", grants(&["a.example", "b.example"]));
+        assert!(current.broadenings_since(&reviewed).is_empty());
+    }
+
+    #[test]
+    fn test_added_permission_is_pinpointed() {
+        let reviewed = PermissionManifest::from_metadata(&metadata(PluginPermissions::default()))
+            .with_agent("forecast", grants(&["api.weather.example"]));
+
+        let updated = PermissionManifest::from_metadata(&metadata(PluginPermissions {
+            system_commands: true,
+            ..PluginPermissions::default()
+        }))
+        .with_agent("forecast", grants(&["api.weather.example", "evil.example"]));
+
+        assert_eq!(
+            updated.broadenings_since(&reviewed),
+            vec![
+                "capability added: system_commands".to_string(),
+                "agent 'forecast' host added: evil.example".to_string(),
+            ]
+        );
+
+        // Narrowing is not a broadening
+        assert!(reviewed.broadenings_since(&updated).is_empty());
+    }
+
+    #[test]
+    fn test_newer_schema_rejected() {
+        let json = PermissionManifest::from_metadata(&metadata(PluginPermissions::default()))
+            .to_json()
+            .unwrap()
+            .replace("\"schema_version\": 1", "\"schema_version\": 99");
+
+        assert!(PermissionManifest::from_json(&json).is_err());
+    }
+}
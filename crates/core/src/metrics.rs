@@ -0,0 +1,377 @@
+//! Metrics for NEXUS
+//!
+//! A [`MetricsRegistry`] holds the process-wide counters for agent
+//! execution, rate limiting and auditing. Recording is a single relaxed
+//! atomic add, so the hot path never takes a lock. The registry renders
+//! itself in the Prometheus text exposition format, and [`serve_metrics`]
+//! exposes that on `/metrics` for scraping.
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+use crate::events::{EventError, EventSubscription, NexusEvent};
+
+/// Largest request head the metrics listener reads
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Time a scraper gets to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Monotonic counter
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    /// Add one
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Add `n`
+    pub fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Current value
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Severity label of an audit event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Routine event
+    Low,
+    /// Worth reviewing
+    Medium,
+    /// Needs attention
+    High,
+    /// Needs immediate attention
+    Critical,
+}
+
+impl Severity {
+    /// All severities, in label order
+    pub const ALL: [Self; 4] = [Self::Low, Self::Medium, Self::High, Self::Critical];
+
+    /// Label value used in the exposition format
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// Process-wide counters
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    /// Agent executions started
+    pub agent_executions: Counter,
+    /// Agent executions that returned an error
+    pub agent_failures: Counter,
+    /// Agent executions that ran out of time
+    pub agent_timeouts: Counter,
+    /// Requests rejected by the rate limiter
+    pub rate_limit_rejections: Counter,
+    audit_events: [Counter; 4],
+}
+
+impl MetricsRegistry {
+    /// Create a registry with every counter at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count an audit event
+    pub fn record_audit_event(&self, severity: Severity) {
+        self.audit_events[severity as usize].inc();
+    }
+
+    /// Audit events recorded at `severity`
+    pub fn audit_events(&self, severity: Severity) -> u64 {
+        self.audit_events[severity as usize].get()
+    }
+
+    /// Count what `event` reports, if it is an execution or a rejection
+    pub fn record_event(&self, event: &NexusEvent) {
+        match event {
+            NexusEvent::ExecutionStarted { .. } => self.agent_executions.inc(),
+            NexusEvent::ExecutionFailed { error_kind, .. } => {
+                self.agent_failures.inc();
+                if error_kind == "timeout" {
+                    self.agent_timeouts.inc();
+                }
+            },
+            NexusEvent::RateLimitRejected { .. } => self.rate_limit_rejections.inc(),
+            _ => {},
+        }
+    }
+
+    /// Render all counters in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        for (name, help, counter) in [
+            (
+                "nexus_agent_executions_total",
+                "Agent executions started.",
+                &self.agent_executions,
+            ),
+            (
+                "nexus_agent_failures_total",
+                "Agent executions that returned an error.",
+                &self.agent_failures,
+            ),
+            (
+                "nexus_agent_timeouts_total",
+                "Agent executions that ran out of time.",
+                &self.agent_timeouts,
+            ),
+            (
+                "nexus_rate_limit_rejections_total",
+                "Requests rejected by the rate limiter.",
+                &self.rate_limit_rejections,
+            ),
+        ] {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, counter.get());
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP nexus_audit_events_total Audit events recorded, by severity."
+        );
+        let _ = writeln!(out, "# TYPE nexus_audit_events_total counter");
+        for severity in Severity::ALL {
+            let _ = writeln!(
+                out,
+                "nexus_audit_events_total{{severity=\"{}\"}} {}",
+                severity.as_str(),
+                self.audit_events(severity)
+            );
+        }
+
+        out
+    }
+}
+
+/// Serve `registry` on `http://<addr>/metrics` until `token` is cancelled
+///
+/// Connections are answered one at a time, which is plenty for a scraper.
+/// Spawn this through a [`TaskGroup`](crate::task::TaskGroup).
+pub async fn serve_metrics(
+    registry: Arc<MetricsRegistry>,
+    addr: SocketAddr,
+    token: CancellationToken,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!(
+        "Serving metrics on http://{}/metrics",
+        listener.local_addr()?
+    );
+    serve(&registry, listener, &token).await
+}
+
+/// Count the events of `events` into `registry` until `token` is cancelled
+///
+/// Spawn this through a [`TaskGroup`](crate::task::TaskGroup). Events missed
+/// because the subscription fell behind are not counted.
+pub async fn follow_events(
+    registry: Arc<MetricsRegistry>,
+    mut events: EventSubscription,
+    token: CancellationToken,
+) {
+    loop {
+        let received = tokio::select! {
+            () = token.cancelled() => return,
+            received = events.recv() => received,
+        };
+        match received {
+            Ok(event) => registry.record_event(&event),
+            Err(EventError::Lagged(missed)) => {
+                tracing::warn!("Metrics missed {} events", missed);
+            },
+            Err(EventError::Closed) => return,
+        }
+    }
+}
+
+async fn serve(
+    registry: &MetricsRegistry,
+    listener: TcpListener,
+    token: &CancellationToken,
+) -> std::io::Result<()> {
+    loop {
+        let (stream, peer) = tokio::select! {
+            () = token.cancelled() => return Ok(()),
+            accepted = listener.accept() => accepted?,
+        };
+        if let Err(e) = respond(registry, stream).await {
+            tracing::debug!("Metrics request from {} failed: {}", peer, e);
+        }
+    }
+}
+
+async fn respond(registry: &MetricsRegistry, mut stream: TcpStream) -> std::io::Result<()> {
+    let head = tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "request timed out"))??;
+
+    let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+    let (status, content_type, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            registry.render_prometheus(),
+        ),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read up to the end of the request head
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_BYTES {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+
+    #[test]
+    fn test_exposition_format() {
+        let registry = MetricsRegistry::new();
+        registry.agent_executions.add(3);
+        registry.agent_failures.inc();
+        registry.record_audit_event(Severity::High);
+        registry.record_audit_event(Severity::High);
+
+        let text = registry.render_prometheus();
+        assert!(text.contains(
+            "# HELP nexus_agent_executions_total Agent executions started.\n\
+             # TYPE nexus_agent_executions_total counter\n\
+             nexus_agent_executions_total 3\n"
+        ));
+        assert!(text.contains("nexus_agent_failures_total 1\n"));
+        assert!(text.contains("nexus_agent_timeouts_total 0\n"));
+        assert!(text.contains("# TYPE nexus_audit_events_total counter\n"));
+        assert!(text.contains("nexus_audit_events_total{severity=\"high\"} 2\n"));
+        assert!(text.contains("nexus_audit_events_total{severity=\"critical\"} 0\n"));
+        assert!(text.ends_with('\n'));
+
+        // Every sample line follows its HELP and TYPE lines
+        let metric_names: Vec<&str> = text
+            .lines()
+            .filter(|line| line.starts_with("# TYPE"))
+            .filter_map(|line| line.split(' ').nth(2))
+            .collect();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(metric_names.contains(&name), "untyped sample: {}", line);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serves_metrics_endpoint() {
+        let registry = Arc::new(MetricsRegistry::new());
+        registry.rate_limit_rejections.inc();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let token = CancellationToken::new();
+
+        let server = tokio::spawn({
+            let registry = Arc::clone(&registry);
+            let token = token.clone();
+            async move { serve(&registry, listener, &token).await }
+        });
+
+        let fetch = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let response = fetch("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4\r\n"));
+        assert!(response.contains("nexus_rate_limit_rejections_total 1\n"));
+
+        assert!(fetch("/other").await.starts_with("HTTP/1.1 404"));
+
+        token.cancel();
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_counts_published_events() {
+        let registry = Arc::new(MetricsRegistry::new());
+        let bus = EventBus::default();
+        let token = CancellationToken::new();
+        let follower = tokio::spawn(follow_events(
+            Arc::clone(&registry),
+            bus.subscribe(),
+            token.clone(),
+        ));
+
+        let run_id = uuid::Uuid::new_v4();
+        bus.publish(NexusEvent::ExecutionStarted {
+            run_id,
+            agent: "echo".to_string(),
+            correlation_id: crate::correlation::CorrelationId::new(),
+        });
+        bus.publish(NexusEvent::ExecutionFailed {
+            run_id,
+            error_kind: "timeout".to_string(),
+        });
+        bus.publish(NexusEvent::RateLimitRejected {
+            key: "client".to_string(),
+        });
+        drop(bus);
+        follower.await.unwrap();
+
+        assert_eq!(registry.agent_executions.get(), 1);
+        assert_eq!(registry.agent_failures.get(), 1);
+        assert_eq!(registry.agent_timeouts.get(), 1);
+        assert_eq!(registry.rate_limit_rejections.get(), 1);
+    }
+}
@@ -0,0 +1,192 @@
+//! File and network operation counters for agent runs
+//!
+//! An [`OpCounters`] is shared by the helpers an agent does I/O through,
+//! such as [`SandboxedFs`](crate::sandbox::SandboxedFs). Each helper records
+//! an operation before performing it, which both counts it for the run's
+//! metrics and enforces the rate limits of [`AgentResourceLimits`]: an
+//! operation that would exceed a limit fails with
+//! [`AgentError::ResourceLimitExceeded`] and is not performed.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::AgentResourceLimits;
+use crate::error::AgentError;
+
+/// Operations performed so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    /// Reads, writes and directory listings
+    pub file_operations: u64,
+    /// HTTP requests
+    pub network_requests: u64,
+}
+
+/// Operations in the last `window`, capped at `limit`
+#[derive(Debug)]
+struct RateWindow {
+    limit: usize,
+    window: Duration,
+    unit: &'static str,
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateWindow {
+    fn new(limit: u32, window: Duration, unit: &'static str) -> Self {
+        Self {
+            limit: limit as usize,
+            window,
+            unit,
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Admit one operation at `now`, or fail if the window is full
+    ///
+    /// Only admitted operations are remembered, so memory stays bounded by the limit.
+    fn admit(&self, now: Instant, what: &str) -> Result<(), AgentError> {
+        let mut recent = self
+            .recent
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while recent
+            .front()
+            .is_some_and(|&at| now.saturating_duration_since(at) >= self.window)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= self.limit {
+            return Err(AgentError::ResourceLimitExceeded(format!(
+                "more than {} {} per {}",
+                self.limit, what, self.unit
+            )));
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+}
+
+/// Counts and rate-limits the I/O of one agent run
+#[derive(Debug, Default)]
+pub struct OpCounters {
+    file_operations: AtomicU64,
+    network_requests: AtomicU64,
+    file_limit: Option<RateWindow>,
+    network_limit: Option<RateWindow>,
+}
+
+impl OpCounters {
+    /// Counters without rate limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counters enforcing `max_file_ops_per_sec` and `max_network_requests_per_min`
+    ///
+    /// Both are rolling windows: the limit applies to any second or minute,
+    /// not to calendar ones.
+    pub fn with_limits(limits: &AgentResourceLimits) -> Self {
+        Self {
+            file_limit: Some(RateWindow::new(
+                limits.max_file_ops_per_sec,
+                Duration::from_secs(1),
+                "second",
+            )),
+            network_limit: Some(RateWindow::new(
+                limits.max_network_requests_per_min,
+                Duration::from_secs(60),
+                "minute",
+            )),
+            ..Self::default()
+        }
+    }
+
+    /// Record a file operation about to be performed
+    pub fn record_file_op(&self) -> Result<(), AgentError> {
+        self.record_file_op_at(Instant::now())
+    }
+
+    /// Record an HTTP request about to be sent
+    pub fn record_network_request(&self) -> Result<(), AgentError> {
+        self.record_network_request_at(Instant::now())
+    }
+
+    fn record_file_op_at(&self, now: Instant) -> Result<(), AgentError> {
+        if let Some(limit) = &self.file_limit {
+            limit.admit(now, "file operations")?;
+        }
+        self.file_operations.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn record_network_request_at(&self, now: Instant) -> Result<(), AgentError> {
+        if let Some(limit) = &self.network_limit {
+            limit.admit(now, "network requests")?;
+        }
+        self.network_requests.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Operations recorded so far; rejected ones are not included
+    pub fn snapshot(&self) -> OpCounts {
+        OpCounts {
+            file_operations: self.file_operations.load(Ordering::Relaxed),
+            network_requests: self.network_requests.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(file_ops_per_sec: u32, network_per_min: u32) -> AgentResourceLimits {
+        AgentResourceLimits {
+            max_file_ops_per_sec: file_ops_per_sec,
+            max_network_requests_per_min: network_per_min,
+            ..AgentResourceLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_limits_apply_to_rolling_windows() {
+        let counters = OpCounters::with_limits(&limits(2, 1));
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        counters.record_file_op_at(at(0)).unwrap();
+        counters.record_file_op_at(at(600)).unwrap();
+        let error = counters.record_file_op_at(at(900)).unwrap_err();
+        assert!(matches!(
+            &error,
+            AgentError::ResourceLimitExceeded(message)
+                if message == "more than 2 file operations per second"
+        ));
+        // The first operation has left the window, the second hasn't
+        counters.record_file_op_at(at(1_000)).unwrap();
+        assert!(counters.record_file_op_at(at(1_500)).is_err());
+
+        counters.record_network_request_at(at(0)).unwrap();
+        assert!(counters.record_network_request_at(at(59_999)).is_err());
+        counters.record_network_request_at(at(60_000)).unwrap();
+
+        assert_eq!(
+            counters.snapshot(),
+            OpCounts {
+                file_operations: 3,
+                network_requests: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unlimited_counters_only_count() {
+        let counters = OpCounters::new();
+        for _ in 0..1_000 {
+            counters.record_file_op().unwrap();
+        }
+        assert_eq!(counters.snapshot().file_operations, 1_000);
+    }
+}
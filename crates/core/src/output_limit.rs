@@ -0,0 +1,231 @@
+//! Size limits for agent output
+//!
+//! An agent's output is cloned through channels and written to history and
+//! audit logs, so an oversized result has to be stopped where it is
+//! produced. [`limit_output`] measures the serialized size of the output
+//! data and either rejects it or, with `truncate_output`, replaces the
+//! largest values with [`truncation markers`](truncation_marker) until it
+//! fits. Small fields keep their values and the overall shape is preserved.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::config::AgentResourceLimits;
+use crate::error::AgentError;
+
+/// Sizes recorded for one output, for execution metrics and run history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutputSize {
+    /// Serialized size of the data as produced by the agent
+    pub original_bytes: usize,
+    /// Serialized size after enforcement
+    pub output_bytes: usize,
+    /// Whether any value was replaced by a truncation marker
+    pub truncated: bool,
+}
+
+/// Enforce `max_output_bytes` on output data
+///
+/// Returns [`AgentError::ResourceLimitExceeded`] for oversized data unless
+/// `truncate_output` is set.
+pub fn limit_output(
+    data: &mut Value,
+    limits: &AgentResourceLimits,
+) -> Result<OutputSize, AgentError> {
+    let original_bytes = serialized_len(data);
+    let mut size = OutputSize {
+        original_bytes,
+        output_bytes: original_bytes,
+        truncated: false,
+    };
+    if original_bytes <= limits.max_output_bytes {
+        return Ok(size);
+    }
+    if !limits.truncate_output {
+        return Err(AgentError::ResourceLimitExceeded(format!(
+            "output is {} bytes, limit is {}",
+            original_bytes, limits.max_output_bytes
+        )));
+    }
+
+    shrink(data, limits.max_output_bytes);
+    size.output_bytes = serialized_len(data);
+    size.truncated = true;
+    Ok(size)
+}
+
+/// Enforce `max_metadata_bytes` on each metadata value
+///
+/// Oversized values are rejected, or cut at a character boundary when
+/// `truncate_output` is set.
+pub fn limit_metadata(
+    metadata: &mut HashMap<String, String>,
+    limits: &AgentResourceLimits,
+) -> Result<bool, AgentError> {
+    let mut truncated = false;
+    for (key, value) in metadata.iter_mut() {
+        if value.len() <= limits.max_metadata_bytes {
+            continue;
+        }
+        if !limits.truncate_output {
+            return Err(AgentError::ResourceLimitExceeded(format!(
+                "metadata '{}' is {} bytes, limit is {}",
+                key,
+                value.len(),
+                limits.max_metadata_bytes
+            )));
+        }
+        let mut end = limits.max_metadata_bytes;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        truncated = true;
+    }
+    Ok(truncated)
+}
+
+/// Marker standing in for a value that was too large to keep
+pub fn truncation_marker(value: &Value) -> Value {
+    let bytes = serde_json::to_vec(value).unwrap_or_default();
+    let digest = ring::digest::digest(&ring::digest::SHA256, &bytes);
+    let hex = digest.as_ref().iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{:02x}", b);
+        hex
+    });
+    json!({
+        "truncated": true,
+        "original_bytes": bytes.len(),
+        "digest": format!("sha256:{}", hex),
+    })
+}
+
+fn serialized_len(value: &Value) -> usize {
+    serde_json::to_vec(value).map_or(0, |bytes| bytes.len())
+}
+
+/// Replace the largest values under `value` until it fits in `budget` bytes
+fn shrink(value: &mut Value, budget: usize) {
+    let mut size = serialized_len(value);
+    if size <= budget {
+        return;
+    }
+
+    let mut children: Vec<&mut Value> = match value {
+        Value::Array(items) => items.iter_mut().collect(),
+        Value::Object(fields) => fields.values_mut().collect(),
+        _ => Vec::new(),
+    };
+    children.sort_by_cached_key(|child| std::cmp::Reverse(serialized_len(child)));
+    for child in children {
+        if size <= budget {
+            break;
+        }
+        let before = serialized_len(child);
+        let excess = size - budget;
+        if matches!(child, Value::Array(_) | Value::Object(_)) && before > excess {
+            shrink(child, before - excess);
+        }
+        if serialized_len(child) >= before {
+            let marker = truncation_marker(child);
+            if serialized_len(&marker) < before {
+                *child = marker;
+            }
+        }
+        size = size - before + serialized_len(child);
+    }
+
+    if serialized_len(value) > budget {
+        *value = truncation_marker(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(truncate_output: bool) -> AgentResourceLimits {
+        AgentResourceLimits {
+            truncate_output,
+            ..AgentResourceLimits::default()
+        }
+    }
+
+    fn large_output() -> Value {
+        json!({
+            "status": "done",
+            "rows": 3,
+            "report": "x".repeat(10 * 1024 * 1024),
+        })
+    }
+
+    #[test]
+    fn test_oversized_output_rejected_by_default() {
+        let mut data = large_output();
+        let error = limit_output(&mut data, &limits(false)).unwrap_err();
+        assert!(matches!(error, AgentError::ResourceLimitExceeded(_)));
+        assert_eq!(data, large_output());
+    }
+
+    #[test]
+    fn test_oversized_output_truncated_with_digest() {
+        let mut data = large_output();
+        let size = limit_output(&mut data, &limits(true)).unwrap();
+
+        assert!(size.truncated);
+        assert!(size.original_bytes > 10 * 1024 * 1024);
+        assert!(size.output_bytes <= AgentResourceLimits::default().max_output_bytes);
+        assert_eq!(data["status"], "done");
+        assert_eq!(data["rows"], 3);
+        assert_eq!(data["report"]["truncated"], true);
+        assert_eq!(
+            data["report"]["original_bytes"],
+            serialized_len(&large_output()["report"])
+        );
+        assert!(data["report"]["digest"]
+            .as_str()
+            .unwrap()
+            .starts_with("sha256:"));
+
+        // Small outputs pass through untouched
+        let mut small = json!({"ok": true});
+        assert_eq!(
+            limit_output(&mut small, &limits(true)).unwrap(),
+            OutputSize {
+                original_bytes: 11,
+                output_bytes: 11,
+                truncated: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_nested_values_shrink_in_place() {
+        let limits = AgentResourceLimits {
+            max_output_bytes: 200,
+            ..limits(true)
+        };
+        let mut data = json!({
+            "name": "scan",
+            "pages": [{"title": "a", "body": "y".repeat(500)}, {"title": "b", "body": "z"}],
+        });
+        limit_output(&mut data, &limits).unwrap();
+
+        assert_eq!(data["name"], "scan");
+        assert_eq!(data["pages"][0]["title"], "a");
+        assert_eq!(data["pages"][0]["body"]["truncated"], true);
+        assert_eq!(data["pages"][1]["body"], "z");
+    }
+
+    #[test]
+    fn test_metadata_cap() {
+        let mut metadata = HashMap::from([("note".to_string(), "é".repeat(40_000))]);
+        assert!(limit_metadata(&mut metadata, &limits(false)).is_err());
+
+        assert!(limit_metadata(&mut metadata, &limits(true)).unwrap());
+        let note = &metadata["note"];
+        assert!(note.len() <= AgentResourceLimits::default().max_metadata_bytes);
+        assert!(note.chars().all(|c| c == 'é'));
+    }
+}
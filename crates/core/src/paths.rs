@@ -0,0 +1,158 @@
+//! Path resolution for NEXUS
+//!
+//! Paths in configuration files are written for people: `~/nexus/data`,
+//! `./plugins`. [`PathResolver`] turns them into absolute paths. A leading
+//! `~` expands to the home directory, and relative paths are taken relative
+//! to a base directory (normally the directory of the config file that
+//! mentions them) rather than wherever `nexus` happened to be started.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Environment variable replacing the current directory as the base of the
+/// default configuration search paths
+pub const NEXUS_HOME_ENV: &str = "NEXUS_HOME";
+
+/// Turns configured paths into absolute ones
+#[derive(Debug, Clone)]
+pub struct PathResolver {
+    base_dir: PathBuf,
+    home: Option<PathBuf>,
+}
+
+impl PathResolver {
+    /// Resolve relative paths against `base_dir`
+    ///
+    /// A relative `base_dir` is itself taken relative to the current directory.
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        let base_dir = base_dir.into();
+        let base_dir = if base_dir.is_absolute() {
+            base_dir
+        } else {
+            std::env::current_dir()
+                .map(|cwd| normalize(&cwd.join(&base_dir)))
+                .unwrap_or(base_dir)
+        };
+        Self {
+            base_dir,
+            home: dirs::home_dir(),
+        }
+    }
+
+    /// Expand `~` to `home` instead of the user's home directory
+    pub fn with_home(mut self, home: Option<PathBuf>) -> Self {
+        self.home = home;
+        self
+    }
+
+    /// Directory relative paths are resolved against
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
+    /// Expand a leading `~` component; `~user` forms are left alone
+    pub fn expand_tilde(&self, path: &Path) -> PathBuf {
+        match (path.strip_prefix("~"), &self.home) {
+            (Ok(rest), Some(home)) if rest.as_os_str().is_empty() => home.clone(),
+            (Ok(rest), Some(home)) => home.join(rest),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Absolute form of `path`, canonicalized when it exists
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        let absolute = self.absolute(path);
+        absolute
+            .canonicalize()
+            .unwrap_or_else(|_| normalize(&absolute))
+    }
+
+    /// Canonical form of a path that must exist
+    pub fn resolve_existing(&self, path: &Path) -> std::io::Result<PathBuf> {
+        self.absolute(path).canonicalize()
+    }
+
+    fn absolute(&self, path: &Path) -> PathBuf {
+        let path = self.expand_tilde(path);
+        if path.is_absolute() {
+            path
+        } else {
+            self.base_dir.join(path)
+        }
+    }
+}
+
+/// Drop `.` components and fold `..` into their parent, without touching the disk
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tilde_expansion() {
+        let resolver = PathResolver::new("/base").with_home(Some(PathBuf::from("/home/ada")));
+
+        assert_eq!(
+            resolver.expand_tilde(Path::new("~/.config/nexus")),
+            PathBuf::from("/home/ada/.config/nexus")
+        );
+        assert_eq!(
+            resolver.expand_tilde(Path::new("~")),
+            PathBuf::from("/home/ada")
+        );
+        assert_eq!(
+            resolver.expand_tilde(Path::new("~ada/x")),
+            PathBuf::from("~ada/x")
+        );
+        assert_eq!(
+            resolver.expand_tilde(Path::new("data/~")),
+            PathBuf::from("data/~")
+        );
+
+        let homeless = resolver.with_home(None);
+        assert_eq!(
+            homeless.expand_tilde(Path::new("~/x")),
+            PathBuf::from("~/x")
+        );
+    }
+
+    #[test]
+    fn test_relative_paths_resolve_against_base() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("plugins")).unwrap();
+        let resolver = PathResolver::new(dir.path());
+        let canonical = dir.path().canonicalize().unwrap();
+
+        assert_eq!(
+            resolver.resolve(Path::new("./plugins")),
+            canonical.join("plugins")
+        );
+        assert_eq!(
+            resolver.resolve_existing(Path::new("plugins")).unwrap(),
+            canonical.join("plugins")
+        );
+
+        // Missing paths are still made absolute, just not canonical
+        assert_eq!(
+            resolver.resolve(Path::new("./data/../state")),
+            dir.path().join("state")
+        );
+        assert!(resolver.resolve_existing(Path::new("state")).is_err());
+        assert_eq!(resolver.resolve(Path::new("/abs")), PathBuf::from("/abs"));
+    }
+}
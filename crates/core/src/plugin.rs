@@ -4,14 +4,77 @@
 //! with sandboxing and permission controls.
 
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use libloading::Library;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::io::Write as _;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+use tempfile::TempDir;
 use tracing::{info, warn, error};
 
-use crate::agent::{Agent, AgentContext, AgentResult, AgentOutput};
+use crate::Agent;
 use crate::config::PluginConfig;
-use crate::security::SecurityManager;
+use crate::error::PluginError;
+use crate::events::{EventBus, NexusEvent};
+use crate::health::ComponentHealth;
+use crate::lifecycle::{BoxFuture, LifecycleComponent};
+use crate::signature::{sha256_hex, signature_path, PluginSignature, SignedManifest};
+use crate::manifest::{AgentGrants, PermissionManifest};
+use crate::SecurityManager;
+use crate::task::TaskGroup;
+use crate::watch::{AdaptiveWatcher, Prober, WatchAction, WatchConfig, WatchMode};
+
+/// Version of the plugin entry point ABI
+///
+/// Bumped whenever [`PluginDeclaration`] or the [`Plugin`] trait changes shape.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Version of nexus-core a plugin was built against
+pub const CORE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Name of the static every plugin library exports, see [`declare_plugin!`]
+pub const PLUGIN_DECLARATION_SYMBOL: &str = "nexus_plugin_declaration";
+
+/// Entry point exported by a plugin library
+///
+/// Plugins are Rust dynamic libraries built with the same compiler and
+/// nexus-core version as the host; the ABI and core versions are checked
+/// before `create` is called.
+#[repr(C)]
+pub struct PluginDeclaration {
+    /// Must equal [`PLUGIN_ABI_VERSION`]; kept first so it can be read before anything else
+    pub abi_version: u32,
+    /// nexus-core version the plugin was built against
+    pub core_version: &'static str,
+    /// Construct the plugin
+    pub create: fn() -> Box<dyn Plugin>,
+}
+
+/// Export a plugin's entry point
+///
+/// ```ignore
+/// nexus_core::declare_plugin!(MyPlugin::new);
+/// ```
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:path) => {
+        /// Entry point read by the NEXUS plugin loader
+        // SAFETY: the exported name is unique to NEXUS plugins and the static
+        // has the layout the loader reads, since `PluginDeclaration` is #[repr(C)]
+        #[allow(unsafe_code)]
+        #[export_name = "nexus_plugin_declaration"]
+        pub static NEXUS_PLUGIN_DECLARATION: $crate::plugin::PluginDeclaration =
+            $crate::plugin::PluginDeclaration {
+                abi_version: $crate::plugin::PLUGIN_ABI_VERSION,
+                core_version: $crate::plugin::CORE_VERSION,
+                create: || Box::new($constructor()),
+            };
+    };
+}
 
 /// Plugin trait that all plugins must implement
 pub trait Plugin: Send + Sync {
@@ -90,12 +153,153 @@ pub enum PluginHealth {
     Unhealthy(String),
 }
 
+impl std::fmt::Display for PluginHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Healthy => write!(f, "healthy"),
+            Self::Degraded(reason) => write!(f, "degraded: {}", reason),
+            Self::Unhealthy(reason) => write!(f, "unhealthy: {}", reason),
+        }
+    }
+}
+
+impl From<PluginHealth> for ComponentHealth {
+    fn from(health: PluginHealth) -> Self {
+        match health {
+            PluginHealth::Healthy => Self::healthy(),
+            PluginHealth::Degraded(reason) => Self::degraded(reason),
+            PluginHealth::Unhealthy(reason) => Self::unhealthy(reason),
+        }
+    }
+}
+
+/// Plugins added, replaced or removed by a rescan of the plugin directories
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginChanges {
+    /// Plugins loaded from new libraries
+    pub loaded: Vec<String>,
+    /// Plugins replaced by a new version of their library
+    pub reloaded: Vec<String>,
+    /// Plugins whose library was removed
+    pub unloaded: Vec<String>,
+    /// Libraries that failed to load; a plugin already loaded from one keeps running
+    pub failed: Vec<PathBuf>,
+}
+
+impl PluginChanges {
+    /// Whether the rescan found nothing to do
+    pub fn is_empty(&self) -> bool {
+        self.loaded.is_empty()
+            && self.reloaded.is_empty()
+            && self.unloaded.is_empty()
+            && self.failed.is_empty()
+    }
+}
+
+/// Health history and restarts of a plugin, as tracked by [`PluginManager::supervise`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginStatus {
+    /// Result of the last health check; `None` before the first one
+    pub health: Option<PluginHealth>,
+    /// Unhealthy results in a row
+    pub consecutive_failures: u32,
+    /// Restarts since the plugin was loaded
+    pub restarts: u32,
+    /// Restarts didn't help and the plugin was unloaded, agents and all
+    pub failed: bool,
+    /// Earliest time of the next restart
+    next_restart: Option<Instant>,
+}
+
+/// A health change or restart decision made by [`PluginManager::supervise`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupervisionEvent {
+    /// A health check returned something other than the previous one
+    HealthChanged {
+        /// Plugin name
+        plugin: String,
+        /// Previous result; plugins start out healthy
+        from: PluginHealth,
+        /// New result
+        to: PluginHealth,
+    },
+    /// The plugin was restarted after failing its health checks
+    Restarted {
+        /// Plugin name
+        plugin: String,
+        /// Restarts so far, this one included
+        attempt: u32,
+    },
+    /// A restart failed; it is tried again after the backoff
+    RestartFailed {
+        /// Plugin name
+        plugin: String,
+        /// Restarts so far, this one included
+        attempt: u32,
+        /// Why the restart failed
+        reason: String,
+    },
+    /// The restart limit was reached and the plugin was unloaded
+    GaveUp {
+        /// Plugin name
+        plugin: String,
+        /// Restarts tried
+        restarts: u32,
+    },
+}
+
+impl std::fmt::Display for SupervisionEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HealthChanged { plugin, from, to } => {
+                write!(f, "plugin '{}' went from {} to {}", plugin, from, to)
+            },
+            Self::Restarted { plugin, attempt } => {
+                write!(f, "restarted plugin '{}' (attempt {})", plugin, attempt)
+            },
+            Self::RestartFailed { plugin, attempt, reason } => {
+                write!(f, "restart {} of plugin '{}' failed: {}", attempt, plugin, reason)
+            },
+            Self::GaveUp { plugin, restarts } => {
+                write!(
+                    f,
+                    "gave up on plugin '{}' after {} restarts; it was unloaded",
+                    plugin, restarts
+                )
+            },
+        }
+    }
+}
+
+/// Callback run after a rescan changed the loaded plugins
+pub type PluginsChangedHook = Box<dyn Fn(&PluginManager, &PluginChanges) + Send + Sync>;
+
+/// Loads a library copy and constructs its plugin; the library must outlive the plugin
+type LoadFn = fn(&ShadowCopy) -> Result<(Box<dyn Plugin>, Option<Library>)>;
+
+/// Size and modification time of a plugin library, used to spot changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
 /// Plugin manager for loading and managing plugins
 pub struct PluginManager {
     plugins: HashMap<String, Box<dyn Plugin>>,
     config: PluginConfig,
     security_manager: Option<Arc<SecurityManager>>,
     plugin_agents: HashMap<String, Vec<String>>, // plugin_name -> agent_names
+    plugin_paths: HashMap<String, PathBuf>, // plugin_name -> library path
+    // Declared after `plugins` so a plugin is always dropped before the code it runs on
+    libraries: HashMap<String, Library>,
+    shadow_dir: OnceLock<TempDir>, // private directory of library copies, see `ShadowCopy`
+    known_files: BTreeMap<PathBuf, FileStamp>, // libraries seen by the last scan
+    skipped: BTreeMap<PathBuf, String>, // libraries the last load left out, and why
+    status: HashMap<String, PluginStatus>, // supervision state, including plugins given up on
+    change_hooks: Vec<PluginsChangedHook>,
+    load: LoadFn,
+    events: Option<EventBus>,
 }
 
 impl PluginManager {
@@ -106,14 +310,36 @@ impl PluginManager {
             config,
             security_manager,
             plugin_agents: HashMap::new(),
+            plugin_paths: HashMap::new(),
+            libraries: HashMap::new(),
+            shadow_dir: OnceLock::new(),
+            known_files: BTreeMap::new(),
+            skipped: BTreeMap::new(),
+            status: HashMap::new(),
+            change_hooks: Vec::new(),
+            load: |copy| load_dynamic_plugin(copy).map(|(plugin, library)| (plugin, Some(library))),
+            events: None,
+        }
+    }
+    
+    /// Publish plugin and agent events on `events`
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+    
+    fn publish(&self, event: NexusEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
         }
     }
     
     /// Load plugins from configured directories
     pub async fn load_plugins(&mut self) -> Result<()> {
         info!("Loading plugins from {} directories", self.config.plugin_dirs.len());
+        self.skipped.clear();
         
-        for plugin_dir in &self.config.plugin_dirs {
+        for plugin_dir in &self.config.plugin_dirs.clone() {
             if plugin_dir.exists() {
                 self.load_plugins_from_directory(plugin_dir).await
                     .with_context(|| format!("Failed to load plugins from {:?}", plugin_dir))?;
@@ -122,7 +348,15 @@ impl PluginManager {
             }
         }
         
+        self.known_files = scan_libraries(&self.config.plugin_dirs);
         info!("Loaded {} plugins", self.plugins.len());
+        
+        for name in &self.config.disabled {
+            let dependents = self.dependents_of(name);
+            if !dependents.is_empty() {
+                warn!("Disabled plugin '{}' is a dependency of: {}", name, dependents.join(", "));
+            }
+        }
         Ok(())
     }
     
@@ -137,12 +371,14 @@ impl PluginManager {
             
             if path.is_file() {
                 // Check for plugin libraries (e.g., .so, .dll, .dylib)
-                if let Some(extension) = path.extension() {
-                    if self.is_plugin_library(extension) {
-                        self.load_plugin_from_file(&path).await
-                            .unwrap_or_else(|e| {
-                                error!("Failed to load plugin from {:?}: {}", path, e);
-                            });
+                if is_plugin_library(&path) {
+                    if let Err(e) = self.load_plugin_from_file(&path).await {
+                        if matches!(e.downcast_ref::<PluginError>(), Some(PluginError::Disabled(_))) {
+                            info!("Skipping {:?}: {}", path, e);
+                        } else {
+                            error!("Failed to load plugin from {:?}: {}", path, e);
+                        }
+                        self.skipped.insert(path, format!("{:#}", e));
                     }
                 }
             }
@@ -151,38 +387,100 @@ impl PluginManager {
         Ok(())
     }
     
-    /// Check if file extension indicates a plugin library
-    fn is_plugin_library(&self, extension: &std::ffi::OsStr) -> bool {
-        matches!(
-            extension.to_str(),
-            Some("so") | Some("dll") | Some("dylib")
-        )
+    /// Load a plugin from a file, returning its name
+    async fn load_plugin_from_file(&mut self, path: &Path) -> Result<String> {
+        info!("Loading plugin from: {:?}", path);
+        
+        let (plugin, library) = self.prepare_plugin(path).await?;
+        Ok(self.insert_plugin(plugin, library, Some(path)))
     }
     
-    /// Load a plugin from a file
-    async fn load_plugin_from_file(&mut self, path: &Path) -> Result<()> {
-        info!("Loading plugin from: {:?}", path);
+    /// Register a plugin compiled into the binary, returning its name
+    ///
+    /// The plugin goes through the same disabled, permission and
+    /// initialization checks as one loaded from a library.
+    pub fn register_plugin(&mut self, plugin: Box<dyn Plugin>) -> Result<String> {
+        let plugin = self.admit_plugin(plugin)?;
+        Ok(self.insert_plugin(plugin, None, None))
+    }
+    
+    /// Verify, load and initialize the plugin at `path` without registering it
+    async fn prepare_plugin(&self, path: &Path) -> Result<(Box<dyn Plugin>, Option<Library>)> {
+        // Verify and load the same bytes: from here on only the copy is read,
+        // so replacing the file at `path` meanwhile can't slip unsigned code in
+        let copy = self.shadow_copy(path).map_err(|e| {
+            PluginError::LoadingFailed(format!("{}: {}", path.display(), e))
+        })?;
         
         // Security check: verify plugin signature if required
-        if self.config.security_policy.require_signed {
-            self.verify_plugin_signature(path).await
-                .context("Plugin signature verification failed")?;
-        }
+        let signed = if self.config.security_policy.require_signed {
+            self.verify_plugin_signature(&copy).await?
+        } else {
+            None
+        };
         
         // Load the plugin using the dynamic library loader
-        let plugin = self.load_dynamic_plugin(path).await
+        let (plugin, library) = (self.load)(&copy)
             .context("Failed to load dynamic plugin")?;
         
-        let metadata = plugin.metadata().clone();
+        // The signed identity must be the identity the library reports
+        if let Some(signed) = signed {
+            let metadata = plugin.metadata();
+            if signed.name != metadata.name || signed.version != metadata.version {
+                let error = PluginError::SignatureVerificationFailed(format!(
+                    "{}: signed as {} {} but loaded as {} {}",
+                    path.display(), signed.name, signed.version, metadata.name, metadata.version
+                ));
+                // The plugin's code lives in the library, so drop the plugin first
+                drop(plugin);
+                drop(library);
+                return Err(error.into());
+            }
+        }
+        
+        // Errors are flattened to strings because they may point into the
+        // library, which is unloaded when this returns
+        match self.admit_plugin(plugin) {
+            Ok(plugin) => Ok((plugin, library)),
+            Err(e) => {
+                drop(library);
+                Err(e)
+            }
+        }
+    }
+    
+    /// Check a constructed plugin against the configuration and initialize it
+    ///
+    /// On error the plugin has been dropped and the error holds no
+    /// references into its code.
+    fn admit_plugin(&self, plugin: Box<dyn Plugin>) -> Result<Box<dyn Plugin>> {
+        let name = plugin.metadata().name.clone();
+        if self.is_disabled(&name) {
+            return Err(PluginError::Disabled(name).into());
+        }
         
         // Validate plugin permissions
-        self.validate_plugin_permissions(&metadata)
-            .context("Plugin permission validation failed")?;
+        if let Err(e) = self.validate_plugin_permissions(plugin.metadata()) {
+            return Err(anyhow::anyhow!("Plugin permission validation failed: {:#}", e));
+        }
         
         // Initialize the plugin
         let mut plugin = plugin;
-        plugin.initialize(&self.config)
-            .context("Plugin initialization failed")?;
+        if let Err(e) = plugin.initialize(&self.config) {
+            return Err(anyhow::anyhow!("Plugin initialization failed: {:#}", e));
+        }
+        
+        Ok(plugin)
+    }
+    
+    /// Register an initialized plugin, returning its name
+    fn insert_plugin(
+        &mut self,
+        plugin: Box<dyn Plugin>,
+        library: Option<Library>,
+        path: Option<&Path>,
+    ) -> String {
+        let metadata = plugin.metadata().clone();
         
         // Register plugin agents
         let agents = plugin.agents();
@@ -191,33 +489,82 @@ impl PluginManager {
         info!("Plugin '{}' provides {} agents: {:?}", 
             metadata.name, agent_names.len(), agent_names);
         
+        for agent in &agent_names {
+            self.publish(NexusEvent::AgentRegistered { agent: agent.clone() });
+        }
         self.plugin_agents.insert(metadata.name.clone(), agent_names);
+        self.status.remove(&metadata.name);
+        if let Some(path) = path {
+            self.plugin_paths.insert(metadata.name.clone(), path.to_path_buf());
+        }
         self.plugins.insert(metadata.name.clone(), plugin);
+        if let Some(library) = library {
+            self.libraries.insert(metadata.name.clone(), library);
+        }
+        self.publish(NexusEvent::PluginLoaded {
+            plugin: metadata.name.clone(),
+            version: metadata.version,
+        });
         
-        Ok(())
+        metadata.name
     }
     
-    /// Verify plugin signature
-    async fn verify_plugin_signature(&self, _path: &Path) -> Result<()> {
-        // In a real implementation, this would:
-        // 1. Check the plugin's digital signature
-        // 2. Verify against trusted certificates
-        // 3. Ensure the plugin hasn't been tampered with
-        
-        // For now, we'll just log that signature verification would happen here
-        info!("Plugin signature verification (not implemented yet)");
-        Ok(())
+    /// Copy the library at `origin` into the manager's private directory
+    fn shadow_copy(&self, origin: &Path) -> std::io::Result<ShadowCopy> {
+        ShadowCopy::of(origin, self.shadow_dir()?)
     }
     
-    /// Load dynamic plugin (placeholder)
-    async fn load_dynamic_plugin(&self, _path: &Path) -> Result<Box<dyn Plugin>> {
-        // In a real implementation, this would use libloading to:
-        // 1. Load the dynamic library
-        // 2. Get the plugin factory function
-        // 3. Create the plugin instance
+    /// Directory holding the library copies, created on first use
+    ///
+    /// It has a random name and only the current user may enter it, so no
+    /// one else can replace a copy between its verification and its loading.
+    fn shadow_dir(&self) -> std::io::Result<&Path> {
+        if let Some(dir) = self.shadow_dir.get() {
+            return Ok(dir.path());
+        }
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("nexus-plugins-");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o700));
+        }
+        let dir = builder.tempdir()?;
+        Ok(self.shadow_dir.get_or_init(|| dir).path())
+    }
+    
+    /// Verify plugin signature
+    ///
+    /// The signature file and location are those of the original library,
+    /// the hash that of the bytes written to `library`'s copy.
+    ///
+    /// Returns the signed manifest, or `None` for an unsigned plugin that the
+    /// policy lets through because it lives under the user's home or
+    /// workspace directory.
+    async fn verify_plugin_signature(&self, library: &ShadowCopy) -> Result<Option<SignedManifest>> {
+        let policy = &self.config.security_policy;
+        let path = library.origin.as_path();
+        let failed = |reason: String| {
+            PluginError::SignatureVerificationFailed(format!("{}: {}", path.display(), reason))
+        };
         
-        // For now, return a mock plugin
-        Ok(Box::new(MockPlugin::new()))
+        if !signature_path(path).exists() {
+            let roots = local_roots(dirs::home_dir(), policy.workspace_dir.as_deref());
+            if policy.allow_local_unsigned && is_local_path(path, &roots) {
+                warn!("Loading unsigned local plugin: {:?}", path);
+                return Ok(None);
+            }
+            return Err(failed("plugin is not signed".to_string()).into());
+        }
+        
+        let signature = PluginSignature::read_for(path)
+            .map_err(|e| failed(e.to_string()))?;
+        let manifest = signature
+            .verify_digest(&library.sha256, &policy.trusted_publishers, &policy.trusted_keys)
+            .map_err(|e| failed(e.to_string()))?;
+        
+        info!("Verified signature of '{}' by publisher '{}'", manifest.name, manifest.publisher);
+        Ok(Some(manifest.clone()))
     }
     
     /// Validate plugin permissions
@@ -255,6 +602,32 @@ impl PluginManager {
         self.plugins.get(name)
     }
     
+    /// Whether the configuration disables the plugin called `name`
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.config.disabled.iter().any(|disabled| disabled == name)
+    }
+    
+    /// Loaded plugins that list `name` as a dependency, sorted
+    pub fn dependents_of(&self, name: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self.plugins.values()
+            .map(|plugin| plugin.metadata())
+            .filter(|metadata| metadata.dependencies.iter().any(|dependency| dependency == name))
+            .map(|metadata| metadata.name.clone())
+            .collect();
+        dependents.sort();
+        dependents
+    }
+    
+    /// Plugin libraries the last load found but did not load, with the reason
+    pub fn skipped_plugins(&self) -> &BTreeMap<PathBuf, String> {
+        &self.skipped
+    }
+    
+    /// Library a plugin was loaded from; `None` for plugins compiled in
+    pub fn plugin_path(&self, name: &str) -> Option<&Path> {
+        self.plugin_paths.get(name).map(PathBuf::as_path)
+    }
+    
     /// Get agents from all plugins
     pub fn get_all_agents(&self) -> Vec<Box<dyn Agent>> {
         let mut agents = Vec::new();
@@ -278,19 +651,170 @@ impl PluginManager {
                 .with_context(|| format!("Failed to shutdown plugin '{}'", name))?;
             
             self.plugin_agents.remove(name);
+            self.plugin_paths.remove(name);
+            self.status.remove(name);
+            // The plugin's code lives in the library, so drop the plugin first
+            drop(plugin);
+            self.libraries.remove(name);
+            self.publish(NexusEvent::PluginUnloaded { plugin: name.to_string() });
             info!("Unloaded plugin: {}", name);
         }
         
         Ok(())
     }
     
-    /// Reload a plugin
+    /// Replace a loaded plugin with the library at `path`
+    ///
+    /// The new library is verified, loaded and initialized before the old
+    /// plugin is touched. If any of that fails the old plugin keeps running
+    /// and the error is returned as [`PluginError::LoadingFailed`].
     pub async fn reload_plugin(&mut self, name: &str, path: &Path) -> Result<()> {
-        self.unload_plugin(name).await?;
-        self.load_plugin_from_file(path).await?;
+        let (plugin, library) = match self.prepare_plugin(path).await {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                let error = PluginError::LoadingFailed(format!("{}: {:#}", path.display(), e));
+                error!("Keeping plugin '{}' after failed reload: {}", name, error);
+                return Err(error.into());
+            }
+        };
+        
+        if let Some(mut old) = self.plugins.remove(name) {
+            if let Err(e) = old.shutdown() {
+                warn!("Failed to shutdown plugin '{}' during reload: {}", name, e);
+            }
+            self.plugin_agents.remove(name);
+            self.plugin_paths.remove(name);
+            drop(old);
+            self.libraries.remove(name);
+        }
+        
+        let name = self.insert_plugin(plugin, library, Some(path));
+        info!("Reloaded plugin '{}' from {:?}", name, path);
         Ok(())
     }
     
+    /// Register a callback run after [`sync_plugin_dirs`](Self::sync_plugin_dirs)
+    /// changed the loaded plugins, e.g. to re-register their agents
+    pub fn on_plugins_changed(
+        &mut self,
+        hook: impl Fn(&PluginManager, &PluginChanges) + Send + Sync + 'static,
+    ) {
+        self.change_hooks.push(Box::new(hook));
+    }
+    
+    /// Bring the loaded plugins in line with the plugin directories
+    ///
+    /// Libraries that appeared since the last scan are loaded, libraries that
+    /// changed are reloaded and plugins whose library was removed are
+    /// unloaded. Failures are logged and listed in the returned changes.
+    pub async fn sync_plugin_dirs(&mut self) -> PluginChanges {
+        let current = scan_libraries(&self.config.plugin_dirs);
+        let mut changes = PluginChanges::default();
+        
+        let removed: Vec<PathBuf> = self.known_files.keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned()
+            .collect();
+        for path in removed {
+            if let Some(name) = self.plugin_loaded_from(&path) {
+                match self.unload_plugin(&name).await {
+                    Ok(()) => changes.unloaded.push(name),
+                    Err(e) => error!("Failed to unload plugin '{}': {}", name, e),
+                }
+            }
+        }
+        
+        for (path, stamp) in &current {
+            if self.known_files.get(path) == Some(stamp) {
+                continue;
+            }
+            let result = match self.plugin_loaded_from(path) {
+                Some(name) => self.reload_plugin(&name, path).await.map(|()| changes.reloaded.push(name)),
+                None => self.load_plugin_from_file(path).await.map(|name| changes.loaded.push(name)),
+            };
+            if let Err(e) = result {
+                error!("Failed to load plugin from {:?}: {:#}", path, e);
+                changes.failed.push(path.clone());
+            }
+        }
+        
+        self.known_files = current;
+        if !changes.is_empty() {
+            for hook in &self.change_hooks {
+                hook(self, &changes);
+            }
+        }
+        changes
+    }
+    
+    /// Watch the plugin directories and apply changes as they settle
+    ///
+    /// Does nothing and returns `false` unless `enable_hot_reload` is set.
+    /// Bursts of changes are debounced according to `watch` and applied
+    /// with [`sync_plugin_dirs`](Self::sync_plugin_dirs). The watcher stops
+    /// when `tasks` shuts down.
+    pub async fn spawn_hot_reload(
+        manager: Arc<tokio::sync::Mutex<Self>>,
+        watch: WatchConfig,
+        tasks: &TaskGroup,
+    ) -> bool {
+        let dirs = {
+            let manager = manager.lock().await;
+            if !manager.config.enable_hot_reload {
+                return false;
+            }
+            manager.config.plugin_dirs.clone()
+        };
+        
+        // There is no native notification backend yet, so always poll
+        let watch = WatchConfig { mode: WatchMode::Poll, ..watch };
+        // Take the baseline fingerprint now, so changes made before the task runs are seen
+        let mut watcher = AdaptiveWatcher::new(watch, LibraryProber { dirs }, Instant::now());
+        tasks.spawn("plugin-hot-reload", move |token| async move {
+            loop {
+                tokio::select! {
+                    () = token.cancelled() => break,
+                    () = tokio::time::sleep_until(watcher.next_deadline().into()) => {}
+                }
+                if watcher.tick(Instant::now()) == WatchAction::Reload {
+                    manager.lock().await.sync_plugin_dirs().await;
+                }
+            }
+        });
+        true
+    }
+    
+    /// Name of the plugin loaded from `path`, if any
+    fn plugin_loaded_from(&self, path: &Path) -> Option<String> {
+        self.plugin_paths.iter()
+            .find(|(_, loaded)| loaded.as_path() == path)
+            .map(|(name, _)| name.clone())
+    }
+    
+    /// Build the permission manifest of a loaded plugin
+    pub fn permission_manifest(&self, name: &str) -> Result<PermissionManifest> {
+        let plugin = self.plugins.get(name)
+            .ok_or_else(|| anyhow::anyhow!("Plugin '{}' is not loaded", name))?;
+        
+        // Agents don't declare allowlists yet, so each is listed with empty grants
+        let mut manifest = PermissionManifest::from_metadata(plugin.metadata());
+        for agent in self.plugin_agents.get(name).into_iter().flatten() {
+            manifest = manifest.with_agent(agent.clone(), AgentGrants::default());
+        }
+        
+        match self.plugin_paths.get(name) {
+            Some(path) => manifest.with_content_hash(path),
+            None => Ok(manifest),
+        }
+    }
+    
+    /// Build the permission manifests of all loaded plugins, sorted by name
+    pub fn permission_manifests(&self) -> Result<Vec<PermissionManifest>> {
+        let mut names: Vec<&String> = self.plugins.keys().collect();
+        names.sort();
+        names.into_iter().map(|name| self.permission_manifest(name)).collect()
+    }
+    
     /// Check health of all plugins
     pub fn check_plugin_health(&self) -> HashMap<String, PluginHealth> {
         let mut health_status = HashMap::new();
@@ -305,32 +829,394 @@ impl PluginManager {
         health_status
     }
     
+    /// Health of every loaded plugin, as a [`HealthAggregator`] group
+    ///
+    /// Plugin health checks are synchronous, so they run on the blocking
+    /// pool where the aggregator's timeout can give up on them.
+    ///
+    /// [`HealthAggregator`]: crate::health::HealthAggregator
+    pub async fn plugin_health(manager: Arc<tokio::sync::Mutex<Self>>) -> Vec<(String, ComponentHealth)> {
+        let manager = manager.lock_owned().await;
+        match tokio::task::spawn_blocking(move || manager.check_plugin_health()).await {
+            Ok(health) => health.into_iter()
+                .map(|(name, health)| (name, health.into()))
+                .collect(),
+            Err(e) => vec![("*".to_string(), ComponentHealth::unhealthy(format!("health check failed: {}", e)))],
+        }
+    }
+    
+    /// Supervision state of every loaded plugin and of those given up on
+    pub fn plugin_status(&self) -> BTreeMap<String, PluginStatus> {
+        let mut status: BTreeMap<String, PluginStatus> = self.status.iter()
+            .map(|(name, status)| (name.clone(), status.clone()))
+            .collect();
+        for name in self.plugins.keys() {
+            status.entry(name.clone()).or_default();
+        }
+        status
+    }
+    
+    /// Check every plugin's health once and apply the restart policy
+    ///
+    /// A plugin that is unhealthy `failure_threshold` times in a row is
+    /// restarted: reloaded from its library, or shut down and initialized
+    /// again if it was compiled in. Later restarts wait out the backoff
+    /// first. When `max_restarts` restarts haven't helped, the plugin is
+    /// unloaded, which removes its agents, and stays listed as failed in
+    /// [`plugin_status`](Self::plugin_status) until it is loaded again.
+    ///
+    /// Every event is also logged as a security event for the audit log.
+    pub async fn supervise(&mut self, now: Instant) -> Vec<SupervisionEvent> {
+        let policy = self.config.supervision.clone();
+        let mut names: Vec<String> = self.plugins.keys().cloned().collect();
+        names.sort();
+        
+        let mut events = Vec::new();
+        for name in names {
+            let Some(plugin) = self.plugins.get(&name) else {
+                continue;
+            };
+            let health = plugin.health_check().unwrap_or_else(|e| {
+                PluginHealth::Unhealthy(format!("health check failed: {:#}", e))
+            });
+            
+            let status = self.status.entry(name.clone()).or_default();
+            let previous = status.health.replace(health.clone()).unwrap_or(PluginHealth::Healthy);
+            if previous != health {
+                events.push(SupervisionEvent::HealthChanged {
+                    plugin: name.clone(),
+                    from: previous,
+                    to: health.clone(),
+                });
+            }
+            if !matches!(health, PluginHealth::Unhealthy(_)) {
+                status.consecutive_failures = 0;
+                continue;
+            }
+            status.consecutive_failures += 1;
+            if status.consecutive_failures < policy.failure_threshold
+                || status.next_restart.is_some_and(|at| now < at)
+            {
+                continue;
+            }
+            
+            if status.restarts >= policy.max_restarts {
+                let restarts = status.restarts;
+                let mut status = status.clone();
+                if let Err(e) = self.unload_plugin(&name).await {
+                    error!("Failed to unload plugin '{}': {:#}", name, e);
+                }
+                status.failed = true;
+                self.status.insert(name.clone(), status);
+                events.push(SupervisionEvent::GaveUp { plugin: name, restarts });
+                continue;
+            }
+            
+            status.restarts += 1;
+            status.next_restart = Some(now + policy.backoff(status.restarts));
+            let attempt = status.restarts;
+            match self.restart_plugin(&name).await {
+                Ok(()) => {
+                    if let Some(status) = self.status.get_mut(&name) {
+                        status.consecutive_failures = 0;
+                    }
+                    events.push(SupervisionEvent::Restarted { plugin: name, attempt });
+                },
+                Err(e) => events.push(SupervisionEvent::RestartFailed {
+                    plugin: name,
+                    attempt,
+                    reason: format!("{:#}", e),
+                }),
+            }
+        }
+        
+        for event in &events {
+            match event {
+                SupervisionEvent::HealthChanged { to: PluginHealth::Healthy, .. }
+                | SupervisionEvent::Restarted { .. } => info!(security = true, "{}", event),
+                SupervisionEvent::GaveUp { .. } => error!(security = true, "{}", event),
+                _ => warn!(security = true, "{}", event),
+            }
+        }
+        events
+    }
+    
+    /// Reload a plugin from its library, or shut it down and initialize it again
+    ///
+    /// The plugin's supervision state survives the restart.
+    async fn restart_plugin(&mut self, name: &str) -> Result<()> {
+        let status = self.status.remove(name);
+        let result = match self.plugin_paths.get(name).cloned() {
+            Some(path) => self.reload_plugin(name, &path).await,
+            None => self.reinitialize_plugin(name),
+        };
+        if let Some(status) = status {
+            self.status.insert(name.to_string(), status);
+        }
+        result
+    }
+    
+    /// Shut down and initialize a compiled-in plugin, registering its agents again
+    fn reinitialize_plugin(&mut self, name: &str) -> Result<()> {
+        let plugin = self.plugins.get_mut(name)
+            .ok_or_else(|| anyhow::anyhow!("Plugin '{}' is not loaded", name))?;
+        plugin.shutdown()
+            .with_context(|| format!("Failed to shutdown plugin '{}'", name))?;
+        plugin.initialize(&self.config)
+            .with_context(|| format!("Failed to initialize plugin '{}'", name))?;
+        
+        let agent_names = plugin.agents().iter().map(|a| a.name().to_string()).collect();
+        self.plugin_agents.insert(name.to_string(), agent_names);
+        Ok(())
+    }
+    
+    /// Run [`supervise`](Self::supervise) every `health_check_interval_secs`
+    ///
+    /// Does nothing and returns `false` when the interval is 0. The
+    /// supervisor stops when `tasks` shuts down.
+    pub async fn spawn_supervisor(
+        manager: Arc<tokio::sync::Mutex<Self>>,
+        tasks: &TaskGroup,
+    ) -> bool {
+        let interval = manager.lock().await.config.supervision.health_check_interval_secs;
+        if interval == 0 {
+            return false;
+        }
+        
+        let interval = Duration::from_secs(interval);
+        tasks.spawn("plugin-supervisor", move |token| async move {
+            loop {
+                tokio::select! {
+                    () = token.cancelled() => break,
+                    () = tokio::time::sleep(interval) => {}
+                }
+                manager.lock().await.supervise(Instant::now()).await;
+            }
+        });
+        true
+    }
+    
     /// Shutdown all plugins
     pub async fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down {} plugins", self.plugins.len());
         
-        for (name, mut plugin) in self.plugins.drain() {
+        let plugins: Vec<_> = self.plugins.drain().collect();
+        for (name, mut plugin) in plugins {
             if let Err(e) = plugin.shutdown() {
                 error!("Failed to shutdown plugin '{}': {}", name, e);
             }
+            self.publish(NexusEvent::PluginUnloaded { plugin: name });
         }
         
         self.plugin_agents.clear();
+        self.plugin_paths.clear();
+        self.status.clear();
+        self.libraries.clear();
         Ok(())
     }
 }
 
+/// Unloads every plugin when a [`Lifecycle`](crate::lifecycle::Lifecycle) shuts down
+///
+/// The manager is shared as for [`PluginManager::spawn_supervisor`].
+impl LifecycleComponent for tokio::sync::Mutex<PluginManager> {
+    fn name(&self) -> &str {
+        "plugins"
+    }
+
+    fn shutdown(&self, _deadline: Instant) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { self.lock().await.shutdown().await })
+    }
+}
+
+/// Load a plugin library copy and construct the plugin it declares
+///
+/// The returned library must outlive the plugin.
+#[allow(unsafe_code)]
+fn load_dynamic_plugin(copy: &ShadowCopy) -> Result<(Box<dyn Plugin>, Library)> {
+    let failed = |reason: String| {
+        PluginError::LoadingFailed(format!("{}: {}", copy.origin.display(), reason))
+    };
+    
+    // SAFETY: loading runs the library's initializers. Only copies of files
+    // from the configured plugin directories reach this point, after the
+    // signature policy checked the copy's own bytes.
+    let library = unsafe { Library::new(&copy.path) }.map_err(|e| failed(e.to_string()))?;
+    
+    // SAFETY: the symbol is the address of a `PluginDeclaration` static
+    // exported by `declare_plugin!`; `abi_version` is its first field and
+    // is checked before any other field is trusted.
+    let declaration: &PluginDeclaration = unsafe {
+        let symbol = library
+            .get::<*const PluginDeclaration>(PLUGIN_DECLARATION_SYMBOL.as_bytes())
+            .map_err(|e| failed(format!("missing entry point '{}': {}", PLUGIN_DECLARATION_SYMBOL, e)))?;
+        &**symbol
+    };
+    
+    if declaration.abi_version != PLUGIN_ABI_VERSION {
+        return Err(failed(format!(
+            "plugin ABI version {} does not match host ABI version {}",
+            declaration.abi_version, PLUGIN_ABI_VERSION
+        )).into());
+    }
+    if declaration.core_version != CORE_VERSION {
+        return Err(failed(format!(
+            "plugin built against nexus-core {}, host is {}",
+            declaration.core_version, CORE_VERSION
+        )).into());
+    }
+    
+    let plugin = (declaration.create)();
+    Ok((plugin, library))
+}
+
+/// Whether `path` looks like a plugin library (.so, .dll or .dylib)
+pub fn is_plugin_library(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("so") | Some("dll") | Some("dylib")
+    )
+}
+
+/// Names of the plugin libraries in `dirs`, sorted, without loading any
+///
+/// A signed library is named by its signature manifest. An unsigned one is
+/// named by its file stem without a `lib` prefix, which is only a guess at
+/// the name it reports once loaded.
+pub fn library_names(dirs: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = scan_libraries(dirs)
+        .into_keys()
+        .filter_map(|path| {
+            if let Ok(signature) = PluginSignature::read_for(&path) {
+                return Some(signature.manifest.name);
+            }
+            let stem = path.file_stem()?.to_str()?;
+            Some(stem.strip_prefix("lib").unwrap_or(stem).to_string())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Plugin libraries in `dirs`; unreadable directories are skipped
+fn scan_libraries(dirs: &[PathBuf]) -> BTreeMap<PathBuf, FileStamp> {
+    let mut libraries = BTreeMap::new();
+    for entry in dirs.iter().filter_map(|dir| std::fs::read_dir(dir).ok()).flatten().flatten() {
+        let path = entry.path();
+        if !is_plugin_library(&path) {
+            continue;
+        }
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            if metadata.is_file() {
+                let stamp = FileStamp { len: metadata.len(), modified: metadata.modified().ok() };
+                libraries.insert(path, stamp);
+            }
+        }
+    }
+    libraries
+}
+
+/// Fingerprints the plugin libraries for the hot reload watcher
+struct LibraryProber {
+    dirs: Vec<PathBuf>,
+}
+
+impl Prober for LibraryProber {
+    fn probe(&mut self) -> std::io::Result<u64> {
+        let mut hasher = DefaultHasher::new();
+        scan_libraries(&self.dirs).hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
+
+/// Private copy of a plugin library, removed when dropped
+///
+/// The signature check uses the hash of the bytes written to the copy and
+/// the loader opens the copy, so what is verified is what runs. The copy
+/// also has a fresh path: the dynamic loader caches libraries by path, so
+/// reloading a replaced file from its own path would hand back the old code.
+struct ShadowCopy {
+    origin: PathBuf,
+    path: PathBuf,
+    sha256: String,
+}
+
+impl ShadowCopy {
+    /// Copy the library at `origin` to a new file in `dir`
+    ///
+    /// Fails rather than write through a file or symlink already at the
+    /// chosen path.
+    fn of(origin: &Path, dir: &Path) -> std::io::Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        
+        let bytes = std::fs::read(origin)?;
+        let path = dir.join(format!(
+            "{}-{}",
+            NEXT.fetch_add(1, Ordering::Relaxed),
+            origin.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        let copy = Self {
+            origin: origin.to_path_buf(),
+            path,
+            sha256: sha256_hex(&bytes),
+        };
+        file.write_all(&bytes)?;
+        Ok(copy)
+    }
+}
+
+impl Drop for ShadowCopy {
+    fn drop(&mut self) {
+        // Best effort: unix keeps the mapping alive, Windows refuses while it is mapped
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Directories unsigned plugins may be loaded from when the policy allows it
+///
+/// The workspace is the one the configuration came from, never the current
+/// directory: started from `/`, that would make every path local.
+fn local_roots(home: Option<PathBuf>, workspace: Option<&Path>) -> Vec<PathBuf> {
+    home.into_iter()
+        .chain(workspace.map(Path::to_path_buf))
+        .collect()
+}
+
+/// Whether `path` resolves to a location under one of `roots`
+///
+/// Both sides are canonicalized so `..` and symlinks can't escape a root.
+fn is_local_path(path: &Path, roots: &[PathBuf]) -> bool {
+    let Ok(path) = path.canonicalize() else {
+        return false;
+    };
+    roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| path.starts_with(root))
+}
+
 /// Mock plugin for testing
+#[cfg(test)]
 struct MockPlugin {
     metadata: PluginMetadata,
 }
 
+#[cfg(test)]
 impl MockPlugin {
     fn new() -> Self {
+        Self::with_version("1.0.0")
+    }
+    
+    fn with_version(version: &str) -> Self {
         Self {
             metadata: PluginMetadata {
                 name: "mock-plugin".to_string(),
-                version: "1.0.0".to_string(),
+                version: version.to_string(),
                 description: "Mock plugin for testing".to_string(),
                 author: "NEXUS Team".to_string(),
                 required_nexus_version: "0.1.0".to_string(),
@@ -342,6 +1228,7 @@ impl MockPlugin {
     }
 }
 
+#[cfg(test)]
 impl Plugin for MockPlugin {
     fn metadata(&self) -> &PluginMetadata {
         &self.metadata
@@ -368,7 +1255,8 @@ impl Plugin for MockPlugin {
 mod tests {
     use super::*;
     use crate::config::PluginSecurityPolicy;
-    use tempfile::TempDir;
+    use crate::testing::Fixture;
+    use std::sync::Mutex;
     
     fn test_plugin_config() -> PluginConfig {
         PluginConfig {
@@ -376,9 +1264,11 @@ mod tests {
             enable_hot_reload: false,
             security_policy: PluginSecurityPolicy::default(),
             max_load_time_secs: 30,
+            disabled: Vec::new(),
+            supervision: crate::config::PluginSupervisionConfig::default(),
         }
     }
-    
+
     #[tokio::test]
     async fn test_plugin_manager_creation() {
         let config = test_plugin_config();
@@ -389,13 +1279,8 @@ mod tests {
     
     #[tokio::test]
     async fn test_load_plugins_empty_dir() {
-        let temp_dir = TempDir::new().unwrap();
-        let config = PluginConfig {
-            plugin_dirs: vec![temp_dir.path().to_path_buf()],
-            enable_hot_reload: false,
-            security_policy: PluginSecurityPolicy::default(),
-            max_load_time_secs: 30,
-        };
+        let fixture = Fixture::new().unwrap();
+        let config = fixture.config().unwrap().plugin;
         
         let mut manager = PluginManager::new(config, None);
         let result = manager.load_plugins().await;
@@ -416,6 +1301,558 @@ mod tests {
         assert!(!permissions.config_access);
     }
     
+    #[test]
+    fn test_permission_manifest_of_loaded_plugin() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"plugin bytes".to_vec())
+            .build()
+            .unwrap();
+        
+        let mut manager = PluginManager::new(fixture.config().unwrap().plugin, None);
+        manager.insert_plugin(Box::new(MockPlugin::new()), None, Some(&fixture.plugin_dir().join("mock.so")));
+        
+        let manifest = manager.permission_manifest("mock-plugin").unwrap();
+        assert_eq!(manifest.version, "1.0.0");
+        assert!(manifest.capabilities.is_empty());
+        assert_eq!(manifest.content_hash.unwrap().len(), 64);
+        assert_eq!(manager.permission_manifests().unwrap().len(), 1);
+        assert!(manager.permission_manifest("missing").is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_invalid_library_is_rejected() {
+        let fixture = Fixture::builder()
+            .plugin_file("broken.so", b"not a shared library".to_vec())
+            .build()
+            .unwrap();
+        
+        let dir = TempDir::new().unwrap();
+        let copy = ShadowCopy::of(&fixture.plugin_dir().join("broken.so"), dir.path()).unwrap();
+        let error = load_dynamic_plugin(&copy)
+            .err()
+            .unwrap();
+        
+        let error = error.downcast_ref::<PluginError>().unwrap();
+        assert!(matches!(error, PluginError::LoadingFailed(reason) if reason.contains("broken.so")));
+    }
+    
+    fn signing_key() -> (ring::signature::Ed25519KeyPair, String) {
+        use base64::Engine as _;
+        use ring::signature::KeyPair as _;
+        
+        let pkcs8 = ring::signature::Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public = base64::engine::general_purpose::STANDARD.encode(key_pair.public_key().as_ref());
+        (key_pair, public)
+    }
+    
+    #[tokio::test]
+    async fn test_signature_policy() {
+        let fixture = Fixture::builder()
+            .plugin_file("signed.so", b"signed bytes".to_vec())
+            .plugin_file("unsigned.so", b"unsigned bytes".to_vec())
+            .build()
+            .unwrap();
+        let (key_pair, public) = signing_key();
+        let signed = fixture.plugin_dir().join("signed.so");
+        let manifest = SignedManifest::for_library(&signed, "mock-plugin", "1.0.0", "acme").unwrap();
+        let signature = PluginSignature::sign(manifest, &key_pair).unwrap();
+        std::fs::write(signature_path(&signed), signature.to_json().unwrap()).unwrap();
+        
+        let mut config = fixture.config().unwrap().plugin;
+        config.security_policy.trusted_keys.insert("acme".to_string(), public);
+        
+        // Known key, but the publisher isn't on the trusted list
+        let manager = PluginManager::new(config.clone(), None);
+        let error = manager.verify_plugin_signature(&manager.shadow_copy(&signed).unwrap()).await.unwrap_err();
+        assert!(error.to_string().contains("not trusted"));
+        
+        config.security_policy.trusted_publishers.push("acme".to_string());
+        let manager = PluginManager::new(config.clone(), None);
+        let verified = manager.verify_plugin_signature(&manager.shadow_copy(&signed).unwrap()).await.unwrap().unwrap();
+        assert_eq!(verified.name, "mock-plugin");
+        
+        std::fs::write(&signed, b"tampered bytes").unwrap();
+        let error = manager.verify_plugin_signature(&manager.shadow_copy(&signed).unwrap()).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PluginError>(),
+            Some(PluginError::SignatureVerificationFailed(_))
+        ));
+        
+        // The fixture lives in the temp dir, outside home; it is local only
+        // while it is the workspace the config came from
+        config.security_policy.allow_local_unsigned = true;
+        assert_eq!(
+            config.security_policy.workspace_dir.as_deref(),
+            Some(fixture.root().canonicalize().unwrap().as_path())
+        );
+        let manager = PluginManager::new(config.clone(), None);
+        let unsigned = fixture.plugin_dir().join("unsigned.so");
+        let copy = manager.shadow_copy(&unsigned).unwrap();
+        assert!(manager.verify_plugin_signature(&copy).await.unwrap().is_none());
+        
+        config.security_policy.workspace_dir = None;
+        let manager = PluginManager::new(config, None);
+        let copy = manager.shadow_copy(&unsigned).unwrap();
+        assert!(manager.verify_plugin_signature(&copy).await.is_err());
+    }
+    
+    #[test]
+    fn test_current_directory_is_not_local() {
+        // A plugin below the current directory, as every path is when nexus runs from `/`
+        let cwd = std::env::current_dir().unwrap();
+        let below_cwd = tempfile::tempdir_in(&cwd).unwrap();
+        let plugin = below_cwd.path().join("unsigned.so");
+        std::fs::write(&plugin, b"unsigned bytes").unwrap();
+        let workspace = Fixture::new().unwrap();
+        
+        assert!(!is_local_path(&plugin, &local_roots(None, Some(workspace.root()))));
+        assert!(!is_local_path(&plugin, &local_roots(None, None)));
+        assert!(is_local_path(&plugin, &local_roots(None, Some(below_cwd.path()))));
+    }
+    
+    #[tokio::test]
+    async fn test_library_replaced_after_verification_is_not_loaded() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .build()
+            .unwrap();
+        let (key_pair, public) = signing_key();
+        let library = fixture.plugin_dir().join("mock.so");
+        let manifest = SignedManifest::for_library(&library, "mock-plugin", "1.0.0", "acme").unwrap();
+        let signature = PluginSignature::sign(manifest, &key_pair).unwrap();
+        std::fs::write(signature_path(&library), signature.to_json().unwrap()).unwrap();
+        
+        let mut config = fixture.config().unwrap().plugin;
+        config.security_policy.trusted_keys.insert("acme".to_string(), public);
+        config.security_policy.trusted_publishers.push("acme".to_string());
+        let mut manager = PluginManager::new(config, None);
+        // The original is swapped once verification has passed, as an attacker racing the loader would
+        manager.load = |copy| {
+            std::fs::write(&copy.origin, b"6.6.6-unsigned").unwrap();
+            mock_load(copy)
+        };
+        
+        let (plugin, _) = manager.prepare_plugin(&library).await.unwrap();
+        assert_eq!(plugin.metadata().version, "1.0.0");
+        
+        // The replacement itself fails verification
+        manager.load = mock_load;
+        let error = manager.prepare_plugin(&library).await.err().unwrap();
+        assert!(matches!(
+            error.downcast_ref::<PluginError>(),
+            Some(PluginError::SignatureVerificationFailed(_))
+        ));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_shadow_copies_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .build()
+            .unwrap();
+        let manager = PluginManager::new(fixture.config().unwrap().plugin, None);
+        let copy = manager.shadow_copy(&fixture.plugin_dir().join("mock.so")).unwrap();
+        
+        let dir = copy.path.parent().unwrap();
+        assert_ne!(dir, std::env::temp_dir());
+        assert_eq!(std::fs::metadata(dir).unwrap().permissions().mode() & 0o777, 0o700);
+        assert_eq!(copy.sha256, sha256_hex(b"1.0.0"));
+        assert_eq!(std::fs::read(&copy.path).unwrap(), b"1.0.0");
+        
+        // Every manager gets its own directory
+        let other = PluginManager::new(fixture.config().unwrap().plugin, None);
+        assert_ne!(other.shadow_dir().unwrap(), dir);
+    }
+    
+    #[test]
+    fn test_library_names_load_nothing() {
+        let fixture = Fixture::builder()
+            .plugin_file("libweather.so", b"not a library".to_vec())
+            .plugin_file("forecast.dylib", b"not a library".to_vec())
+            .plugin_file("signed.so", b"signed bytes".to_vec())
+            .plugin_file("notes.txt", b"not a plugin".to_vec())
+            .build()
+            .unwrap();
+        let (key_pair, _) = signing_key();
+        let signed = fixture.plugin_dir().join("signed.so");
+        let manifest = SignedManifest::for_library(&signed, "radar", "1.0.0", "acme").unwrap();
+        let signature = PluginSignature::sign(manifest, &key_pair).unwrap();
+        std::fs::write(signature_path(&signed), signature.to_json().unwrap()).unwrap();
+        
+        assert_eq!(
+            library_names(&[fixture.plugin_dir(), fixture.root().join("missing")]),
+            ["forecast", "radar", "weather"]
+        );
+    }
+    
+    #[test]
+    fn test_local_path_detection() {
+        let fixture = Fixture::new().unwrap();
+        let root = fixture.plugin_dir();
+        std::fs::write(root.join("local.so"), b"bytes").unwrap();
+        
+        assert!(is_local_path(&root.join("local.so"), &[root.clone()]));
+        assert!(!is_local_path(&root.join("../nexus.toml"), &[root.clone()]));
+        assert!(!is_local_path(&root.join("missing.so"), &[root]));
+    }
+    
+    /// Stands in for the dynamic loader: the "library" holds the plugin version
+    fn mock_load(copy: &ShadowCopy) -> Result<(Box<dyn Plugin>, Option<Library>)> {
+        let version = std::fs::read_to_string(&copy.path)?;
+        if version.starts_with("corrupt") {
+            anyhow::bail!("not a plugin library");
+        }
+        Ok((Box::new(MockPlugin::with_version(version.trim())), None))
+    }
+    
+    /// A manager that loaded `mock.so` at version 1.0.0 through `mock_load`
+    async fn hot_reload_manager(fixture: &Fixture) -> PluginManager {
+        let mut config = fixture.config().unwrap().plugin;
+        config.enable_hot_reload = true;
+        config.security_policy.require_signed = false;
+        
+        let mut manager = PluginManager::new(config, None);
+        manager.load = mock_load;
+        manager.load_plugins().await.unwrap();
+        assert_eq!(manager.get_plugin("mock-plugin").unwrap().metadata().version, "1.0.0");
+        manager
+    }
+    
+    #[tokio::test]
+    async fn test_disabled_plugins_are_skipped() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .plugin_file("broken.so", b"corrupt".to_vec())
+            .build()
+            .unwrap();
+        let mut config = fixture.config().unwrap().plugin;
+        config.security_policy.require_signed = false;
+        config.disabled = vec!["mock-plugin".to_string()];
+        
+        let mut manager = PluginManager::new(config, None);
+        manager.load = mock_load;
+        manager.load_plugins().await.unwrap();
+        
+        assert!(manager.get_plugin("mock-plugin").is_none());
+        let skipped: Vec<(&str, &str)> = manager.skipped_plugins().iter()
+            .map(|(path, reason)| (path.file_name().unwrap().to_str().unwrap(), reason.as_str()))
+            .collect();
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[0].0, "broken.so");
+        assert_eq!(skipped[1], ("mock.so", "Plugin disabled: mock-plugin"));
+        
+        // Compiled-in plugins honor the disabled list too
+        assert!(manager.register_plugin(Box::new(MockPlugin::new())).is_err());
+        manager.config.disabled.clear();
+        assert_eq!(manager.register_plugin(Box::new(MockPlugin::new())).unwrap(), "mock-plugin");
+        assert_eq!(manager.plugin_path("mock-plugin"), None);
+    }
+    
+    #[test]
+    fn test_dependents_of() {
+        let mut manager = PluginManager::new(PluginConfig::default(), None);
+        let mut dependent = MockPlugin::new();
+        dependent.metadata.name = "dashboard".to_string();
+        dependent.metadata.dependencies = vec!["mock-plugin".to_string()];
+        manager.insert_plugin(Box::new(dependent), None, None);
+        manager.insert_plugin(Box::new(MockPlugin::new()), None, None);
+        
+        assert_eq!(manager.dependents_of("mock-plugin"), vec!["dashboard"]);
+        assert!(manager.dependents_of("dashboard").is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_sync_plugin_dirs() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .build()
+            .unwrap();
+        let library = fixture.plugin_dir().join("mock.so");
+        let mut manager = hot_reload_manager(&fixture).await;
+        let notified = Arc::new(AtomicU64::new(0));
+        manager.on_plugins_changed({
+            let notified = Arc::clone(&notified);
+            move |_, _| {
+                notified.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        
+        assert!(manager.sync_plugin_dirs().await.is_empty());
+        
+        std::fs::write(&library, b"2.0.0-beta").unwrap();
+        let changes = manager.sync_plugin_dirs().await;
+        assert_eq!(changes.reloaded, vec!["mock-plugin"]);
+        assert_eq!(manager.get_plugin("mock-plugin").unwrap().metadata().version, "2.0.0-beta");
+        
+        // A broken replacement leaves the running version in place
+        std::fs::write(&library, b"corrupt").unwrap();
+        let changes = manager.sync_plugin_dirs().await;
+        assert_eq!(changes.failed, vec![library.clone()]);
+        assert_eq!(manager.get_plugin("mock-plugin").unwrap().metadata().version, "2.0.0-beta");
+        
+        std::fs::remove_file(&library).unwrap();
+        let changes = manager.sync_plugin_dirs().await;
+        assert_eq!(changes.unloaded, vec!["mock-plugin"]);
+        assert!(manager.list_plugins().is_empty());
+        
+        assert_eq!(notified.load(Ordering::Relaxed), 3);
+    }
+    
+    #[tokio::test]
+    async fn test_failed_reload_keeps_old_plugin() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .build()
+            .unwrap();
+        let library = fixture.plugin_dir().join("mock.so");
+        let mut manager = hot_reload_manager(&fixture).await;
+        
+        std::fs::write(&library, b"corrupt").unwrap();
+        let error = manager.reload_plugin("mock-plugin", &library).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PluginError>(),
+            Some(PluginError::LoadingFailed(reason)) if reason.contains("not a plugin library")
+        ));
+        assert_eq!(manager.get_plugin("mock-plugin").unwrap().metadata().version, "1.0.0");
+        assert_eq!(manager.plugin_agents.len(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_hot_reload_watches_plugin_dirs() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .build()
+            .unwrap();
+        let manager = Arc::new(tokio::sync::Mutex::new(hot_reload_manager(&fixture).await));
+        let tasks = TaskGroup::new("plugins");
+        let watch = WatchConfig {
+            debounce_ms: 10,
+            max_delay_ms: 100,
+            min_poll_ms: 10,
+            max_poll_ms: 20,
+            ..WatchConfig::default()
+        };
+        assert!(PluginManager::spawn_hot_reload(Arc::clone(&manager), watch.clone(), &tasks).await);
+        
+        std::fs::write(fixture.plugin_dir().join("mock.so"), b"2.0.0-beta").unwrap();
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        loop {
+            let version = manager.lock().await
+                .get_plugin("mock-plugin")
+                .map(|plugin| plugin.metadata().version.clone());
+            if version.as_deref() == Some("2.0.0-beta") {
+                break;
+            }
+            assert!(Instant::now() < deadline, "plugin was not reloaded");
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(tasks.shutdown(std::time::Duration::from_secs(1)).await.is_clean());
+        
+        // Disabled hot reload spawns nothing
+        manager.lock().await.config.enable_hot_reload = false;
+        assert!(!PluginManager::spawn_hot_reload(manager, watch, &tasks).await);
+    }
+    
+    #[tokio::test]
+    async fn test_plugin_health_reports_each_plugin() {
+        let fixture = Fixture::builder()
+            .plugin_file("mock.so", b"1.0.0".to_vec())
+            .build()
+            .unwrap();
+        let manager = Arc::new(tokio::sync::Mutex::new(hot_reload_manager(&fixture).await));
+        
+        let health = crate::health::HealthAggregator::new()
+            .group("plugin", move || PluginManager::plugin_health(Arc::clone(&manager)))
+            .check()
+            .await;
+        assert_eq!(health.components.len(), 1);
+        assert_eq!(health.components["plugin:mock-plugin"], ComponentHealth::healthy());
+        
+        assert_eq!(
+            ComponentHealth::from(PluginHealth::Degraded("slow rpc".to_string())),
+            ComponentHealth::degraded("slow rpc")
+        );
+    }
+    
+    /// Compiled-in plugin whose health checks follow a script, then repeat `fallback`
+    struct ScriptedPlugin {
+        metadata: PluginMetadata,
+        script: Mutex<std::collections::VecDeque<PluginHealth>>,
+        fallback: PluginHealth,
+        initialized: Arc<AtomicU64>,
+    }
+    
+    impl ScriptedPlugin {
+        fn new(script: Vec<PluginHealth>, fallback: PluginHealth) -> (Box<Self>, Arc<AtomicU64>) {
+            let mut metadata = MockPlugin::new().metadata;
+            metadata.name = "scripted".to_string();
+            let initialized = Arc::new(AtomicU64::new(0));
+            let plugin = Self {
+                metadata,
+                script: Mutex::new(script.into()),
+                fallback,
+                initialized: Arc::clone(&initialized),
+            };
+            (Box::new(plugin), initialized)
+        }
+    }
+    
+    impl Plugin for ScriptedPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+        
+        fn initialize(&mut self, _config: &PluginConfig) -> Result<()> {
+            self.initialized.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+        
+        fn agents(&self) -> Vec<Box<dyn Agent>> {
+            Vec::new()
+        }
+        
+        fn shutdown(&mut self) -> Result<()> {
+            Ok(())
+        }
+        
+        fn health_check(&self) -> Result<PluginHealth> {
+            let next = self.script.lock().unwrap().pop_front();
+            Ok(next.unwrap_or_else(|| self.fallback.clone()))
+        }
+    }
+    
+    fn supervised_manager(failure_threshold: u32, max_restarts: u32) -> PluginManager {
+        let mut config = test_plugin_config();
+        config.supervision.failure_threshold = failure_threshold;
+        config.supervision.max_restarts = max_restarts;
+        PluginManager::new(config, None)
+    }
+    
+    #[tokio::test]
+    async fn test_supervisor_restarts_failing_plugin() {
+        let mut manager = supervised_manager(2, 3);
+        let down = PluginHealth::Unhealthy("rpc down".to_string());
+        let (plugin, initialized) =
+            ScriptedPlugin::new(vec![down.clone(), down.clone()], PluginHealth::Healthy);
+        manager.register_plugin(plugin).unwrap();
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+        
+        assert_eq!(
+            manager.supervise(start).await,
+            vec![SupervisionEvent::HealthChanged {
+                plugin: "scripted".to_string(),
+                from: PluginHealth::Healthy,
+                to: down.clone(),
+            }]
+        );
+        assert_eq!(
+            manager.supervise(start + second).await,
+            vec![SupervisionEvent::Restarted { plugin: "scripted".to_string(), attempt: 1 }]
+        );
+        assert_eq!(initialized.load(Ordering::Relaxed), 2);
+        
+        let events = manager.supervise(start + 2 * second).await;
+        assert!(matches!(
+            &events[..],
+            [SupervisionEvent::HealthChanged { to: PluginHealth::Healthy, .. }]
+        ));
+        let status = &manager.plugin_status()["scripted"];
+        assert_eq!(status.health, Some(PluginHealth::Healthy));
+        assert_eq!((status.consecutive_failures, status.restarts, status.failed), (0, 1, false));
+    }
+    
+    #[tokio::test]
+    async fn test_supervisor_gives_up_after_max_restarts() {
+        let mut manager = supervised_manager(1, 2);
+        let (plugin, initialized) =
+            ScriptedPlugin::new(Vec::new(), PluginHealth::Unhealthy("stuck".to_string()));
+        manager.register_plugin(plugin).unwrap();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let restarted =
+            |attempt| SupervisionEvent::Restarted { plugin: "scripted".to_string(), attempt };
+        
+        assert_eq!(manager.supervise(at(0)).await.last(), Some(&restarted(1)));
+        // The second restart waits 1s, the third would wait 2s more
+        assert!(manager.supervise(at(500)).await.is_empty());
+        assert_eq!(manager.supervise(at(1_000)).await, vec![restarted(2)]);
+        assert!(manager.supervise(at(2_000)).await.is_empty());
+        assert_eq!(initialized.load(Ordering::Relaxed), 3);
+        
+        assert_eq!(
+            manager.supervise(at(3_000)).await,
+            vec![SupervisionEvent::GaveUp { plugin: "scripted".to_string(), restarts: 2 }]
+        );
+        assert!(manager.get_plugin("scripted").is_none());
+        assert!(!manager.plugin_agents.contains_key("scripted"));
+        assert!(manager.plugin_status()["scripted"].failed);
+        assert!(manager.supervise(at(60_000)).await.is_empty());
+        
+        let policy = crate::config::PluginSupervisionConfig::default();
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(40), Duration::from_secs(60));
+    }
+    
+    #[tokio::test]
+    async fn test_supervisor_stops_with_task_group() {
+        let manager = Arc::new(tokio::sync::Mutex::new(supervised_manager(3, 5)));
+        let tasks = TaskGroup::new("plugins");
+        assert!(PluginManager::spawn_supervisor(Arc::clone(&manager), &tasks).await);
+        
+        let report = tasks.shutdown(Duration::from_secs(1)).await;
+        assert!(report.is_clean());
+        assert_eq!(report.stopped, vec!["plugin-supervisor"]);
+        
+        manager.lock().await.config.supervision.health_check_interval_secs = 0;
+        assert!(!PluginManager::spawn_supervisor(manager, &tasks).await);
+    }
+    
+    #[tokio::test]
+    async fn test_lifecycle_stops_supervisor_before_unloading() {
+        let mut manager = supervised_manager(3, 5);
+        manager.insert_plugin(Box::new(MockPlugin::new()), None, None);
+        let manager = Arc::new(tokio::sync::Mutex::new(manager));
+        let tasks = TaskGroup::new("supervision");
+        assert!(PluginManager::spawn_supervisor(Arc::clone(&manager), &tasks).await);
+        
+        let lifecycle = crate::lifecycle::Lifecycle::new();
+        lifecycle.register(manager.clone(), &[]).unwrap();
+        lifecycle.register(Arc::new(tasks), &["plugins"]).unwrap();
+        let report = lifecycle.shutdown(Duration::from_secs(1)).await;
+        
+        assert!(report.is_clean());
+        let order: Vec<_> = report.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(order, ["supervision", "plugins"]);
+        assert!(manager.lock().await.list_plugins().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_publishes_load_and_unload_events() {
+        let bus = EventBus::default();
+        let mut recorder = crate::events::EventRecorder::new(&bus);
+        let mut manager = PluginManager::new(test_plugin_config(), None).with_events(bus);
+        
+        let name = manager.insert_plugin(Box::new(MockPlugin::new()), None, None);
+        manager.unload_plugin(&name).await.unwrap();
+        
+        assert_eq!(
+            recorder.events(),
+            [
+                NexusEvent::PluginLoaded {
+                    plugin: "mock-plugin".to_string(),
+                    version: "1.0.0".to_string(),
+                },
+                NexusEvent::PluginUnloaded { plugin: "mock-plugin".to_string() },
+            ]
+        );
+    }
+    
     #[test]
     fn test_mock_plugin() {
         let plugin = MockPlugin::new();
@@ -0,0 +1,368 @@
+//! Path-checked filesystem access for agents
+//!
+//! [`SandboxedFs`] is the only way an agent should touch files. Every
+//! target is canonicalized before use and must resolve under one of the
+//! agent's allowed roots, so `..` segments and symlinks pointing out of a
+//! root are rejected rather than followed.
+//!
+//! A sandbox in simulation mode still checks every path, but records writes
+//! in a [`SimulationLog`](crate::simulation::SimulationLog) instead of
+//! performing them, so agents that know nothing about dry runs stay safe.
+//!
+//! A sandbox given [`OpCounters`] counts every read, write and listing, and
+//! fails those over the counters' rate limit.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use crate::error::AgentError;
+use crate::op_counters::OpCounters;
+use crate::simulation::{IntendedAction, SharedSimulationLog};
+
+/// Result type for sandboxed filesystem operations
+pub type SandboxResult<T> = std::result::Result<T, AgentError>;
+
+/// Filesystem access restricted to a set of allowed roots
+#[derive(Debug, Clone)]
+pub struct SandboxedFs {
+    roots: Vec<PathBuf>,
+    simulation: Option<SharedSimulationLog>,
+    counters: Option<Arc<OpCounters>>,
+}
+
+impl SandboxedFs {
+    /// Restrict access to `allowed_paths`
+    ///
+    /// Each root must exist; roots are canonicalized once here.
+    pub fn new(allowed_paths: impl IntoIterator<Item = impl AsRef<Path>>) -> SandboxResult<Self> {
+        let roots = allowed_paths
+            .into_iter()
+            .map(|root| {
+                let root = root.as_ref();
+                root.canonicalize().map_err(|e| {
+                    AgentError::ConfigurationInvalid(format!(
+                        "allowed path {}: {}",
+                        root.display(),
+                        e
+                    ))
+                })
+            })
+            .collect::<SandboxResult<_>>()?;
+        Ok(Self {
+            roots,
+            simulation: None,
+            counters: None,
+        })
+    }
+
+    /// Record writes in `log` instead of performing them
+    pub fn simulated(mut self, log: SharedSimulationLog) -> Self {
+        self.simulation = Some(log);
+        self
+    }
+
+    /// Count operations in `counters`, failing those over its rate limit
+    ///
+    /// Simulated writes are counted too, so a dry run reports the same
+    /// operations as a real one.
+    pub fn counted(mut self, counters: Arc<OpCounters>) -> Self {
+        self.counters = Some(counters);
+        self
+    }
+
+    /// Whether writes are only being recorded
+    pub fn is_simulated(&self) -> bool {
+        self.simulation.is_some()
+    }
+
+    /// Canonical allowed roots
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Resolve `path` to its canonical location, failing if it escapes the roots
+    ///
+    /// A path that doesn't exist yet resolves through its parent directory,
+    /// so new files can be created inside a root. A path that can't be
+    /// resolved is denied unless it lies inside a root, so the error never
+    /// tells whether a path outside the roots exists.
+    pub fn resolve(&self, path: &Path) -> SandboxResult<PathBuf> {
+        let resolved = match path.canonicalize() {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                let Some((located, missing)) = locate(path) else {
+                    return Err(denied(path));
+                };
+                if !self.contains(&located) {
+                    return Err(denied(path));
+                }
+                // A dangling symlink would be followed on write, wherever it points
+                let creatable = e.kind() == std::io::ErrorKind::NotFound
+                    && missing == 1
+                    && path.symlink_metadata().is_err();
+                if !creatable {
+                    return Err(io_error(path, &e));
+                }
+                located
+            },
+        };
+
+        if self.contains(&resolved) {
+            Ok(resolved)
+        } else {
+            Err(denied(path))
+        }
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.roots.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Read a whole file
+    pub fn read(&self, path: &Path) -> SandboxResult<Vec<u8>> {
+        let resolved = self.resolve(path)?;
+        self.count()?;
+        std::fs::read(&resolved).map_err(|e| io_error(path, &e))
+    }
+
+    /// Create or replace a file
+    pub fn write(&self, path: &Path, contents: &[u8]) -> SandboxResult<()> {
+        let resolved = self.resolve(path)?;
+        self.count()?;
+        if let Some(log) = &self.simulation {
+            log.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .record(IntendedAction::WriteFile {
+                    path: resolved,
+                    bytes: contents.len(),
+                });
+            return Ok(());
+        }
+        std::fs::write(&resolved, contents).map_err(|e| io_error(path, &e))
+    }
+
+    /// Entries of a directory, sorted
+    pub fn list(&self, path: &Path) -> SandboxResult<Vec<PathBuf>> {
+        let resolved = self.resolve(path)?;
+        self.count()?;
+        let mut entries = std::fs::read_dir(&resolved)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .map_err(|e| io_error(path, &e))?;
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// Record an operation on a path that passed the checks
+    fn count(&self) -> SandboxResult<()> {
+        match &self.counters {
+            Some(counters) => counters.record_file_op(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Where `path` would be, and how many of its trailing components are missing
+///
+/// The nearest ancestor that exists is canonicalized and the missing
+/// components appended. `None` if a missing part steps out with `..`.
+fn locate(path: &Path) -> Option<(PathBuf, usize)> {
+    let mut missing = Vec::new();
+    let mut current = path;
+    loop {
+        let existing = if current.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            current
+        };
+        if let Ok(found) = existing.canonicalize() {
+            let count = missing.len();
+            let located = missing
+                .into_iter()
+                .rev()
+                .fold(found, |located, name| located.join(name));
+            return Some((located, count));
+        }
+        match current.components().next_back()? {
+            Component::Normal(name) => missing.push(name),
+            _ => return None,
+        }
+        current = current.parent()?;
+    }
+}
+
+fn denied(path: &Path) -> AgentError {
+    AgentError::PermissionDenied(format!("{} is outside the allowed paths", path.display()))
+}
+
+fn io_error(path: &Path, error: &std::io::Error) -> AgentError {
+    AgentError::ResourceUnavailable(format!("{}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A sandbox over `<tmp>/allowed`, with a secret file next to it
+    fn sandbox() -> (TempDir, SandboxedFs) {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("allowed/nested")).unwrap();
+        std::fs::write(dir.path().join("allowed/nested/notes.txt"), b"notes").unwrap();
+        std::fs::write(dir.path().join("secret.txt"), b"secret").unwrap();
+        let fs = SandboxedFs::new([dir.path().join("allowed")]).unwrap();
+        (dir, fs)
+    }
+
+    #[test]
+    fn test_nested_read_and_write() {
+        let (dir, fs) = sandbox();
+        let allowed = dir.path().join("allowed");
+
+        assert_eq!(
+            fs.read(&allowed.join("nested/notes.txt")).unwrap(),
+            b"notes"
+        );
+        fs.write(&allowed.join("nested/new.txt"), b"new").unwrap();
+        assert_eq!(
+            fs.list(&allowed.join("nested")).unwrap(),
+            vec![
+                fs.roots()[0].join("nested/new.txt"),
+                fs.roots()[0].join("nested/notes.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dot_dot_traversal_rejected() {
+        let (dir, fs) = sandbox();
+        let escape = dir.path().join("allowed/nested/../../secret.txt");
+
+        let error = fs.read(&escape).unwrap_err();
+        assert!(
+            matches!(&error, AgentError::PermissionDenied(message) if message.contains("secret.txt"))
+        );
+        assert!(fs
+            .write(&dir.path().join("allowed/../planted.txt"), b"x")
+            .is_err());
+        assert!(!dir.path().join("planted.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escape_rejected() {
+        let (dir, fs) = sandbox();
+        let link = dir.path().join("allowed/link.txt");
+        std::os::unix::fs::symlink(dir.path().join("secret.txt"), &link).unwrap();
+
+        assert!(matches!(
+            fs.read(&link),
+            Err(AgentError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            fs.write(&link, b"overwritten"),
+            Err(AgentError::PermissionDenied(_))
+        ));
+        assert_eq!(
+            std::fs::read(dir.path().join("secret.txt")).unwrap(),
+            b"secret"
+        );
+
+        let dangling = dir.path().join("allowed/dangling.txt");
+        std::os::unix::fs::symlink(dir.path().join("created.txt"), &dangling).unwrap();
+        assert!(fs.write(&dangling, b"x").is_err());
+        assert!(!dir.path().join("created.txt").exists());
+    }
+
+    #[test]
+    fn test_simulated_write_is_recorded_not_performed() {
+        let (dir, fs) = sandbox();
+        let log = crate::simulation::SimulationLog::shared();
+        let fs = fs.simulated(log.clone());
+        let target = dir.path().join("allowed/nested/notes.txt");
+
+        fs.write(&target, b"overwritten").unwrap();
+        fs.write(&dir.path().join("allowed/new.txt"), b"new").unwrap();
+        assert!(fs.write(&dir.path().join("secret.txt"), b"x").is_err());
+
+        assert_eq!(std::fs::read(&target).unwrap(), b"notes");
+        assert!(!dir.path().join("allowed/new.txt").exists());
+        assert_eq!(
+            log.lock().unwrap().intended_actions,
+            vec![
+                IntendedAction::WriteFile {
+                    path: fs.roots()[0].join("nested/notes.txt"),
+                    bytes: 11,
+                },
+                IntendedAction::WriteFile {
+                    path: fs.roots()[0].join("new.txt"),
+                    bytes: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_operations_are_counted_and_limited() {
+        let (dir, fs) = sandbox();
+        let counters = Arc::new(OpCounters::new());
+        let fs = fs.counted(Arc::clone(&counters));
+        let notes = dir.path().join("allowed/nested/notes.txt");
+
+        for _ in 0..5 {
+            fs.read(&notes).unwrap();
+        }
+        fs.list(&dir.path().join("allowed")).unwrap();
+        // Denied paths never reach the filesystem, so they aren't counted
+        assert!(fs.read(&dir.path().join("secret.txt")).is_err());
+        assert_eq!(counters.snapshot().file_operations, 6);
+
+        let limits = crate::config::AgentResourceLimits {
+            max_file_ops_per_sec: 10,
+            ..Default::default()
+        };
+        let counters = Arc::new(OpCounters::with_limits(&limits));
+        let fs = fs.counted(Arc::clone(&counters));
+        let target = dir.path().join("allowed/out.txt");
+        let error = (0..100)
+            .map(|i| fs.write(&target, format!("line {}", i).as_bytes()))
+            .find_map(Result::err)
+            .unwrap();
+        assert!(matches!(error, AgentError::ResourceLimitExceeded(_)));
+        assert_eq!(counters.snapshot().file_operations, 10);
+        assert_eq!(std::fs::read(&target).unwrap(), b"line 9");
+    }
+
+    #[test]
+    fn test_missing_paths_outside_roots_are_denied() {
+        let (dir, fs) = sandbox();
+
+        for outside in ["nope/x", "secret.txt/x", "allowed/nope/../../x"] {
+            assert!(
+                matches!(
+                    fs.read(&dir.path().join(outside)),
+                    Err(AgentError::PermissionDenied(_))
+                ),
+                "{}",
+                outside
+            );
+        }
+        // Inside a root the agent may learn what is missing
+        assert!(matches!(
+            fs.write(&dir.path().join("allowed/nope/x"), b"x"),
+            Err(AgentError::ResourceUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_root_rejected() {
+        let dir = TempDir::new().unwrap();
+        assert!(matches!(
+            SandboxedFs::new([dir.path().join("missing")]),
+            Err(AgentError::ConfigurationInvalid(_))
+        ));
+    }
+}
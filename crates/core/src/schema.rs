@@ -0,0 +1,577 @@
+//! Machine-readable description of the configuration keys
+//!
+//! [`Config::schema`] lists every key `nexus.toml` accepts with its type,
+//! default value and documentation. Keys and defaults come from
+//! [`Config::default`], so they can't drift from the structs; the types and
+//! descriptions below are kept next to them by hand.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+
+/// Documentation of one configuration key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigKeyDoc {
+    /// Dotted key path, e.g. `agent.max_concurrent_agents`
+    pub key: String,
+    /// Value type, e.g. `integer` or `array of paths`
+    #[serde(rename = "type")]
+    pub value_type: String,
+    /// Default value as a TOML literal, `None` when the key is unset by default
+    pub default: Option<String>,
+    /// Cargo feature the key needs, if any
+    pub feature: Option<String>,
+    /// Accepted values, for keys naming one of a fixed set
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+    /// What the key does
+    pub doc: String,
+}
+
+/// Hand-written part of a key's documentation
+struct KeySpec {
+    key: &'static str,
+    value_type: &'static str,
+    allowed: &'static [&'static str],
+    feature: Option<&'static str>,
+    doc: &'static str,
+}
+
+impl KeySpec {
+    const fn new(key: &'static str, value_type: &'static str, doc: &'static str) -> Self {
+        Self {
+            key,
+            value_type,
+            allowed: &[],
+            feature: None,
+            doc,
+        }
+    }
+
+    const fn one_of(mut self, allowed: &'static [&'static str]) -> Self {
+        self.allowed = allowed;
+        self
+    }
+
+    const fn web3(mut self) -> Self {
+        self.feature = Some("web3");
+        self
+    }
+}
+
+/// Types of the keys whose value is a table of names to values
+///
+/// Their entries are user-chosen names, so the table is one key.
+const MAP_TYPES: [&str; 2] = ["table of strings", "table of strings or string arrays"];
+
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+
+const KEYS: &[KeySpec] = &[
+    KeySpec::new(
+        "security.encryption_enabled",
+        "boolean",
+        "Encrypt sensitive data at rest",
+    ),
+    KeySpec::new(
+        "security.min_password_length",
+        "integer",
+        "Minimum password length",
+    ),
+    KeySpec::new(
+        "security.rate_limit.max_requests",
+        "integer",
+        "Requests allowed per rate limit window; must be greater than 0",
+    ),
+    KeySpec::new(
+        "agent.max_concurrent_agents",
+        "integer",
+        "Maximum number of concurrent agents; must be greater than 0",
+    ),
+    KeySpec::new(
+        "agent.default_timeout_secs",
+        "integer",
+        "Default agent timeout in seconds; must be greater than 0",
+    ),
+    KeySpec::new("agent.data_dir", "path", "Agent data directory"),
+    KeySpec::new(
+        "agent.enable_sandboxing",
+        "boolean",
+        "Enable agent sandboxing",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.max_memory_mb",
+        "integer",
+        "Maximum memory usage in MB",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.max_cpu_percent",
+        "float",
+        "Maximum CPU usage percentage",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.max_file_ops_per_sec",
+        "integer",
+        "Maximum file operations per second",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.max_network_requests_per_min",
+        "integer",
+        "Maximum network requests per minute",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.max_output_bytes",
+        "integer",
+        "Maximum serialized size of an agent's output data",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.max_metadata_bytes",
+        "integer",
+        "Maximum size of each output metadata value",
+    ),
+    KeySpec::new(
+        "agent.default_resource_limits.truncate_output",
+        "boolean",
+        "Truncate oversized output instead of rejecting it",
+    ),
+    KeySpec::new(
+        "agent.golden.mode",
+        "string",
+        "Whether outputs are recorded, verified against recordings, or neither",
+    )
+    .one_of(&["off", "record", "verify"]),
+    KeySpec::new(
+        "agent.golden.volatile_fields",
+        "array of strings",
+        "Output fields that differ between identical runs, as path patterns",
+    ),
+    KeySpec::new(
+        "agent.golden.max_reported_diffs",
+        "integer",
+        "Differences listed in the error of a failed verification",
+    ),
+    KeySpec::new(
+        "agent.artifacts.spill_threshold_bytes",
+        "integer",
+        "Output fields larger than this are stored as artifacts; 0 keeps them inline",
+    ),
+    KeySpec::new(
+        "plugin.plugin_dirs",
+        "array of paths",
+        "Plugin directories to scan",
+    ),
+    KeySpec::new(
+        "plugin.enable_hot_reload",
+        "boolean",
+        "Enable plugin hot reloading",
+    ),
+    KeySpec::new(
+        "plugin.security_policy.require_signed",
+        "boolean",
+        "Require signed plugins",
+    ),
+    KeySpec::new(
+        "plugin.security_policy.trusted_publishers",
+        "array of strings",
+        "Trusted plugin publishers",
+    ),
+    KeySpec::new(
+        "plugin.security_policy.trusted_keys",
+        "table of strings",
+        "Base64 ed25519 public keys of trusted publishers, keyed by publisher name",
+    ),
+    KeySpec::new(
+        "plugin.security_policy.allow_local_unsigned",
+        "boolean",
+        "Allow unsigned plugins under the home directory or the config file's directory",
+    ),
+    KeySpec::new(
+        "plugin.security_policy.isolation_level",
+        "string",
+        "Plugin isolation level",
+    )
+    .one_of(&["None", "Basic", "Strict", "Maximum"]),
+    KeySpec::new(
+        "plugin.max_load_time_secs",
+        "integer",
+        "Maximum plugin load time in seconds",
+    ),
+    KeySpec::new(
+        "plugin.disabled",
+        "array of strings",
+        "Plugins found in the plugin directories but not loaded",
+    ),
+    KeySpec::new(
+        "plugin.supervision.health_check_interval_secs",
+        "integer",
+        "Seconds between plugin health checks; 0 turns supervision off",
+    ),
+    KeySpec::new(
+        "plugin.supervision.failure_threshold",
+        "integer",
+        "Unhealthy results in a row before a plugin is restarted",
+    ),
+    KeySpec::new(
+        "plugin.supervision.max_restarts",
+        "integer",
+        "Restarts tried before a plugin is unloaded for good",
+    ),
+    KeySpec::new(
+        "plugin.supervision.restart_backoff_ms",
+        "integer",
+        "Wait before the second restart in milliseconds, doubled for each one after",
+    ),
+    KeySpec::new(
+        "plugin.supervision.max_restart_backoff_ms",
+        "integer",
+        "Longest wait between restarts, in milliseconds",
+    ),
+    KeySpec::new("logging.level", "string", "Log level").one_of(LOG_LEVELS),
+    KeySpec::new(
+        "logging.directives",
+        "array of strings",
+        "Per-module level overrides, e.g. `nexus_core::plugin=debug`",
+    ),
+    KeySpec::new("logging.format", "string", "Log format").one_of(&["json", "pretty", "compact"]),
+    KeySpec::new("logging.log_to_file", "boolean", "Log to file"),
+    KeySpec::new("logging.log_file_path", "path", "Log file path"),
+    KeySpec::new("logging.rotation", "string", "When to start a new log file")
+        .one_of(&["never", "daily", "size"]),
+    KeySpec::new(
+        "logging.max_file_size",
+        "integer",
+        "File size in bytes that triggers a rotation with `rotation = \"size\"`",
+    ),
+    KeySpec::new("logging.max_files", "integer", "Rotated log files to keep"),
+    KeySpec::new("logging.structured", "boolean", "Enable structured logging"),
+    KeySpec::new(
+        "logging.security_events",
+        "boolean",
+        "Enable security event logging",
+    ),
+    KeySpec::new(
+        "logging.audit_log_path",
+        "path",
+        "File receiving a copy of events tagged `security = true`",
+    ),
+    KeySpec::new("watch.mode", "string", "Change detection strategy")
+        .one_of(&["auto", "native", "poll"]),
+    KeySpec::new(
+        "watch.debounce_ms",
+        "integer",
+        "Quiet period after the last change before reloading, in milliseconds",
+    ),
+    KeySpec::new(
+        "watch.max_delay_ms",
+        "integer",
+        "Upper bound between the first change and the reload, in milliseconds",
+    ),
+    KeySpec::new(
+        "watch.probe_interval_ms",
+        "integer",
+        "Interval of the verification probe while using native events, in milliseconds",
+    ),
+    KeySpec::new(
+        "watch.min_poll_ms",
+        "integer",
+        "Initial polling interval, in milliseconds",
+    ),
+    KeySpec::new(
+        "watch.max_poll_ms",
+        "integer",
+        "Polling interval ceiling reached through backoff, in milliseconds",
+    ),
+    KeySpec::new(
+        "watch.missed_event_threshold",
+        "integer",
+        "Probe-detected changes without a native event before falling back to polling",
+    ),
+    KeySpec::new(
+        "web3.default_network",
+        "string",
+        "Default network to connect to",
+    )
+    .web3(),
+    KeySpec::new(
+        "web3.rpc_endpoints",
+        "table of strings or string arrays",
+        "RPC endpoints by network, a URL or a list of URLs in order of preference",
+    )
+    .web3(),
+    KeySpec::new(
+        "web3.enable_simulation",
+        "boolean",
+        "Enable transaction simulation",
+    )
+    .web3(),
+    KeySpec::new(
+        "web3.gas_limit_multiplier",
+        "float",
+        "Gas limit multiplier for safety",
+    )
+    .web3(),
+    KeySpec::new(
+        "web3.key_storage.storage_type",
+        "string",
+        "Private key storage method",
+    )
+    .one_of(&["Keychain", "EncryptedFile", "Hardware", "Environment"])
+    .web3(),
+    KeySpec::new(
+        "web3.key_storage.encrypted",
+        "boolean",
+        "Encrypt stored keys",
+    )
+    .web3(),
+    KeySpec::new("web3.key_storage.kdf", "string", "Key derivation function").web3(),
+    KeySpec::new(
+        "web3.chains",
+        "array of tables",
+        "Chains besides the built-in ones: id, name, aliases, currency, explorer_url",
+    )
+    .web3(),
+];
+
+impl Config {
+    /// Every configuration key, sorted by key
+    ///
+    /// Keys of sections this file doesn't describe, such as fields added to
+    /// the security settings, are still listed with their inferred type and
+    /// default but without a description.
+    pub fn schema() -> Vec<ConfigKeyDoc> {
+        let mut defaults = BTreeMap::new();
+        if let Ok(table) = toml::Table::try_from(Self::default()) {
+            collect_defaults(&table, "", &mut defaults);
+        }
+
+        let mut keys: BTreeMap<String, ConfigKeyDoc> = KEYS
+            .iter()
+            .map(|spec| {
+                let doc = ConfigKeyDoc {
+                    key: spec.key.to_string(),
+                    value_type: spec.value_type.to_string(),
+                    default: defaults.remove(spec.key).map(|value| value.to_string()),
+                    feature: spec.feature.map(str::to_string),
+                    allowed: spec.allowed.iter().map(ToString::to_string).collect(),
+                    doc: spec.doc.to_string(),
+                };
+                (doc.key.clone(), doc)
+            })
+            .collect();
+        for (key, value) in defaults {
+            let doc = ConfigKeyDoc {
+                key: key.clone(),
+                value_type: type_name(&value).to_string(),
+                default: Some(value.to_string()),
+                feature: None,
+                allowed: Vec::new(),
+                doc: String::new(),
+            };
+            keys.insert(key, doc);
+        }
+        keys.into_values().collect()
+    }
+}
+
+/// Whether `key` is a key in `schema`, an entry of one of its tables, or a section of keys
+pub fn is_schema_key(schema: &[ConfigKeyDoc], key: &str) -> bool {
+    schema.iter().any(|doc| {
+        doc.key == key
+            || key.strip_prefix(doc.key.as_str()).is_some_and(|rest| {
+                rest.starts_with('.') && MAP_TYPES.contains(&doc.value_type.as_str())
+            })
+            || doc
+                .key
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// Default value of every key in `table`, by dotted path
+fn collect_defaults(
+    table: &toml::Table,
+    prefix: &str,
+    defaults: &mut BTreeMap<String, toml::Value>,
+) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        let is_map = KEYS
+            .iter()
+            .any(|spec| spec.key == path && MAP_TYPES.contains(&spec.value_type));
+        match value {
+            toml::Value::Table(nested) if !is_map && !nested.is_empty() => {
+                collect_defaults(nested, &path, defaults);
+            },
+            _ => {
+                defaults.insert(path, value.clone());
+            },
+        }
+    }
+}
+
+/// Type of an undocumented key, from its default value
+fn type_name(value: &toml::Value) -> &'static str {
+    match value {
+        toml::Value::String(_) => "string",
+        toml::Value::Integer(_) => "integer",
+        toml::Value::Float(_) => "float",
+        toml::Value::Boolean(_) => "boolean",
+        toml::Value::Datetime(_) => "datetime",
+        toml::Value::Array(_) => "array",
+        toml::Value::Table(_) => "table",
+    }
+}
+
+/// Description of a key with its accepted values and feature, if any
+fn describe(doc: &ConfigKeyDoc) -> String {
+    let mut description = doc.doc.clone();
+    if !doc.allowed.is_empty() {
+        description.push_str(&format!(" (one of: {})", doc.allowed.join(", ")));
+    }
+    if let Some(feature) = &doc.feature {
+        description.push_str(&format!(" [requires the `{}` feature]", feature));
+    }
+    description.trim_start().to_string()
+}
+
+/// Render the schema as aligned `KEY TYPE DEFAULT DESCRIPTION` columns
+pub fn render_table(schema: &[ConfigKeyDoc]) -> String {
+    let defaults: Vec<&str> = schema
+        .iter()
+        .map(|doc| doc.default.as_deref().unwrap_or("-"))
+        .collect();
+    let key_width = schema
+        .iter()
+        .map(|doc| doc.key.len())
+        .max()
+        .unwrap_or(0)
+        .max(3);
+    let type_width = schema
+        .iter()
+        .map(|doc| doc.value_type.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let default_width = defaults
+        .iter()
+        .map(|value| value.len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+
+    let mut output = format!(
+        "{:key_width$}  {:type_width$}  {:default_width$}  DESCRIPTION\n",
+        "KEY", "TYPE", "DEFAULT"
+    );
+    for (doc, default) in schema.iter().zip(defaults) {
+        let line = format!(
+            "{:key_width$}  {:type_width$}  {:default_width$}  {}",
+            doc.key,
+            doc.value_type,
+            default,
+            describe(doc)
+        );
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Render the schema as a Markdown table
+pub fn render_markdown(schema: &[ConfigKeyDoc]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut output = String::from("| Key | Type | Default | Description |\n|---|---|---|---|\n");
+    for doc in schema {
+        let default = match &doc.default {
+            Some(value) => format!("`{}`", cell(value)),
+            None => "-".to_string(),
+        };
+        output.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            doc.key,
+            doc.value_type,
+            default,
+            cell(&describe(doc))
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(schema: &'a [ConfigKeyDoc], key: &str) -> &'a ConfigKeyDoc {
+        schema.iter().find(|doc| doc.key == key).unwrap()
+    }
+
+    #[test]
+    fn schema_lists_nested_keys_with_defaults() {
+        let schema = Config::schema();
+
+        let max_requests = find(&schema, "security.rate_limit.max_requests");
+        assert_eq!(max_requests.value_type, "integer");
+        assert_eq!(max_requests.default.as_deref(), Some("100"));
+        assert!(!max_requests.doc.is_empty());
+
+        let isolation = find(&schema, "plugin.security_policy.isolation_level");
+        assert_eq!(isolation.default.as_deref(), Some("\"Strict\""));
+        assert_eq!(isolation.allowed, ["None", "Basic", "Strict", "Maximum"]);
+
+        // Unset by default, and a table of user-chosen names
+        assert_eq!(find(&schema, "logging.log_file_path").default, None);
+        assert_eq!(
+            find(&schema, "plugin.security_policy.trusted_keys")
+                .default
+                .as_deref(),
+            Some("{}")
+        );
+        assert_eq!(
+            find(&schema, "web3.default_network").feature.as_deref(),
+            Some("web3")
+        );
+
+        // Everything outside the security section is documented here
+        for doc in schema
+            .iter()
+            .filter(|doc| !doc.key.starts_with("security."))
+        {
+            assert!(!doc.doc.is_empty(), "{} is undocumented", doc.key);
+        }
+    }
+
+    #[test]
+    fn schema_keys_include_sections_and_table_entries() {
+        let schema = Config::schema();
+        assert!(is_schema_key(&schema, "agent.max_concurrent_agents"));
+        assert!(is_schema_key(&schema, "agent.golden"));
+        assert!(is_schema_key(
+            &schema,
+            "plugin.security_policy.trusted_keys.acme"
+        ));
+        assert!(!is_schema_key(&schema, "agent.max_concurent_agents"));
+        assert!(!is_schema_key(&schema, "agent.data_dir.nested"));
+    }
+
+    #[test]
+    fn schema_renders_as_table_and_markdown() {
+        let schema = Config::schema();
+
+        let table = render_table(&schema);
+        assert!(table.starts_with("KEY "));
+        let line = table
+            .lines()
+            .find(|line| line.starts_with("logging.level "))
+            .unwrap();
+        assert!(line.contains("\"info\""));
+        assert!(line.ends_with("(one of: trace, debug, info, warn, error)"));
+
+        let markdown = render_markdown(&schema);
+        assert!(markdown.starts_with("| Key | Type | Default | Description |\n"));
+        assert!(markdown.contains("| `agent.data_dir` | path | `\"./data/agents\"` |"));
+    }
+}
@@ -0,0 +1,315 @@
+//! Detached plugin signatures for NEXUS
+//!
+//! A signed plugin ships a `<library>.sig` file next to the library, e.g.
+//! `libweather.so.sig`. The file holds a small manifest (name, version,
+//! publisher and the SHA-256 of the library) and an ed25519 signature over
+//! the canonical JSON of that manifest, so the signature covers both the
+//! library bytes and the identity the publisher vouched for.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use ring::signature::{Ed25519KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::canonical::{to_canonical_string, CanonicalError};
+
+/// Extension appended to a library's file name to find its signature
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Errors produced while checking a plugin signature
+#[derive(Debug, Error)]
+pub enum SignatureError {
+    /// The signature file could not be read
+    #[error("failed to read {path}: {source}")]
+    Io {
+        /// File that failed
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+
+    /// The signature file is not valid
+    #[error("malformed signature file: {0}")]
+    Malformed(String),
+
+    /// The publisher is not trusted by the security policy
+    #[error("publisher '{0}' is not trusted")]
+    UntrustedPublisher(String),
+
+    /// The publisher is trusted but has no public key configured
+    #[error("no public key configured for publisher '{0}'")]
+    MissingKey(String),
+
+    /// The library on disk is not the one that was signed
+    #[error("library hash does not match the signed manifest")]
+    HashMismatch,
+
+    /// The signature does not verify against the publisher's key
+    #[error("signature does not verify against the key of publisher '{0}'")]
+    InvalidSignature(String),
+}
+
+impl From<CanonicalError> for SignatureError {
+    fn from(error: CanonicalError) -> Self {
+        Self::Malformed(error.to_string())
+    }
+}
+
+/// What a publisher vouches for when signing a plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedManifest {
+    /// Plugin name, compared with the loaded plugin's metadata
+    pub name: String,
+    /// Plugin version, compared with the loaded plugin's metadata
+    pub version: String,
+    /// Publisher name, looked up in the trusted keys
+    pub publisher: String,
+    /// SHA-256 of the library, hex encoded
+    pub sha256: String,
+}
+
+impl SignedManifest {
+    /// Describe the library at `path`
+    pub fn for_library(
+        path: &Path,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        publisher: impl Into<String>,
+    ) -> Result<Self, SignatureError> {
+        Ok(Self {
+            name: name.into(),
+            version: version.into(),
+            publisher: publisher.into(),
+            sha256: library_hash(path)?,
+        })
+    }
+
+    fn message(&self) -> Result<String, SignatureError> {
+        let value =
+            serde_json::to_value(self).map_err(|e| SignatureError::Malformed(e.to_string()))?;
+        Ok(to_canonical_string(&value)?)
+    }
+}
+
+/// Contents of a `.sig` file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginSignature {
+    /// Signed manifest
+    pub manifest: SignedManifest,
+    /// Ed25519 signature over the canonical manifest JSON, base64 encoded
+    pub signature: String,
+}
+
+impl PluginSignature {
+    /// Sign a manifest with a publisher's key pair
+    pub fn sign(
+        manifest: SignedManifest,
+        key_pair: &Ed25519KeyPair,
+    ) -> Result<Self, SignatureError> {
+        let signature = key_pair.sign(manifest.message()?.as_bytes());
+        Ok(Self {
+            manifest,
+            signature: BASE64.encode(signature.as_ref()),
+        })
+    }
+
+    /// Read the signature file belonging to the library at `library`
+    pub fn read_for(library: &Path) -> Result<Self, SignatureError> {
+        let path = signature_path(library);
+        let content = std::fs::read_to_string(&path).map_err(|source| SignatureError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        serde_json::from_str(&content).map_err(|e| SignatureError::Malformed(e.to_string()))
+    }
+
+    /// Render the signature file contents
+    pub fn to_json(&self) -> Result<String, SignatureError> {
+        serde_json::to_string_pretty(self).map_err(|e| SignatureError::Malformed(e.to_string()))
+    }
+
+    /// Check the signature against the library at `library`
+    ///
+    /// `trusted_publishers` lists the publishers the policy accepts and
+    /// `trusted_keys` maps publisher names to base64 ed25519 public keys.
+    /// Returns the verified manifest.
+    pub fn verify(
+        &self,
+        library: &Path,
+        trusted_publishers: &[String],
+        trusted_keys: &HashMap<String, String>,
+    ) -> Result<&SignedManifest, SignatureError> {
+        self.verify_digest(&library_hash(library)?, trusted_publishers, trusted_keys)
+    }
+
+    /// Check the signature against a library whose SHA-256 is `sha256`
+    ///
+    /// Like [`verify`](Self::verify), for callers that hashed the bytes
+    /// themselves.
+    pub fn verify_digest(
+        &self,
+        sha256: &str,
+        trusted_publishers: &[String],
+        trusted_keys: &HashMap<String, String>,
+    ) -> Result<&SignedManifest, SignatureError> {
+        let publisher = &self.manifest.publisher;
+        if !trusted_publishers.contains(publisher) {
+            return Err(SignatureError::UntrustedPublisher(publisher.clone()));
+        }
+        let key = trusted_keys
+            .get(publisher)
+            .ok_or_else(|| SignatureError::MissingKey(publisher.clone()))?;
+        let key = BASE64.decode(key).map_err(|e| {
+            SignatureError::Malformed(format!("public key of '{}': {}", publisher, e))
+        })?;
+        let signature = BASE64
+            .decode(&self.signature)
+            .map_err(|e| SignatureError::Malformed(format!("signature: {}", e)))?;
+
+        UnparsedPublicKey::new(&ED25519, key)
+            .verify(self.manifest.message()?.as_bytes(), &signature)
+            .map_err(|_| SignatureError::InvalidSignature(publisher.clone()))?;
+
+        if sha256 != self.manifest.sha256 {
+            return Err(SignatureError::HashMismatch);
+        }
+
+        Ok(&self.manifest)
+    }
+}
+
+/// Path of the signature file for the library at `library`
+pub fn signature_path(library: &Path) -> PathBuf {
+    let mut name = library.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(SIGNATURE_EXTENSION);
+    library.with_file_name(name)
+}
+
+/// SHA-256 of a file, hex encoded
+fn library_hash(path: &Path) -> Result<String, SignatureError> {
+    let bytes = std::fs::read(path).map_err(|source| SignatureError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(sha256_hex(&bytes))
+}
+
+/// SHA-256 of `bytes`, hex encoded as in [`SignedManifest::sha256`]
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, bytes);
+    digest.as_ref().iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{:02x}", b);
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::KeyPair;
+    use tempfile::TempDir;
+
+    struct Publisher {
+        key_pair: Ed25519KeyPair,
+        keys: HashMap<String, String>,
+    }
+
+    fn publisher(name: &str) -> Publisher {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let keys = HashMap::from([(
+            name.to_string(),
+            BASE64.encode(key_pair.public_key().as_ref()),
+        )]);
+        Publisher { key_pair, keys }
+    }
+
+    fn signed_library(dir: &TempDir, publisher: &Publisher) -> PathBuf {
+        let library = dir.path().join("libweather.so");
+        std::fs::write(&library, b"library bytes").unwrap();
+        let manifest = SignedManifest::for_library(&library, "weather", "1.0.0", "acme").unwrap();
+        let signature = PluginSignature::sign(manifest, &publisher.key_pair).unwrap();
+        std::fs::write(signature_path(&library), signature.to_json().unwrap()).unwrap();
+        library
+    }
+
+    #[test]
+    fn test_signature_path() {
+        assert_eq!(
+            signature_path(Path::new("/plugins/libweather.so")),
+            PathBuf::from("/plugins/libweather.so.sig")
+        );
+    }
+
+    #[test]
+    fn test_valid_signature() {
+        let dir = TempDir::new().unwrap();
+        let acme = publisher("acme");
+        let library = signed_library(&dir, &acme);
+
+        let signature = PluginSignature::read_for(&library).unwrap();
+        let manifest = signature
+            .verify(&library, &["acme".to_string()], &acme.keys)
+            .unwrap();
+        assert_eq!(manifest.name, "weather");
+        assert_eq!(manifest.version, "1.0.0");
+    }
+
+    #[test]
+    fn test_tampered_library_rejected() {
+        let dir = TempDir::new().unwrap();
+        let acme = publisher("acme");
+        let library = signed_library(&dir, &acme);
+        std::fs::write(&library, b"library bytes, patched").unwrap();
+
+        let signature = PluginSignature::read_for(&library).unwrap();
+        assert!(matches!(
+            signature.verify(&library, &["acme".to_string()], &acme.keys),
+            Err(SignatureError::HashMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_tampered_manifest_rejected() {
+        let dir = TempDir::new().unwrap();
+        let acme = publisher("acme");
+        let library = signed_library(&dir, &acme);
+
+        let mut signature = PluginSignature::read_for(&library).unwrap();
+        signature.manifest.version = "9.9.9".to_string();
+        assert!(matches!(
+            signature.verify(&library, &["acme".to_string()], &acme.keys),
+            Err(SignatureError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_untrusted_publisher_rejected() {
+        let dir = TempDir::new().unwrap();
+        let acme = publisher("acme");
+        let library = signed_library(&dir, &acme);
+        let signature = PluginSignature::read_for(&library).unwrap();
+
+        assert!(matches!(
+            signature.verify(&library, &["nexus-official".to_string()], &acme.keys),
+            Err(SignatureError::UntrustedPublisher(name)) if name == "acme"
+        ));
+
+        // Trusted by name, but signed with a key that isn't the configured one
+        let impostor = publisher("acme");
+        assert!(matches!(
+            signature.verify(&library, &["acme".to_string()], &impostor.keys),
+            Err(SignatureError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            signature.verify(&library, &["acme".to_string()], &HashMap::new()),
+            Err(SignatureError::MissingKey(_))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+//! Dry-run simulation for agents
+//!
+//! In simulation mode the helpers an agent uses for side effects record
+//! what they would have done instead of doing it. The record is a
+//! [`SimulationLog`], returned to the caller under
+//! [`SIMULATION_METADATA_KEY`] so a dry run can show its intended actions.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Metadata key reserved for the simulation log of a dry run
+pub const SIMULATION_METADATA_KEY: &str = "nexus.simulation";
+
+/// A side effect skipped because simulation was on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntendedAction {
+    /// Create or replace a file
+    WriteFile {
+        /// Canonical target path
+        path: PathBuf,
+        /// Size of the contents that would have been written
+        bytes: usize,
+    },
+    /// Send a network request
+    HttpRequest {
+        /// Request method
+        method: String,
+        /// Target URL
+        url: String,
+    },
+}
+
+/// Side effects a simulated run would have performed, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationLog {
+    /// Recorded actions
+    pub intended_actions: Vec<IntendedAction>,
+}
+
+/// Log shared by the helpers of one simulated run
+pub type SharedSimulationLog = Arc<Mutex<SimulationLog>>;
+
+impl SimulationLog {
+    /// Create an empty log to share between helpers
+    pub fn shared() -> SharedSimulationLog {
+        Arc::default()
+    }
+
+    /// Record an action
+    pub fn record(&mut self, action: IntendedAction) {
+        self.intended_actions.push(action);
+    }
+
+    /// The log as output metadata, under [`SIMULATION_METADATA_KEY`]
+    pub fn to_metadata(&self) -> (String, serde_json::Value) {
+        (
+            SIMULATION_METADATA_KEY.to_string(),
+            serde_json::to_value(self).unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let log = SimulationLog::shared();
+        log.lock().unwrap().record(IntendedAction::HttpRequest {
+            method: "GET".to_string(),
+            url: "https://example.com/price".to_string(),
+        });
+
+        let (key, value) = log.lock().unwrap().to_metadata();
+        assert_eq!(key, SIMULATION_METADATA_KEY);
+        assert_eq!(value["intended_actions"][0]["kind"], "http_request");
+        let parsed: SimulationLog = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, *log.lock().unwrap());
+    }
+}
@@ -0,0 +1,259 @@
+//! "Did you mean" suggestions for NEXUS
+//!
+//! Looks up close matches for a mistyped name among the known ones (agents,
+//! plugins, workflow templates, CLI subcommands) so a `not found` error can
+//! point at what the user probably meant.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+/// Minimum similarity, from 0.0 to 1.0, for a name to be suggested
+pub const DEFAULT_THRESHOLD: f64 = 0.6;
+
+/// Maximum number of suggestions returned
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Edit distance between two strings, counted in characters
+///
+/// Levenshtein distance that also counts swapping two adjacent characters
+/// as a single edit (optimal string alignment), since `ehco` for `echo` is
+/// the most common typo of all.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = Vec::new();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (row[j - 1] + cost).min(row[j] + 1).min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(previous[j - 2] + 1);
+            }
+        }
+        previous = std::mem::replace(&mut row, current);
+    }
+
+    row[b.len()]
+}
+
+/// Similarity between two names, 1.0 for identical and 0.0 for nothing in common
+///
+/// Case is ignored, since `Echo` and `echo` are the same typo target.
+#[allow(clippy::cast_precision_loss)]
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&a, &b) as f64 / longest as f64
+}
+
+/// Up to [`MAX_SUGGESTIONS`] candidates at least [`DEFAULT_THRESHOLD`] similar to `input`, best first
+pub fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut scored: Vec<(f64, &str)> = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != input)
+        .map(|candidate| (similarity(input, candidate), candidate))
+        .filter(|(score, _)| *score >= DEFAULT_THRESHOLD)
+        .collect();
+
+    scored.sort_by(|(a_score, a), (b_score, b)| b_score.total_cmp(a_score).then_with(|| a.cmp(b)));
+    scored.dedup_by(|(_, a), (_, b)| a == b);
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Render suggestions as a hint sentence, or `None` when there are none
+pub fn did_you_mean(suggestions: &[String]) -> Option<String> {
+    match suggestions {
+        [] => None,
+        [only] => Some(format!("Did you mean '{}'?", only)),
+        many => Some(format!(
+            "Did you mean one of: {}?",
+            many.iter()
+                .map(|s| format!("'{}'", s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// A lookup of an unknown name, with suggestions for what was meant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotFound {
+    /// Kind of thing looked up, e.g. "agent" or "crash report"
+    pub kind: String,
+    /// Name that was not found
+    pub name: String,
+    /// Close matches among the known names
+    pub suggestions: Vec<String>,
+}
+
+impl NotFound {
+    /// Build a not-found error, computing suggestions from `known`
+    pub fn new<'a>(
+        kind: impl Into<String>,
+        name: impl Into<String>,
+        known: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let name = name.into();
+        let suggestions = suggest(&name, known);
+        Self {
+            kind: kind.into(),
+            name,
+            suggestions,
+        }
+    }
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}' not found", self.kind, self.name)?;
+        if let Some(hint) = did_you_mean(&self.suggestions) {
+            write!(f, ". {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// Suggestion engine over a fixed set of names, caching results for missing names
+///
+/// Repeated lookups of the same unknown name during one process lifetime
+/// reuse the first result instead of scoring every candidate again.
+#[derive(Debug, Default)]
+pub struct Suggester {
+    names: Vec<String>,
+    misses: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl Suggester {
+    /// Create a suggester over the given known names
+    pub fn new(names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+            misses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the known names, discarding cached results
+    pub fn set_names(&mut self, names: impl IntoIterator<Item = impl Into<String>>) {
+        self.names = names.into_iter().map(Into::into).collect();
+        self.misses
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Whether `name` is one of the known names
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|known| known == name)
+    }
+
+    /// Look up `name`, returning a [`NotFound`] with suggestions if it is unknown
+    pub fn check(&self, kind: &str, name: &str) -> Result<(), NotFound> {
+        if self.contains(name) {
+            return Ok(());
+        }
+
+        Err(NotFound {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            suggestions: self.suggestions_for(name),
+        })
+    }
+
+    /// Suggestions for an unknown name, served from the cache after the first lookup
+    pub fn suggestions_for(&self, name: &str) -> Vec<String> {
+        let mut misses = self
+            .misses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        misses
+            .entry(name.to_string())
+            .or_insert_with(|| suggest(name, self.names.iter().map(String::as_str)))
+            .clone()
+    }
+
+    /// Whether suggestions for `name` are already cached
+    pub fn is_cached(&self, name: &str) -> bool {
+        self.misses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .contains_key(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGENTS: &[&str] = &["echo", "summarize", "translate", "web-search", "wallet-watch"];
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("echo", "echo"), 0);
+        assert_eq!(edit_distance("ehco", "echo"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_close_typos_suggested() {
+        assert_eq!(suggest("ehco", AGENTS.iter().copied()), vec!["echo"]);
+        assert_eq!(suggest("sumarize", AGENTS.iter().copied()), vec!["summarize"]);
+        assert_eq!(suggest("Translate", AGENTS.iter().copied()), vec!["translate"]);
+        assert_eq!(
+            suggest("wallet-wach", AGENTS.iter().copied()).first().map(String::as_str),
+            Some("wallet-watch")
+        );
+    }
+
+    #[test]
+    fn test_wildly_wrong_names_not_suggested() {
+        assert!(suggest("kubernetes", AGENTS.iter().copied()).is_empty());
+        assert!(suggest("x", AGENTS.iter().copied()).is_empty());
+    }
+
+    #[test]
+    fn test_at_most_three_suggestions() {
+        let names = ["agent1", "agent2", "agent3", "agent4", "agent5"];
+        assert_eq!(suggest("agent", names.iter().copied()).len(), MAX_SUGGESTIONS);
+    }
+
+    #[test]
+    fn test_not_found_message() {
+        let error = NotFound::new("agent", "ehco", AGENTS.iter().copied());
+        assert_eq!(error.to_string(), "agent 'ehco' not found. Did you mean 'echo'?");
+
+        let error = NotFound::new("agent", "kubernetes", AGENTS.iter().copied());
+        assert_eq!(error.to_string(), "agent 'kubernetes' not found");
+    }
+
+    #[test]
+    fn test_negative_cache() {
+        let mut suggester = Suggester::new(AGENTS.iter().copied());
+        assert!(suggester.check("agent", "echo").is_ok());
+        assert!(!suggester.is_cached("echo"));
+
+        let error = suggester.check("agent", "ehco").unwrap_err();
+        assert_eq!(error.suggestions, vec!["echo"]);
+        assert!(suggester.is_cached("ehco"));
+        assert_eq!(suggester.suggestions_for("ehco"), vec!["echo"]);
+
+        suggester.set_names(["ehco"]);
+        assert!(!suggester.is_cached("ehco"));
+        assert!(suggester.check("agent", "ehco").is_ok());
+    }
+}
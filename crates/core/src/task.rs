@@ -0,0 +1,321 @@
+//! Tracked background tasks for NEXUS
+//!
+//! Background work in core (watchers, monitors, drains) is spawned through a
+//! [`TaskGroup`] instead of a detached `tokio::spawn`. The group names every
+//! task, logs panics as they happen, propagates cancellation from its owner,
+//! and joins everything with a timeout at shutdown, reporting which tasks
+//! did not stop.
+//!
+//! Convention: core never calls `tokio::spawn` directly. Code that expects
+//! to run inside a tracked task can check it with
+//! [`debug_assert_tracked`].
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+tokio::task_local! {
+    static TRACKED_TASK: &'static str;
+}
+
+/// A task that is still running
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskInfo {
+    /// Task name
+    pub name: String,
+    /// Time since the task was spawned
+    pub uptime: Duration,
+}
+
+/// A task that panicked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskFailure {
+    /// Task name
+    pub name: String,
+    /// Panic message
+    pub message: String,
+}
+
+/// Outcome of shutting a task group down
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Tasks that stopped within the timeout
+    pub stopped: Vec<String>,
+    /// Tasks that were still running at the timeout and were aborted
+    pub timed_out: Vec<String>,
+    /// Tasks that panicked during the group's lifetime
+    pub panicked: Vec<TaskFailure>,
+}
+
+impl ShutdownReport {
+    /// Whether every task stopped cleanly
+    pub fn is_clean(&self) -> bool {
+        self.timed_out.is_empty() && self.panicked.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    started: Instant,
+    abort: AbortHandle,
+    supervisor: JoinHandle<()>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    tasks: HashMap<u64, Entry>,
+    failures: Vec<TaskFailure>,
+}
+
+/// A set of named background tasks sharing one cancellation token
+#[derive(Debug, Clone)]
+pub struct TaskGroup {
+    name: String,
+    token: CancellationToken,
+    next_id: Arc<AtomicU64>,
+    state: Arc<Mutex<State>>,
+}
+
+impl TaskGroup {
+    /// Create a top-level task group
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_token(name, CancellationToken::new())
+    }
+
+    fn with_token(name: impl Into<String>, token: CancellationToken) -> Self {
+        Self {
+            name: name.into(),
+            token,
+            next_id: Arc::new(AtomicU64::new(0)),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Create a group that is cancelled whenever this one is
+    pub fn child(&self, name: &str) -> Self {
+        Self::with_token(format!("{}/{}", self.name, name), self.token.child_token())
+    }
+
+    /// Group name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Token cancelled when the group shuts down
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Spawn a named task, handing it the group's cancellation token
+    ///
+    /// Must be called from within a tokio runtime. A panic in the task is
+    /// logged immediately and reported again at shutdown.
+    pub fn spawn<F, Fut>(&self, name: &'static str, task: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let inner = tokio::spawn(TRACKED_TASK.scope(name, task(self.token.clone())));
+        let abort = inner.abort_handle();
+
+        // Hold the lock across the supervisor spawn so it can't remove its
+        // entry before the entry exists
+        let mut state = self.lock();
+        let supervisor = tokio::spawn({
+            let state = Arc::clone(&self.state);
+            let group = self.name.clone();
+            async move {
+                let result = inner.await;
+                let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+                state.tasks.remove(&id);
+
+                if let Err(e) = result {
+                    if e.is_panic() {
+                        let message = panic_message(e.into_panic());
+                        tracing::error!("Task '{}/{}' panicked: {}", group, name, message);
+                        state.failures.push(TaskFailure {
+                            name: name.to_string(),
+                            message,
+                        });
+                    }
+                }
+            }
+        });
+
+        state.tasks.insert(
+            id,
+            Entry {
+                name: name.to_string(),
+                started: Instant::now(),
+                abort,
+                supervisor,
+            },
+        );
+    }
+
+    /// Tasks still running, oldest first
+    pub fn live_tasks(&self) -> Vec<TaskInfo> {
+        let state = self.lock();
+        let mut tasks: Vec<_> = state
+            .tasks
+            .values()
+            .map(|entry| (entry.started, entry.name.clone()))
+            .collect();
+        tasks.sort();
+
+        tasks
+            .into_iter()
+            .map(|(started, name)| TaskInfo {
+                name,
+                uptime: started.elapsed(),
+            })
+            .collect()
+    }
+
+    /// Tasks that have panicked so far
+    pub fn failures(&self) -> Vec<TaskFailure> {
+        self.lock().failures.clone()
+    }
+
+    /// Cancel all tasks and wait up to `timeout` for them to stop
+    ///
+    /// Tasks still running at the timeout are aborted and listed in the report.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.token.cancel();
+        let deadline = tokio::time::Instant::now() + timeout;
+        let entries: Vec<Entry> = self.lock().tasks.drain().map(|(_, entry)| entry).collect();
+
+        let mut report = ShutdownReport::default();
+        for mut entry in entries {
+            if tokio::time::timeout_at(deadline, &mut entry.supervisor)
+                .await
+                .is_ok()
+            {
+                report.stopped.push(entry.name);
+            } else {
+                tracing::warn!(
+                    "Task '{}/{}' did not stop in time, aborting",
+                    self.name,
+                    entry.name
+                );
+                entry.abort.abort();
+                report.timed_out.push(entry.name);
+            }
+        }
+
+        // Supervisors that finished record their panics before completing
+        report.panicked = self.failures();
+        report
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Name of the tracked task the caller is running in, if any
+pub fn current_task_name() -> Option<&'static str> {
+    TRACKED_TASK.try_with(|name| *name).ok()
+}
+
+/// Assert in debug builds that the caller runs inside a [`TaskGroup`] task
+///
+/// Place this at the top of long-running background loops so a raw
+/// `tokio::spawn` of them fails loudly in tests.
+#[track_caller]
+pub fn debug_assert_tracked() {
+    debug_assert!(
+        current_task_name().is_some(),
+        "background work must be spawned through a TaskGroup"
+    );
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_panic_captured() {
+        let group = TaskGroup::new("test");
+        group.spawn("exploder", |_| async { panic!("boom") });
+
+        for _ in 0..100 {
+            if !group.failures().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        assert_eq!(
+            group.failures(),
+            vec![TaskFailure {
+                name: "exploder".to_string(),
+                message: "boom".to_string()
+            }]
+        );
+        assert!(group.live_tasks().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_reports_hanging_tasks() {
+        let group = TaskGroup::new("test");
+        group.spawn("clean", |token| async move { token.cancelled().await });
+        group.spawn("stubborn", |_| std::future::pending());
+
+        let names: Vec<_> = group.live_tasks().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["clean", "stubborn"]);
+
+        let report = group.shutdown(Duration::from_millis(50)).await;
+        assert_eq!(report.stopped, vec!["clean"]);
+        assert_eq!(report.timed_out, vec!["stubborn"]);
+        assert!(!report.is_clean());
+        assert!(group.live_tasks().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_child_cancelled_with_parent() {
+        let parent = TaskGroup::new("nexus");
+        let child = parent.child("watchers");
+        assert_eq!(child.name(), "nexus/watchers");
+
+        child.spawn(
+            "config-watcher",
+            |token| async move { token.cancelled().await },
+        );
+        parent.token().cancel();
+
+        let report = child.shutdown(Duration::from_secs(1)).await;
+        assert_eq!(report.stopped, vec!["config-watcher"]);
+        assert!(report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_tracked_task_detection() {
+        assert_eq!(current_task_name(), None);
+
+        let group = TaskGroup::new("test");
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        group.spawn("probe", |_| async move {
+            debug_assert_tracked();
+            let _ = tx.send(current_task_name());
+        });
+
+        assert_eq!(rx.await.unwrap(), Some("probe"));
+    }
+}
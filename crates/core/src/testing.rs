@@ -0,0 +1,232 @@
+//! Test fixtures for NEXUS
+//!
+//! This module builds complete temporary NEXUS workspaces for integration
+//! tests, so each test doesn't hand-roll config files and directory trees.
+
+#![cfg(any(test, feature = "testing"))]
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::config::{Config, ConfigLoader};
+
+/// Name of the configuration file written into the fixture root
+pub const CONFIG_FILE_NAME: &str = "nexus.toml";
+
+/// Builder for a temporary NEXUS workspace
+#[derive(Debug, Default)]
+pub struct FixtureBuilder {
+    overrides: Vec<(String, toml::Value)>,
+    plugin_files: Vec<(String, Vec<u8>)>,
+}
+
+impl FixtureBuilder {
+    /// Override a configuration value by dotted key path, e.g. `agent.max_concurrent_agents`
+    pub fn set(mut self, key: &str, value: impl Into<toml::Value>) -> Self {
+        self.overrides.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Drop a file into the fixture's plugin directory
+    pub fn plugin_file(mut self, name: &str, contents: impl Into<Vec<u8>>) -> Self {
+        self.plugin_files.push((name.to_string(), contents.into()));
+        self
+    }
+
+    /// Create the workspace on disk
+    pub fn build(self) -> Result<Fixture> {
+        let root = TempDir::new().context("Failed to create fixture directory")?;
+        let fixture = Fixture { root };
+
+        fixture.ensure_layout()?;
+
+        let mut document = toml::Table::try_from(fixture.base_config())
+            .context("Failed to serialize fixture configuration")?;
+        for (key, value) in self.overrides {
+            set_path(&mut document, &key, value)?;
+        }
+
+        let content =
+            toml::to_string_pretty(&document).context("Failed to render fixture configuration")?;
+        std::fs::write(fixture.config_path(), content)
+            .context("Failed to write fixture configuration")?;
+
+        for (name, contents) in self.plugin_files {
+            std::fs::write(fixture.plugin_dir().join(&name), contents)
+                .with_context(|| format!("Failed to write fixture plugin: {}", name))?;
+        }
+
+        Ok(fixture)
+    }
+}
+
+/// A temporary NEXUS workspace, removed from disk when dropped
+#[derive(Debug)]
+pub struct Fixture {
+    root: TempDir,
+}
+
+impl Fixture {
+    /// Start building a fixture
+    pub fn builder() -> FixtureBuilder {
+        FixtureBuilder::default()
+    }
+
+    /// Create a fixture with the default configuration
+    pub fn new() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// Root directory of the workspace
+    pub fn root(&self) -> &Path {
+        self.root.path()
+    }
+
+    /// Path of the generated `nexus.toml`
+    pub fn config_path(&self) -> PathBuf {
+        self.root().join(CONFIG_FILE_NAME)
+    }
+
+    /// Agent data directory
+    pub fn data_dir(&self) -> PathBuf {
+        self.root().join("data")
+    }
+
+    /// Log directory
+    pub fn log_dir(&self) -> PathBuf {
+        self.root().join("logs")
+    }
+
+    /// Plugin directory
+    pub fn plugin_dir(&self) -> PathBuf {
+        self.root().join("plugins")
+    }
+
+    /// Create the workspace directories; safe to call repeatedly
+    pub fn ensure_layout(&self) -> Result<()> {
+        for dir in [self.data_dir(), self.log_dir(), self.plugin_dir()] {
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create fixture directory: {:?}", dir))?;
+        }
+        Ok(())
+    }
+
+    /// A configuration loader that finds this fixture's config first
+    pub fn loader(&self) -> ConfigLoader {
+        let mut loader = ConfigLoader::new();
+        loader.prepend_search_path(self.config_path());
+        loader
+    }
+
+    /// Load the fixture's configuration through the regular loader
+    pub fn config(&self) -> Result<Config> {
+        self.loader().load_from_file(&self.config_path())
+    }
+
+    /// Default configuration pointing every path into the fixture
+    fn base_config(&self) -> Config {
+        let mut config = Config::default();
+        config.agent.data_dir = self.data_dir();
+        config.plugin.plugin_dirs = vec![self.plugin_dir()];
+        config.logging.log_file_path = Some(self.log_dir().join("nexus.log"));
+        config
+    }
+}
+
+/// Set a value in a TOML document by dotted key path, creating tables as needed
+fn set_path(document: &mut toml::Table, key: &str, value: toml::Value) -> Result<()> {
+    let mut segments = key.split('.').peekable();
+    let mut table = document;
+
+    while let Some(segment) = segments.next() {
+        if segments.peek().is_none() {
+            table.insert(segment.to_string(), value);
+            return Ok(());
+        }
+
+        table = table
+            .entry(segment)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("Override key '{}' crosses a non-table value", key))?;
+    }
+
+    Err(anyhow::anyhow!("Empty override key"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_layout() {
+        let fixture = Fixture::new().unwrap();
+
+        assert!(fixture.config_path().is_file());
+        assert!(fixture.data_dir().is_dir());
+        assert!(fixture.log_dir().is_dir());
+        assert!(fixture.plugin_dir().is_dir());
+
+        let config = fixture.config().unwrap();
+        assert_eq!(config.agent.data_dir, fixture.data_dir().canonicalize().unwrap());
+        assert_eq!(
+            config.plugin.plugin_dirs,
+            vec![fixture.plugin_dir().canonicalize().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_fixture_overrides() {
+        let fixture = Fixture::builder()
+            .set("agent.max_concurrent_agents", 4)
+            .set("logging.level", "debug")
+            .build()
+            .unwrap();
+
+        let config = fixture.config().unwrap();
+        assert_eq!(config.agent.max_concurrent_agents, 4);
+        assert_eq!(config.logging.level, "debug");
+    }
+
+    #[test]
+    fn test_fixture_loader_prefers_fixture_config() {
+        let fixture = Fixture::builder()
+            .set("agent.default_timeout_secs", 7)
+            .build()
+            .unwrap();
+
+        let config = fixture.loader().load().unwrap();
+        assert_eq!(config.agent.default_timeout_secs, 7);
+    }
+
+    #[test]
+    fn test_fixture_layout_idempotent() {
+        let fixture = Fixture::builder()
+            .plugin_file("dummy.so", b"not a plugin".to_vec())
+            .build()
+            .unwrap();
+
+        fixture.ensure_layout().unwrap();
+        fixture.ensure_layout().unwrap();
+
+        assert!(fixture.plugin_dir().join("dummy.so").is_file());
+    }
+
+    #[test]
+    fn test_fixture_cleanup_on_drop() {
+        let fixture = Fixture::new().unwrap();
+        let root = fixture.root().to_path_buf();
+        assert!(root.exists());
+
+        drop(fixture);
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_override_through_scalar_rejected() {
+        let result = Fixture::builder().set("logging.level.nested", "x").build();
+
+        assert!(result.is_err());
+    }
+}
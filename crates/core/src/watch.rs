@@ -0,0 +1,467 @@
+//! Adaptive file watching strategy for NEXUS
+//!
+//! Config and plugin hot reload both watch files on disk. Native change
+//! notifications are cheap but never arrive on some network filesystems,
+//! while fixed-rate polling keeps laptops awake. [`AdaptiveWatcher`] decides
+//! which to use: it prefers native events, probes file fingerprints on a
+//! slow schedule to detect changes the native backend missed, falls back to
+//! polling with exponential backoff, and coalesces bursts of changes into a
+//! single reload.
+//!
+//! The watcher is a pure state machine. Callers feed it native events and
+//! the current time, and act on the [`WatchAction`] returned by
+//! [`AdaptiveWatcher::tick`], which keeps it independent of any particular
+//! notification backend and testable with synthetic time.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Which change detection strategy to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// Prefer native events, fall back to polling when they go missing
+    #[default]
+    Auto,
+    /// Always use native events
+    Native,
+    /// Always poll
+    Poll,
+}
+
+/// Strategy currently in effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveStrategy {
+    /// Native change notifications
+    Native,
+    /// Periodic polling of file fingerprints
+    Polling,
+}
+
+impl fmt::Display for ActiveStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native => write!(f, "native"),
+            Self::Polling => write!(f, "polling"),
+        }
+    }
+}
+
+/// File watching configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Strategy selection
+    pub mode: WatchMode,
+    /// Quiet period after the last change before reloading, in milliseconds
+    pub debounce_ms: u64,
+    /// Upper bound between the first change and the reload, in milliseconds
+    pub max_delay_ms: u64,
+    /// Interval of the verification probe while using native events, in milliseconds
+    pub probe_interval_ms: u64,
+    /// Initial polling interval, in milliseconds
+    pub min_poll_ms: u64,
+    /// Polling interval ceiling reached through backoff, in milliseconds
+    pub max_poll_ms: u64,
+    /// Probe-detected changes without a native event before falling back to polling
+    pub missed_event_threshold: u32,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            mode: WatchMode::Auto,
+            debounce_ms: 250,
+            max_delay_ms: 2_000,
+            probe_interval_ms: 30_000,
+            min_poll_ms: 1_000,
+            max_poll_ms: 60_000,
+            missed_event_threshold: 2,
+        }
+    }
+}
+
+/// Source of file fingerprints for the watched paths
+///
+/// A fingerprint changes whenever any watched file changes, e.g. the
+/// newest modification time combined with the file count.
+pub trait Prober {
+    /// Current fingerprint of the watched paths
+    fn probe(&mut self) -> std::io::Result<u64>;
+}
+
+/// What the caller should do after a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchAction {
+    /// Nothing to do yet
+    Idle,
+    /// Changes have settled; reload now
+    Reload,
+}
+
+/// Watcher state exposed for diagnostics
+#[derive(Debug, Clone)]
+pub struct WatchDiagnostics {
+    /// Configured mode
+    pub mode: WatchMode,
+    /// Strategy currently in effect
+    pub strategy: ActiveStrategy,
+    /// Current polling interval
+    pub poll_interval: Duration,
+    /// Last native event received
+    pub last_native_event: Option<Instant>,
+    /// Last change observed by any means
+    pub last_change: Option<Instant>,
+    /// Last reload requested
+    pub last_reload: Option<Instant>,
+    /// Number of times the watcher fell back to polling
+    pub fallbacks: u32,
+}
+
+/// Chooses between native events and polling, and debounces reloads
+#[derive(Debug)]
+pub struct AdaptiveWatcher<P> {
+    config: WatchConfig,
+    prober: P,
+    strategy: ActiveStrategy,
+    poll_interval: Duration,
+    next_probe: Instant,
+    fingerprint: Option<u64>,
+    native_since_probe: bool,
+    missed_events: u32,
+    pending_since: Option<Instant>,
+    last_native_event: Option<Instant>,
+    last_change: Option<Instant>,
+    last_reload: Option<Instant>,
+    fallbacks: u32,
+}
+
+impl<P: Prober> AdaptiveWatcher<P> {
+    /// Create a watcher starting at `now`
+    pub fn new(config: WatchConfig, mut prober: P, now: Instant) -> Self {
+        let strategy = match config.mode {
+            WatchMode::Poll => ActiveStrategy::Polling,
+            WatchMode::Auto | WatchMode::Native => ActiveStrategy::Native,
+        };
+        let fingerprint = prober.probe().ok();
+        let poll_interval = Duration::from_millis(config.min_poll_ms);
+
+        let mut watcher = Self {
+            config,
+            prober,
+            strategy,
+            poll_interval,
+            next_probe: now,
+            fingerprint,
+            native_since_probe: false,
+            missed_events: 0,
+            pending_since: None,
+            last_native_event: None,
+            last_change: None,
+            last_reload: None,
+            fallbacks: 0,
+        };
+        watcher.next_probe = now + watcher.probe_interval();
+        watcher
+    }
+
+    /// Strategy currently in effect
+    pub const fn strategy(&self) -> ActiveStrategy {
+        self.strategy
+    }
+
+    /// Record a native change notification
+    pub fn on_native_event(&mut self, now: Instant) {
+        self.last_native_event = Some(now);
+        self.native_since_probe = true;
+        self.missed_events = 0;
+
+        if self.strategy == ActiveStrategy::Polling && self.config.mode == WatchMode::Auto {
+            tracing::info!("Native file events resumed, leaving polling mode");
+            self.strategy = ActiveStrategy::Native;
+            self.next_probe = now + self.probe_interval();
+        }
+
+        self.record_change(now);
+    }
+
+    /// Advance the watcher to `now`, probing and firing the debounced reload when due
+    pub fn tick(&mut self, now: Instant) -> WatchAction {
+        if now >= self.next_probe {
+            self.run_probe(now);
+        }
+
+        match self.pending_since {
+            Some(first) if self.reload_due(first, now) => {
+                self.pending_since = None;
+                self.last_reload = Some(now);
+                WatchAction::Reload
+            }
+            _ => WatchAction::Idle,
+        }
+    }
+
+    /// Earliest instant at which `tick` may have something to do
+    pub fn next_deadline(&self) -> Instant {
+        match (self.pending_since, self.last_change) {
+            (Some(first), Some(last)) => self.next_probe.min(self.debounce_deadline(first, last)),
+            _ => self.next_probe,
+        }
+    }
+
+    /// Snapshot of the watcher state for diagnostics
+    pub fn diagnostics(&self) -> WatchDiagnostics {
+        WatchDiagnostics {
+            mode: self.config.mode,
+            strategy: self.strategy,
+            poll_interval: self.poll_interval,
+            last_native_event: self.last_native_event,
+            last_change: self.last_change,
+            last_reload: self.last_reload,
+            fallbacks: self.fallbacks,
+        }
+    }
+
+    fn run_probe(&mut self, now: Instant) {
+        let fingerprint = match self.prober.probe() {
+            Ok(fingerprint) => Some(fingerprint),
+            Err(e) => {
+                tracing::debug!("Watch probe failed: {}", e);
+                None
+            }
+        };
+        let changed = fingerprint.is_some() && fingerprint != self.fingerprint;
+        if fingerprint.is_some() {
+            self.fingerprint = fingerprint;
+        }
+
+        match self.strategy {
+            ActiveStrategy::Native => {
+                if changed && !self.native_since_probe {
+                    self.missed_events += 1;
+                    self.record_change(now);
+
+                    if self.config.mode == WatchMode::Auto
+                        && self.missed_events >= self.config.missed_event_threshold
+                    {
+                        tracing::warn!(
+                            "File changes detected without native events, falling back to polling"
+                        );
+                        self.strategy = ActiveStrategy::Polling;
+                        self.poll_interval = Duration::from_millis(self.config.min_poll_ms);
+                        self.fallbacks += 1;
+                    }
+                }
+            }
+            ActiveStrategy::Polling => {
+                if changed {
+                    self.poll_interval = Duration::from_millis(self.config.min_poll_ms);
+                    self.record_change(now);
+                } else {
+                    self.poll_interval = (self.poll_interval * 2)
+                        .min(Duration::from_millis(self.config.max_poll_ms));
+                }
+            }
+        }
+
+        self.native_since_probe = false;
+        self.next_probe = now + self.probe_interval();
+    }
+
+    fn probe_interval(&self) -> Duration {
+        match self.strategy {
+            ActiveStrategy::Native => Duration::from_millis(self.config.probe_interval_ms),
+            ActiveStrategy::Polling => self.poll_interval,
+        }
+    }
+
+    fn record_change(&mut self, now: Instant) {
+        self.last_change = Some(now);
+        self.pending_since.get_or_insert(now);
+    }
+
+    fn debounce_deadline(&self, first: Instant, last: Instant) -> Instant {
+        let quiet = last + Duration::from_millis(self.config.debounce_ms);
+        let bound = first + Duration::from_millis(self.config.max_delay_ms);
+        quiet.min(bound)
+    }
+
+    fn reload_due(&self, first: Instant, now: Instant) -> bool {
+        let last = self.last_change.unwrap_or(first);
+        now >= self.debounce_deadline(first, last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Fake filesystem whose fingerprint the test bumps to simulate edits
+    #[derive(Clone, Default)]
+    struct FakeFs(Rc<Cell<u64>>);
+
+    impl FakeFs {
+        fn touch(&self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    impl Prober for FakeFs {
+        fn probe(&mut self) -> std::io::Result<u64> {
+            Ok(self.0.get())
+        }
+    }
+
+    fn config() -> WatchConfig {
+        WatchConfig {
+            mode: WatchMode::Auto,
+            debounce_ms: 100,
+            max_delay_ms: 500,
+            probe_interval_ms: 1_000,
+            min_poll_ms: 200,
+            max_poll_ms: 1_600,
+            missed_event_threshold: 2,
+        }
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_fallback_when_native_events_missing() {
+        let fs = FakeFs::default();
+        let start = Instant::now();
+        let mut watcher = AdaptiveWatcher::new(config(), fs.clone(), start);
+
+        // Two probes see changes that native events never reported
+        fs.touch();
+        watcher.tick(start + ms(1_000));
+        assert_eq!(watcher.strategy(), ActiveStrategy::Native);
+
+        fs.touch();
+        watcher.tick(start + ms(2_000));
+        assert_eq!(watcher.strategy(), ActiveStrategy::Polling);
+        assert_eq!(watcher.diagnostics().fallbacks, 1);
+
+        // Native events coming back restore the native strategy
+        watcher.on_native_event(start + ms(2_100));
+        assert_eq!(watcher.strategy(), ActiveStrategy::Native);
+    }
+
+    #[test]
+    fn test_no_fallback_when_native_events_arrive() {
+        let fs = FakeFs::default();
+        let start = Instant::now();
+        let mut watcher = AdaptiveWatcher::new(config(), fs.clone(), start);
+
+        for i in 1..=3 {
+            fs.touch();
+            watcher.on_native_event(start + ms(i * 1_000 - 500));
+            watcher.tick(start + ms(i * 1_000));
+        }
+
+        assert_eq!(watcher.strategy(), ActiveStrategy::Native);
+        assert_eq!(watcher.diagnostics().fallbacks, 0);
+    }
+
+    #[test]
+    fn test_forced_modes() {
+        let fs = FakeFs::default();
+        let start = Instant::now();
+
+        let watcher = AdaptiveWatcher::new(
+            WatchConfig {
+                mode: WatchMode::Poll,
+                ..config()
+            },
+            fs.clone(),
+            start,
+        );
+        assert_eq!(watcher.strategy(), ActiveStrategy::Polling);
+
+        let mut watcher = AdaptiveWatcher::new(
+            WatchConfig {
+                mode: WatchMode::Native,
+                ..config()
+            },
+            fs.clone(),
+            start,
+        );
+        for i in 1..=3 {
+            fs.touch();
+            watcher.tick(start + ms(i * 1_000));
+        }
+        assert_eq!(watcher.strategy(), ActiveStrategy::Native);
+    }
+
+    #[test]
+    fn test_poll_backoff_is_bounded() {
+        let fs = FakeFs::default();
+        let start = Instant::now();
+        let mut watcher = AdaptiveWatcher::new(
+            WatchConfig {
+                mode: WatchMode::Poll,
+                ..config()
+            },
+            fs.clone(),
+            start,
+        );
+
+        let mut now = start;
+        for _ in 0..10 {
+            now = watcher.next_deadline();
+            watcher.tick(now);
+        }
+        assert_eq!(watcher.diagnostics().poll_interval, ms(1_600));
+
+        // A change resets the interval
+        fs.touch();
+        watcher.tick(watcher.next_deadline().max(now));
+        assert_eq!(watcher.diagnostics().poll_interval, ms(200));
+    }
+
+    #[test]
+    fn test_event_storm_coalesced() {
+        let fs = FakeFs::default();
+        let start = Instant::now();
+        let mut watcher = AdaptiveWatcher::new(config(), fs, start);
+
+        let mut reloads = 0;
+        for i in 0..5 {
+            watcher.on_native_event(start + ms(i * 20));
+            if watcher.tick(start + ms(i * 20)) == WatchAction::Reload {
+                reloads += 1;
+            }
+        }
+        assert_eq!(reloads, 0);
+
+        assert_eq!(watcher.tick(start + ms(150)), WatchAction::Idle);
+        assert_eq!(watcher.tick(start + ms(180)), WatchAction::Reload);
+        assert_eq!(watcher.tick(start + ms(400)), WatchAction::Idle);
+    }
+
+    #[test]
+    fn test_max_delay_bound() {
+        let fs = FakeFs::default();
+        let start = Instant::now();
+        let mut watcher = AdaptiveWatcher::new(config(), fs, start);
+
+        // Events every 50ms never leave a 100ms quiet period
+        let mut fired_at = None;
+        for i in 0..40 {
+            let now = start + ms(i * 50);
+            watcher.on_native_event(now);
+            if watcher.tick(now) == WatchAction::Reload {
+                fired_at = Some(now);
+                break;
+            }
+        }
+
+        assert_eq!(fired_at, Some(start + ms(500)));
+        assert_eq!(watcher.diagnostics().last_reload, fired_at);
+    }
+}
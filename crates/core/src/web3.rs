@@ -0,0 +1,230 @@
+//! Chains known to NEXUS
+//!
+//! Configuration and commands name chains by free-form strings:
+//! `ethereum`, `eth`, `mainnet` or `1` all mean the same network. A
+//! [`ChainRegistry`] resolves any of these to one [`ChainInfo`], so the rest
+//! of the code compares canonical names instead of whatever the user typed.
+//! It starts from the chains NEXUS knows about and adds those defined under
+//! `[[web3.chains]]`, which replace a built-in chain with the same id.
+
+use serde::{Deserialize, Serialize};
+
+use crate::suggest::NotFound;
+
+/// A chain and how to refer to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainInfo {
+    /// EIP-155 chain id
+    pub id: u64,
+    /// Canonical name, used as the key everywhere else
+    pub name: String,
+    /// Other names accepted for the chain
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Symbol of the native currency
+    pub currency: String,
+    /// Block explorer base URL, e.g. `https://etherscan.io`
+    #[serde(default)]
+    pub explorer_url: Option<String>,
+}
+
+impl ChainInfo {
+    /// Explorer page of the transaction `hash`
+    pub fn tx_url(&self, hash: &str) -> Option<String> {
+        self.explorer_page("tx", hash)
+    }
+
+    /// Explorer page of the account or contract `address`
+    pub fn address_url(&self, address: &str) -> Option<String> {
+        self.explorer_page("address", address)
+    }
+
+    fn explorer_page(&self, kind: &str, id: &str) -> Option<String> {
+        let base = self.explorer_url.as_deref()?.trim_end_matches('/');
+        Some(format!("{}/{}/{}", base, kind, id))
+    }
+
+    /// Whether `name` is the chain's name or one of its aliases, ignoring case
+    fn is_named(&self, name: &str) -> bool {
+        self.names().any(|known| known.eq_ignore_ascii_case(name))
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// Chains NEXUS knows without configuration: id, name, aliases, currency, explorer
+const BUILTIN: &[(u64, &str, &[&str], &str, &str)] = &[
+    (
+        1,
+        "ethereum",
+        &["eth", "mainnet"],
+        "ETH",
+        "https://etherscan.io",
+    ),
+    (
+        10,
+        "optimism",
+        &["op"],
+        "ETH",
+        "https://optimistic.etherscan.io",
+    ),
+    (137, "polygon", &["matic"], "POL", "https://polygonscan.com"),
+    (8453, "base", &[], "ETH", "https://basescan.org"),
+    (
+        42161,
+        "arbitrum",
+        &["arb", "arbitrum-one"],
+        "ETH",
+        "https://arbiscan.io",
+    ),
+    (
+        11_155_111,
+        "sepolia",
+        &[],
+        "ETH",
+        "https://sepolia.etherscan.io",
+    ),
+];
+
+/// Resolves chain names, aliases and ids to [`ChainInfo`]
+#[derive(Debug, Clone)]
+pub struct ChainRegistry {
+    chains: Vec<ChainInfo>,
+}
+
+impl Default for ChainRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl ChainRegistry {
+    /// The built-in chains
+    pub fn builtin() -> Self {
+        let chains = BUILTIN
+            .iter()
+            .map(|&(id, name, aliases, currency, explorer)| ChainInfo {
+                id,
+                name: name.to_string(),
+                aliases: aliases.iter().map(ToString::to_string).collect(),
+                currency: currency.to_string(),
+                explorer_url: Some(explorer.to_string()),
+            })
+            .collect();
+        Self { chains }
+    }
+
+    /// The built-in chains plus `defined`, which replace built-ins with the same id
+    pub fn with_chains(defined: impl IntoIterator<Item = ChainInfo>) -> Self {
+        let mut registry = Self::builtin();
+        for chain in defined {
+            registry.define(chain);
+        }
+        registry
+    }
+
+    /// Add `chain`, replacing the chain with the same id or name
+    ///
+    /// Aliases of other chains that `chain` also claims move to `chain`.
+    pub fn define(&mut self, chain: ChainInfo) {
+        self.chains.retain(|known| known.id != chain.id);
+        for known in &mut self.chains {
+            known.aliases.retain(|alias| !chain.is_named(alias));
+        }
+        self.chains
+            .retain(|known| !chain.names().any(|name| known.is_named(name)));
+        self.chains.push(chain);
+    }
+
+    /// The chain called `name`, by name, alias or decimal chain id
+    pub fn resolve(&self, name: &str) -> Result<&ChainInfo, NotFound> {
+        let name = name.trim();
+        let found = match name.parse::<u64>() {
+            Ok(id) => self.by_id(id),
+            Err(_) => self.chains.iter().find(|chain| chain.is_named(name)),
+        };
+        found.ok_or_else(|| {
+            NotFound::new("chain", name, self.chains.iter().flat_map(ChainInfo::names))
+        })
+    }
+
+    /// The chain with id `id`
+    pub fn by_id(&self, id: u64) -> Option<&ChainInfo> {
+        self.chains.iter().find(|chain| chain.id == id)
+    }
+
+    /// Every chain, in the order they were defined
+    pub fn chains(&self) -> &[ChainInfo] {
+        &self.chains
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolves_names_aliases_and_ids() {
+        let registry = ChainRegistry::builtin();
+        for name in ["ethereum", "ETH", "mainnet", "1", " eth "] {
+            assert_eq!(registry.resolve(name).unwrap().name, "ethereum", "{}", name);
+        }
+        assert_eq!(registry.resolve("matic").unwrap().id, 137);
+    }
+
+    #[test]
+    fn test_unknown_chain_suggests_close_names() {
+        let error = ChainRegistry::builtin().resolve("etherum").unwrap_err();
+        assert_eq!(error.suggestions, ["ethereum"]);
+        assert_eq!(
+            error.to_string(),
+            "chain 'etherum' not found. Did you mean 'ethereum'?"
+        );
+        assert!(ChainRegistry::builtin().resolve("42").is_err());
+    }
+
+    #[test]
+    fn test_defined_chain_replaces_builtin() {
+        let registry = ChainRegistry::with_chains([
+            ChainInfo {
+                id: 137,
+                name: "polygon-pos".to_string(),
+                aliases: vec!["polygon".to_string()],
+                currency: "POL".to_string(),
+                explorer_url: None,
+            },
+            ChainInfo {
+                id: 31337,
+                name: "anvil".to_string(),
+                aliases: vec!["local".to_string()],
+                currency: "ETH".to_string(),
+                explorer_url: Some("http://localhost:5100/".to_string()),
+            },
+        ]);
+
+        assert_eq!(registry.resolve("polygon").unwrap().name, "polygon-pos");
+        assert!(registry.resolve("matic").is_err());
+        assert_eq!(registry.resolve("local").unwrap().id, 31337);
+        assert_eq!(registry.chains().len(), BUILTIN.len() + 1);
+    }
+
+    #[test]
+    fn test_explorer_urls() {
+        let registry = ChainRegistry::builtin();
+        let base = registry.resolve("base").unwrap();
+        assert_eq!(
+            base.tx_url("0xabc").as_deref(),
+            Some("https://basescan.org/tx/0xabc")
+        );
+        assert_eq!(
+            base.address_url("0xdef").as_deref(),
+            Some("https://basescan.org/address/0xdef")
+        );
+
+        let mut anvil = base.clone();
+        anvil.explorer_url = None;
+        assert_eq!(anvil.tx_url("0xabc"), None);
+    }
+}
@@ -1,6 +1,8 @@
 //! Example plugin for NEXUS
 
-use nexus_core::{Agent, Result};
+use nexus_core::config::PluginConfig;
+use nexus_core::plugin::{Plugin, PluginHealth, PluginMetadata, PluginPermissions};
+use nexus_core::Agent;
 
 /// Example agent implementation
 pub struct ExampleAgent {
@@ -31,13 +33,62 @@ impl Agent for ExampleAgent {
     }
 }
 
-/// Plugin entry point
-#[no_mangle]
-pub extern "C" fn create_plugin() -> *mut dyn Agent {
-    let agent = Box::new(ExampleAgent::new());
-    Box::into_raw(agent)
+/// Name the example plugin registers under
+pub const PLUGIN_NAME: &str = "example";
+
+/// Example plugin exposing the NEXUS plugin entry point
+pub struct ExamplePlugin {
+    metadata: PluginMetadata,
+}
+
+impl ExamplePlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: PluginMetadata {
+                name: PLUGIN_NAME.to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                description: env!("CARGO_PKG_DESCRIPTION").to_string(),
+                author: "NEXUS Team".to_string(),
+                required_nexus_version: nexus_core::plugin::CORE_VERSION.to_string(),
+                dependencies: Vec::new(),
+                signature: None,
+                permissions: PluginPermissions::default(),
+            },
+        }
+    }
 }
 
+impl Default for ExamplePlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for ExamplePlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+    
+    fn initialize(&mut self, _config: &PluginConfig) -> anyhow::Result<()> {
+        Ok(())
+    }
+    
+    fn agents(&self) -> Vec<Box<dyn nexus_core::Agent>> {
+        Vec::new()
+    }
+    
+    fn shutdown(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+    
+    fn health_check(&self) -> anyhow::Result<PluginHealth> {
+        Ok(PluginHealth::Healthy)
+    }
+}
+
+// Plugin entry point
+nexus_core::declare_plugin!(ExamplePlugin::new);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,4 +99,11 @@ mod tests {
         assert_eq!(agent.name(), "example");
         assert!(!agent.run().is_empty());
     }
+    
+    #[test]
+    fn test_plugin_declaration() {
+        assert_eq!(NEXUS_PLUGIN_DECLARATION.abi_version, nexus_core::plugin::PLUGIN_ABI_VERSION);
+        let plugin = (NEXUS_PLUGIN_DECLARATION.create)();
+        assert_eq!(plugin.metadata().name, PLUGIN_NAME);
+    }
 }
@@ -0,0 +1,98 @@
+//! End-to-end test: load the built example plugin through the plugin manager
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use nexus_core::config::PluginConfig;
+use nexus_core::plugin::PluginManager;
+use nexus_core::signature::{signature_path, PluginSignature, SignedManifest};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::{Path, PathBuf};
+
+/// Locate the example plugin library cargo built alongside this test
+fn built_library() -> PathBuf {
+    let file = format!("{}nexus_plugin_example{}", DLL_PREFIX, DLL_SUFFIX);
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+
+    [deps.join(&file), deps.parent().unwrap().join(&file)]
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or_else(|| panic!("{} not found next to {:?}", file, exe))
+}
+
+/// Copy the built library into a fresh plugin directory
+fn plugin_dir() -> (tempfile::TempDir, PathBuf) {
+    let dir = tempfile::TempDir::new().unwrap();
+    let library = dir.path().join(built_library().file_name().unwrap());
+    std::fs::copy(built_library(), &library).unwrap();
+    (dir, library)
+}
+
+/// Sign the library as `version`, returning a config that trusts the signer
+fn sign(dir: &tempfile::TempDir, library: &Path, version: &str) -> PluginConfig {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let manifest =
+        SignedManifest::for_library(library, nexus_plugin_example::PLUGIN_NAME, version, "acme")
+            .unwrap();
+    let signature = PluginSignature::sign(manifest, &key_pair).unwrap();
+    std::fs::write(signature_path(library), signature.to_json().unwrap()).unwrap();
+
+    let mut config = PluginConfig {
+        plugin_dirs: vec![dir.path().to_path_buf()],
+        ..PluginConfig::default()
+    };
+    config.security_policy.trusted_publishers = vec!["acme".to_string()];
+    config.security_policy.trusted_keys.insert(
+        "acme".to_string(),
+        BASE64.encode(key_pair.public_key().as_ref()),
+    );
+    config
+}
+
+#[tokio::test]
+async fn test_load_example_plugin_from_directory() {
+    let (dir, _) = plugin_dir();
+
+    let mut config = PluginConfig {
+        plugin_dirs: vec![dir.path().to_path_buf()],
+        ..PluginConfig::default()
+    };
+    config.security_policy.require_signed = false;
+
+    let mut manager = PluginManager::new(config, None);
+    manager.load_plugins().await.unwrap();
+
+    let plugin = manager
+        .get_plugin(nexus_plugin_example::PLUGIN_NAME)
+        .expect("example plugin should be loaded");
+    assert_eq!(plugin.metadata().version, env!("CARGO_PKG_VERSION"));
+
+    manager
+        .unload_plugin(nexus_plugin_example::PLUGIN_NAME)
+        .await
+        .unwrap();
+    assert!(manager.list_plugins().is_empty());
+}
+
+#[tokio::test]
+async fn test_load_signed_example_plugin() {
+    let (dir, library) = plugin_dir();
+    let config = sign(&dir, &library, env!("CARGO_PKG_VERSION"));
+
+    let mut manager = PluginManager::new(config, None);
+    manager.load_plugins().await.unwrap();
+    assert!(manager.get_plugin(nexus_plugin_example::PLUGIN_NAME).is_some());
+}
+
+#[tokio::test]
+async fn test_signed_version_must_match_loaded_plugin() {
+    let (dir, library) = plugin_dir();
+    let config = sign(&dir, &library, "0.0.0-signed");
+
+    // Bad plugins are logged and skipped, so nothing ends up loaded
+    let mut manager = PluginManager::new(config, None);
+    manager.load_plugins().await.unwrap();
+    assert!(manager.list_plugins().is_empty());
+}